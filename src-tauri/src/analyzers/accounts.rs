@@ -0,0 +1,481 @@
+use crate::extractors::deb::{FileInfo, Scripts};
+use crate::utils::shell_words::{command_start, is_operator, script_lines, shell_words};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const SYSUSERS_DIRS: &[&str] = &["usr/lib/sysusers.d/", "lib/sysusers.d/", "etc/sysusers.d/"];
+const TMPFILES_DIRS: &[&str] = &["usr/lib/tmpfiles.d/", "lib/tmpfiles.d/", "etc/tmpfiles.d/"];
+/// Account management commands recognised in maintainer scripts
+const ACCOUNT_COMMANDS: &[&str] = &["adduser", "addgroup", "useradd", "groupadd", "systemd-sysusers"];
+
+/// Users, groups and paths a package creates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInventory {
+    pub accounts: Vec<Account>,
+    pub tmpfiles: Vec<TmpfilesEntry>,
+}
+
+impl AccountInventory {
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.tmpfiles.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountKind {
+    User,
+    Group,
+    /// An existing user added to a group
+    Membership,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub name: String,
+    pub kind: AccountKind,
+    /// Numeric id, `uid:gid`, or a path whose owner provides the id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Primary group of a user, or the group of a membership
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    pub system: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub home: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create_home: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gecos: Option<String>,
+    /// Every place that creates the account, the first one provides the settings
+    pub sources: Vec<AccountSource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSource {
+    /// Package path of a config file, or the maintainer script name
+    pub path: String,
+    pub line: usize,
+    /// sysusers.d, adduser, useradd, ...
+    pub method: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TmpfilesEntry {
+    /// Line type with modifiers, e.g. `d`, `L+`, `z`
+    pub entry_type: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub argument: Option<String>,
+    pub source: AccountSource,
+}
+
+/// Collect accounts from sysusers.d and maintainer scripts, and paths from tmpfiles.d
+pub fn inventory_accounts(files: &[FileInfo], extract_path: &Path, scripts: Option<&Scripts>) -> AccountInventory {
+    let mut inventory = AccountInventory::default();
+
+    for file in files {
+        let is_sysusers = SYSUSERS_DIRS.iter().any(|dir| in_config_dir(&file.path, dir));
+        let is_tmpfiles = TMPFILES_DIRS.iter().any(|dir| in_config_dir(&file.path, dir));
+        if !is_sysusers && !is_tmpfiles {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(extract_path.join(&file.path)) else {
+            continue;
+        };
+        if is_sysusers {
+            parse_sysusers(&content, &file.path, "sysusers.d", &mut inventory.accounts);
+        } else {
+            parse_tmpfiles(&content, &file.path, &mut inventory.tmpfiles);
+        }
+    }
+
+    if let Some(scripts) = scripts {
+        let scripts = [
+            ("preinst", &scripts.preinst),
+            ("postinst", &scripts.postinst),
+            ("config", &scripts.config),
+            ("prerm", &scripts.prerm),
+            ("postrm", &scripts.postrm),
+        ];
+        for (name, content) in scripts {
+            if let Some(content) = content {
+                scan_script(name, content, files, extract_path, &mut inventory.accounts);
+            }
+        }
+    }
+
+    inventory
+}
+
+fn in_config_dir(path: &str, dir: &str) -> bool {
+    path.strip_prefix(dir)
+        .is_some_and(|name| !name.contains('/') && name.ends_with(".conf"))
+}
+
+/// Add an account, merging with an earlier definition of the same account
+fn add_account(accounts: &mut Vec<Account>, account: Account) {
+    let existing = accounts.iter_mut().find(|a| {
+        a.kind == account.kind
+            && a.name == account.name
+            && (a.kind != AccountKind::Membership || a.group == account.group)
+    });
+    let Some(existing) = existing else {
+        accounts.push(account);
+        return;
+    };
+    existing.system |= account.system;
+    existing.id = existing.id.take().or(account.id);
+    existing.group = existing.group.take().or(account.group);
+    existing.home = existing.home.take().or(account.home);
+    existing.create_home = existing.create_home.or(account.create_home);
+    existing.shell = existing.shell.take().or(account.shell);
+    existing.gecos = existing.gecos.take().or(account.gecos);
+    existing.sources.extend(account.sources);
+}
+
+fn new_account(name: &str, kind: AccountKind, source: AccountSource) -> Account {
+    Account {
+        name: name.to_string(),
+        kind,
+        id: None,
+        group: None,
+        system: false,
+        home: None,
+        create_home: None,
+        shell: None,
+        gecos: None,
+        sources: vec![source],
+    }
+}
+
+/// `-` means "use the default" in sysusers.d and tmpfiles.d
+fn field(fields: &[String], index: usize) -> Option<String> {
+    fields.get(index).filter(|f| f.as_str() != "-").cloned()
+}
+
+/// `u name id gecos home shell`, `g name id`, `m user group`
+fn parse_sysusers(content: &str, path: &str, method: &str, accounts: &mut Vec<Account>) {
+    parse_sysusers_lines(content, path, 1, method, accounts);
+}
+
+fn parse_sysusers_lines(content: &str, path: &str, first_line: usize, method: &str, accounts: &mut Vec<Account>) {
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = shell_words(line);
+        let (Some(line_type), Some(name)) = (fields.first(), fields.get(1)) else {
+            continue;
+        };
+        let source = AccountSource {
+            path: path.to_string(),
+            line: first_line + index,
+            method: method.to_string(),
+        };
+        let id = field(&fields, 2);
+
+        match line_type.trim_end_matches('!') {
+            "u" => {
+                let mut user = new_account(name, AccountKind::User, source.clone());
+                user.system = true;
+                user.gecos = field(&fields, 3);
+                user.home = field(&fields, 4);
+                user.shell = field(&fields, 5);
+                // `uid:group` uses an existing group instead of creating one
+                let group = id.as_deref().and_then(|id| id.split_once(':')).map(|(_, g)| g.to_string());
+                let creates_group = group.as_deref().is_none_or(|g| g.chars().all(|c| c.is_ascii_digit()));
+                user.group = Some(group.filter(|_| !creates_group).unwrap_or_else(|| name.clone()));
+                user.id = id;
+                add_account(accounts, user);
+                if creates_group {
+                    let mut group = new_account(name, AccountKind::Group, source);
+                    group.system = true;
+                    add_account(accounts, group);
+                }
+            }
+            "g" => {
+                let mut group = new_account(name, AccountKind::Group, source);
+                group.system = true;
+                group.id = id;
+                add_account(accounts, group);
+            }
+            "m" => {
+                let mut membership = new_account(name, AccountKind::Membership, source);
+                membership.group = id;
+                add_account(accounts, membership);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `Type Path Mode User Group Age Argument`
+fn parse_tmpfiles(content: &str, path: &str, entries: &mut Vec<TmpfilesEntry>) {
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = shell_words(line);
+        if fields.len() < 2 {
+            continue;
+        }
+        let argument = (fields.len() > 6).then(|| fields[6..].join(" "));
+        entries.push(TmpfilesEntry {
+            entry_type: fields[0].clone(),
+            path: fields[1].clone(),
+            mode: field(&fields, 2),
+            user: field(&fields, 3),
+            group: field(&fields, 4),
+            age: field(&fields, 5),
+            argument,
+            source: AccountSource {
+                path: path.to_string(),
+                line: index + 1,
+                method: "tmpfiles.d".to_string(),
+            },
+        });
+    }
+}
+
+/// Find account management commands in a maintainer script
+fn scan_script(script: &str, content: &str, files: &[FileInfo], extract_path: &Path, accounts: &mut Vec<Account>) {
+    let lines = script_lines(content);
+    let mut index = 0;
+
+    while index < lines.len() {
+        let (line_number, ref line) = lines[index];
+        index += 1;
+        if !ACCOUNT_COMMANDS.iter().any(|c| line.contains(c)) {
+            continue;
+        }
+
+        let words = shell_words(line);
+        for (position, segment) in words.split(|w| is_operator(w)).enumerate() {
+            let Some(start) = command_start(segment, ACCOUNT_COMMANDS) else {
+                continue;
+            };
+            let command = segment[start].rsplit('/').next().unwrap_or_default();
+            let args = &segment[start + 1..];
+            let source = AccountSource {
+                path: script.to_string(),
+                line: line_number,
+                method: command.to_string(),
+            };
+
+            match command {
+                "adduser" | "addgroup" => parse_adduser(command, args, source, accounts),
+                "useradd" => parse_useradd(args, source, accounts),
+                "groupadd" => parse_groupadd(args, source, accounts),
+                _ => {
+                    // Configuration comes from files, a pipe or a here-document
+                    let mut inline = String::new();
+                    let heredoc = args.iter().position(|a| a.starts_with("<<")).map(|i| {
+                        let inline = args[i].trim_start_matches('<').trim_start_matches('-');
+                        if inline.is_empty() {
+                            args.get(i + 1).map(|a| a.as_str()).unwrap_or_default()
+                        } else {
+                            inline
+                        }
+                    });
+                    if let Some(terminator) = heredoc {
+                        while index < lines.len() && lines[index].1 != terminator {
+                            let (number, ref text) = lines[index];
+                            parse_sysusers_lines(text, script, number, command, accounts);
+                            index += 1;
+                        }
+                        index += 1;
+                    } else if position > 0 {
+                        let piped = words.split(|w| is_operator(w)).nth(position - 1).unwrap_or_default();
+                        if piped.first().is_some_and(|w| w == "echo" || w == "printf") {
+                            inline = piped[1..]
+                                .iter()
+                                .filter(|w| !w.starts_with('-'))
+                                .map(|w| w.replace("\\n", "\n"))
+                                .collect::<Vec<_>>()
+                                .join(" ");
+                        }
+                    }
+                    if !inline.is_empty() {
+                        parse_sysusers_lines(&inline, script, line_number, command, accounts);
+                    }
+                    for config in args.iter().filter(|a| a.ends_with(".conf")) {
+                        let name = config.rsplit('/').next().unwrap_or_default();
+                        let packaged = files.iter().find(|f| {
+                            SYSUSERS_DIRS.iter().any(|dir| f.path.strip_prefix(dir) == Some(name))
+                        });
+                        if let Some(Ok(content)) = packaged.map(|f| fs::read_to_string(extract_path.join(&f.path))) {
+                            let mut parsed = Vec::new();
+                            parse_sysusers(&content, script, command, &mut parsed);
+                            for mut account in parsed {
+                                for source in account.sources.iter_mut() {
+                                    source.line = line_number;
+                                }
+                                add_account(accounts, account);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Split `--opt=value` and pair options with their values
+fn parse_options<'a>(args: &'a [String], with_value: &[&str]) -> (Vec<(String, Option<String>)>, Vec<&'a str>) {
+    let mut options = Vec::new();
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg.starts_with('>') || arg.starts_with('<') || arg.starts_with("2>") {
+            continue;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            positional.push(arg.as_str());
+            continue;
+        }
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let value = if inline_value.is_some() {
+            inline_value
+        } else if with_value.contains(&name.as_str()) {
+            iter.next().cloned()
+        } else {
+            None
+        };
+        options.push((name, value));
+    }
+
+    (options, positional)
+}
+
+fn option_value(options: &[(String, Option<String>)], names: &[&str]) -> Option<String> {
+    options
+        .iter()
+        .rev()
+        .find(|(name, _)| names.contains(&name.as_str()))
+        .and_then(|(_, value)| value.clone())
+}
+
+fn has_option(options: &[(String, Option<String>)], names: &[&str]) -> bool {
+    options.iter().any(|(name, _)| names.contains(&name.as_str()))
+}
+
+/// Debian `adduser`/`addgroup`, including the `adduser user group` membership form
+fn parse_adduser(command: &str, args: &[String], source: AccountSource, accounts: &mut Vec<Account>) {
+    const WITH_VALUE: &[&str] = &[
+        "--home", "--shell", "--gecos", "--comment", "--ingroup", "--uid", "--gid", "--firstuid", "--lastuid",
+        "--firstgid", "--lastgid", "--conf", "--add-extra-groups", "--extrausers",
+    ];
+    let (options, positional) = parse_options(args, WITH_VALUE);
+    let system = has_option(&options, &["--system"]);
+
+    match (positional.as_slice(), command) {
+        ([user, group], _) => {
+            let mut membership = new_account(user, AccountKind::Membership, source);
+            membership.group = Some(group.to_string());
+            add_account(accounts, membership);
+        }
+        ([name], "addgroup") => {
+            let mut group = new_account(name, AccountKind::Group, source);
+            group.system = system;
+            group.id = option_value(&options, &["--gid"]);
+            add_account(accounts, group);
+        }
+        ([name], _) if has_option(&options, &["--group"]) && !system => {
+            // `adduser --group name` without --system is addgroup
+            let mut group = new_account(name, AccountKind::Group, source);
+            group.id = option_value(&options, &["--gid"]);
+            add_account(accounts, group);
+        }
+        ([name], _) => {
+            let mut user = new_account(name, AccountKind::User, source.clone());
+            user.system = system;
+            user.id = option_value(&options, &["--uid"]);
+            user.home = option_value(&options, &["--home"]);
+            user.shell = option_value(&options, &["--shell"]);
+            user.gecos = option_value(&options, &["--gecos", "--comment"]);
+            user.create_home = Some(!has_option(&options, &["--no-create-home"]));
+            let own_group = has_option(&options, &["--group"]);
+            user.group = option_value(&options, &["--ingroup", "--gid"]).or_else(|| own_group.then(|| name.to_string()));
+            add_account(accounts, user);
+            if own_group {
+                let mut group = new_account(name, AccountKind::Group, source);
+                group.system = system;
+                add_account(accounts, group);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_useradd(args: &[String], source: AccountSource, accounts: &mut Vec<Account>) {
+    const WITH_VALUE: &[&str] = &[
+        "-b", "--base-dir", "-c", "--comment", "-d", "--home-dir", "-e", "--expiredate", "-f", "--inactive", "-g",
+        "--gid", "-G", "--groups", "-k", "--skel", "-K", "--key", "-p", "--password", "-R", "--root", "-P",
+        "--prefix", "-s", "--shell", "-u", "--uid", "-Z", "--selinux-user",
+    ];
+    let (options, positional) = parse_options(args, WITH_VALUE);
+    let Some(name) = positional.last() else {
+        return;
+    };
+
+    let mut user = new_account(name, AccountKind::User, source.clone());
+    user.system = has_option(&options, &["-r", "--system"]);
+    user.id = option_value(&options, &["-u", "--uid"]);
+    user.home = option_value(&options, &["-d", "--home-dir"]);
+    user.shell = option_value(&options, &["-s", "--shell"]);
+    user.gecos = option_value(&options, &["-c", "--comment"]);
+    if has_option(&options, &["-m", "--create-home"]) {
+        user.create_home = Some(true);
+    } else if has_option(&options, &["-M", "--no-create-home"]) {
+        user.create_home = Some(false);
+    }
+    let own_group = has_option(&options, &["-U", "--user-group"]);
+    user.group = option_value(&options, &["-g", "--gid"]).or_else(|| own_group.then(|| name.to_string()));
+    let system = user.system;
+    add_account(accounts, user);
+
+    if own_group {
+        let mut group = new_account(name, AccountKind::Group, source.clone());
+        group.system = system;
+        add_account(accounts, group);
+    }
+    for group in option_value(&options, &["-G", "--groups"]).unwrap_or_default().split(',').filter(|g| !g.is_empty()) {
+        let mut membership = new_account(name, AccountKind::Membership, source.clone());
+        membership.group = Some(group.to_string());
+        add_account(accounts, membership);
+    }
+}
+
+fn parse_groupadd(args: &[String], source: AccountSource, accounts: &mut Vec<Account>) {
+    const WITH_VALUE: &[&str] = &["-g", "--gid", "-K", "--key", "-p", "--password", "-R", "--root", "-P", "--prefix"];
+    let (options, positional) = parse_options(args, WITH_VALUE);
+    let Some(name) = positional.last() else {
+        return;
+    };
+    let mut group = new_account(name, AccountKind::Group, source);
+    group.system = has_option(&options, &["-r", "--system"]);
+    group.id = option_value(&options, &["-g", "--gid"]);
+    add_account(accounts, group);
+}
@@ -0,0 +1,329 @@
+use crate::analyzers::desktop::locate_program;
+use crate::extractors::deb::Scripts;
+use crate::utils::shell_words::{command_start, is_operator, script_lines, shell_words};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const HOOK_COMMANDS: &[&str] = &["update-alternatives", "dpkg-divert"];
+
+/// Alternatives and diversions set up by maintainer scripts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlternativesInventory {
+    pub alternatives: Vec<Alternative>,
+    pub diversions: Vec<Diversion>,
+}
+
+impl AlternativesInventory {
+    pub fn is_empty(&self) -> bool {
+        self.alternatives.is_empty() && self.diversions.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptLocation {
+    pub script: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alternative {
+    /// install, remove, remove-all, set, auto, ...
+    pub action: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    /// Whether `path` ships in this package, None when there is no path or it is not literal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipped: Option<bool>,
+    pub slaves: Vec<AlternativeSlave>,
+    pub source: ScriptLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlternativeSlave {
+    pub link: String,
+    pub name: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipped: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diversion {
+    /// add or remove
+    pub action: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub divert_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    pub local: bool,
+    pub rename: bool,
+    /// Whether the package ships its own file at the diverted path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipped: Option<bool>,
+    pub source: ScriptLocation,
+}
+
+/// Find update-alternatives and dpkg-divert calls in maintainer scripts and triggers
+pub fn inventory_alternatives(scripts: &Scripts, extract_path: &Path) -> AlternativesInventory {
+    let mut inventory = AlternativesInventory::default();
+    let scripts = [
+        ("preinst", &scripts.preinst),
+        ("postinst", &scripts.postinst),
+        ("prerm", &scripts.prerm),
+        ("postrm", &scripts.postrm),
+        ("config", &scripts.config),
+        ("triggers", &scripts.triggers),
+    ];
+
+    for (script, content) in scripts {
+        let Some(content) = content else {
+            continue;
+        };
+        for (line, text) in script_lines(content) {
+            if !HOOK_COMMANDS.iter().any(|c| text.contains(c)) {
+                continue;
+            }
+            let words = shell_words(&text);
+            for segment in words.split(|w| is_operator(w)) {
+                let Some(start) = command_start(segment, HOOK_COMMANDS) else {
+                    continue;
+                };
+                let source = ScriptLocation {
+                    script: script.to_string(),
+                    line,
+                };
+                let args = &segment[start + 1..];
+                if segment[start].ends_with("update-alternatives") {
+                    inventory.alternatives.extend(parse_update_alternatives(args, source, extract_path));
+                } else if let Some(diversion) = parse_dpkg_divert(args, source, extract_path) {
+                    inventory.diversions.push(diversion);
+                }
+            }
+        }
+    }
+
+    inventory
+}
+
+/// Whether a literal absolute path ships in the package
+fn shipped(path: &str, extract_path: &Path) -> Option<bool> {
+    if !path.starts_with('/') || path.contains('$') {
+        return None;
+    }
+    Some(locate_program(path, extract_path).found)
+}
+
+fn is_redirection(arg: &str) -> bool {
+    arg.starts_with('>') || arg.starts_with('<') || arg.starts_with("2>") || arg.starts_with("1>")
+}
+
+/// `update-alternatives [options] --install link name path priority [--slave link name path]...`
+fn parse_update_alternatives(args: &[String], source: ScriptLocation, extract_path: &Path) -> Vec<Alternative> {
+    const OPTIONS_WITH_VALUE: &[&str] = &["--altdir", "--admindir", "--instdir", "--root", "--log"];
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).filter(|a| !is_redirection(a)).collect();
+    let mut alternatives: Vec<Alternative> = Vec::new();
+    let mut index = 0;
+
+    let new_alternative = |action: &str, name: &str| Alternative {
+        action: action.to_string(),
+        name: name.to_string(),
+        link: None,
+        path: None,
+        priority: None,
+        shipped: None,
+        slaves: Vec::new(),
+        source: source.clone(),
+    };
+
+    while index < args.len() {
+        let arg = args[index];
+        let rest = &args[index + 1..];
+        index += 1;
+        match arg {
+            "--install" if rest.len() >= 4 => {
+                let mut alternative = new_alternative("install", rest[1]);
+                alternative.link = Some(rest[0].to_string());
+                alternative.path = Some(rest[2].to_string());
+                alternative.priority = Some(rest[3].to_string());
+                alternative.shipped = shipped(rest[2], extract_path);
+                alternatives.push(alternative);
+                index += 4;
+            }
+            "--slave" | "--follower" if rest.len() >= 3 => {
+                if let Some(alternative) = alternatives.last_mut().filter(|a| a.action == "install") {
+                    alternative.slaves.push(AlternativeSlave {
+                        link: rest[0].to_string(),
+                        name: rest[1].to_string(),
+                        path: rest[2].to_string(),
+                        shipped: shipped(rest[2], extract_path),
+                    });
+                }
+                index += 3;
+            }
+            "--remove" | "--set" if rest.len() >= 2 => {
+                let mut alternative = new_alternative(arg.trim_start_matches('-'), rest[0]);
+                alternative.path = Some(rest[1].to_string());
+                alternative.shipped = shipped(rest[1], extract_path);
+                alternatives.push(alternative);
+                index += 2;
+            }
+            "--remove-all" | "--auto" if !rest.is_empty() => {
+                alternatives.push(new_alternative(arg.trim_start_matches('-'), rest[0]));
+                index += 1;
+            }
+            _ if OPTIONS_WITH_VALUE.contains(&arg) => index += 1,
+            _ => {}
+        }
+    }
+
+    alternatives
+}
+
+/// `dpkg-divert [--add|--remove] [--package pkg|--local] [--divert to] [--rename] file`
+fn parse_dpkg_divert(args: &[String], source: ScriptLocation, extract_path: &Path) -> Option<Diversion> {
+    const OPTIONS_WITH_VALUE: &[&str] = &["--package", "--divert", "--admindir", "--instdir", "--root"];
+    let mut action = "add";
+    let mut package = None;
+    let mut divert_to = None;
+    let mut local = false;
+    let mut rename = false;
+    let mut path = None;
+    let mut iter = args.iter().map(|a| a.as_str()).filter(|a| !is_redirection(a));
+
+    while let Some(arg) = iter.next() {
+        let (option, inline) = match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => (option, Some(value.to_string())),
+            _ => (arg, None),
+        };
+        let value = if OPTIONS_WITH_VALUE.contains(&option) {
+            inline.or_else(|| iter.next().map(|v| v.to_string()))
+        } else {
+            None
+        };
+        match option {
+            "--add" => action = "add",
+            "--remove" => action = "remove",
+            // Queries do not change anything
+            "--list" | "--listpackage" | "--truename" => return None,
+            "--package" => package = value,
+            "--divert" => divert_to = value,
+            "--local" => local = true,
+            "--rename" => rename = true,
+            "--no-rename" => rename = false,
+            _ if option.starts_with('-') => {}
+            _ => path = Some(arg.to_string()),
+        }
+    }
+
+    let path = path?;
+    Some(Diversion {
+        action: action.to_string(),
+        shipped: shipped(&path, extract_path),
+        path,
+        divert_to,
+        package,
+        local,
+        rename,
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|w| w.to_string()).collect()
+    }
+
+    fn source() -> ScriptLocation {
+        ScriptLocation {
+            script: "postinst".to_string(),
+            line: 3,
+        }
+    }
+
+    #[test]
+    fn consumes_install_and_slave_arguments() {
+        let alternatives = parse_update_alternatives(
+            &args(
+                "--quiet --altdir /etc/alternatives --install /usr/bin/editor editor /usr/bin/vim.basic 30 \
+                 --slave /usr/share/man/man1/editor.1.gz editor.1.gz /usr/share/man/man1/vim.1.gz \
+                 --follower /usr/bin/view view /usr/bin/vim.view >/dev/null",
+            ),
+            source(),
+            Path::new("/nonexistent"),
+        );
+        assert_eq!(alternatives.len(), 1);
+        let editor = &alternatives[0];
+        assert_eq!((editor.action.as_str(), editor.name.as_str()), ("install", "editor"));
+        assert_eq!(editor.link.as_deref(), Some("/usr/bin/editor"));
+        assert_eq!(editor.path.as_deref(), Some("/usr/bin/vim.basic"));
+        assert_eq!(editor.priority.as_deref(), Some("30"));
+        assert_eq!(editor.shipped, Some(false));
+        let slaves: Vec<(&str, &str, &str)> = editor
+            .slaves
+            .iter()
+            .map(|s| (s.link.as_str(), s.name.as_str(), s.path.as_str()))
+            .collect();
+        assert_eq!(
+            slaves,
+            vec![
+                ("/usr/share/man/man1/editor.1.gz", "editor.1.gz", "/usr/share/man/man1/vim.1.gz"),
+                ("/usr/bin/view", "view", "/usr/bin/vim.view"),
+            ]
+        );
+    }
+
+    #[test]
+    fn operands_are_not_read_as_actions() {
+        let alternatives = parse_update_alternatives(
+            &args("--install /usr/bin/auto --auto /opt/auto 10 --remove editor /usr/bin/vim.basic --auto pager"),
+            source(),
+            Path::new("/nonexistent"),
+        );
+        let actions: Vec<(&str, &str)> = alternatives
+            .iter()
+            .map(|a| (a.action.as_str(), a.name.as_str()))
+            .collect();
+        assert_eq!(actions, vec![("install", "--auto"), ("remove", "editor"), ("auto", "pager")]);
+        assert!(alternatives[1].slaves.is_empty());
+    }
+
+    #[test]
+    fn parses_dpkg_divert() {
+        let diversion = parse_dpkg_divert(
+            &args("--package foo --divert=/usr/bin/tool.real --rename --add /usr/bin/tool"),
+            source(),
+            Path::new("/nonexistent"),
+        )
+        .unwrap();
+        assert_eq!(diversion.action, "add");
+        assert_eq!(diversion.path, "/usr/bin/tool");
+        assert_eq!(diversion.divert_to.as_deref(), Some("/usr/bin/tool.real"));
+        assert_eq!(diversion.package.as_deref(), Some("foo"));
+        assert!(diversion.rename && !diversion.local);
+
+        let removal = parse_dpkg_divert(
+            &args("--local --remove --no-rename /etc/foo.conf"),
+            source(),
+            Path::new("/nonexistent"),
+        )
+        .unwrap();
+        assert_eq!(removal.action, "remove");
+        assert!(removal.local && !removal.rename);
+
+        assert!(parse_dpkg_divert(&args("--truename /usr/bin/tool"), source(), Path::new("/nonexistent")).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::analyzers::desktop::locate_program;
+use crate::extractors::appstream::AppStreamComponent;
+use crate::extractors::deb::upstream_version;
+use crate::extractors::desktop::DesktopEntry;
+use crate::utils::issue::{Issue, IssueSeverity};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Cross-check a metainfo component against the desktop entries, files and version of the package
+pub fn cross_check_appstream(
+    component: &mut AppStreamComponent,
+    path: &str,
+    desktop_info: &HashMap<String, DesktopEntry>,
+    package_version: &str,
+    extract_path: &Path,
+) {
+    let mut issues = Vec::new();
+    let mut push = |severity: IssueSeverity, message: String| issues.push(Issue { severity, message });
+
+    if path.starts_with("usr/share/appdata/") {
+        push(
+            IssueSeverity::Warning,
+            "Metainfo is installed in the legacy usr/share/appdata directory".to_string(),
+        );
+    }
+    if let Some(id) = &component.id {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        if file_name != format!("{}.metainfo.xml", id) && file_name != format!("{}.appdata.xml", id) {
+            push(
+                IssueSeverity::Hint,
+                format!("File name \"{}\" does not match component id \"{}\"", file_name, id),
+            );
+        }
+    }
+
+    for launchable in component.launchables.iter().filter(|l| l.launchable_type == "desktop-id") {
+        let desktop_path = format!("usr/share/applications/{}", launchable.value);
+        let Some(entry) = desktop_info.get(&desktop_path) else {
+            push(
+                IssueSeverity::Error,
+                format!("Launchable desktop entry \"{}\" is not shipped in the package", launchable.value),
+            );
+            continue;
+        };
+
+        if let (Some(name), Some(desktop_name)) = (&component.name, &entry.name) {
+            if name.value != desktop_name.value {
+                push(
+                    IssueSeverity::Hint,
+                    format!(
+                        "Name \"{}\" differs from desktop entry Name \"{}\"",
+                        name.value, desktop_name.value
+                    ),
+                );
+            }
+        }
+        for mediatype in &component.provides.mediatypes {
+            if !entry.mime_types.contains(mediatype) {
+                push(
+                    IssueSeverity::Warning,
+                    format!(
+                        "Provided media type \"{}\" is missing from the MimeType of {}",
+                        mediatype, launchable.value
+                    ),
+                );
+            }
+        }
+    }
+
+    for binary in &component.provides.binaries {
+        if !locate_program(binary, extract_path).found {
+            push(
+                IssueSeverity::Error,
+                format!("Provided binary \"{}\" is not shipped in the package", binary),
+            );
+        }
+    }
+
+    // The newest release should be the packaged upstream version
+    let upstream = upstream_version(package_version);
+    let latest = component
+        .releases
+        .iter()
+        .filter(|r| r.date.is_some())
+        .max_by(|a, b| a.date.cmp(&b.date))
+        .or(component.releases.first());
+    if let Some(latest) = latest.and_then(|r| r.version.as_deref()) {
+        // Repacks like 1.2+dfsg still match release 1.2
+        let matches = upstream == latest
+            || upstream.strip_prefix(latest).is_some_and(|rest| rest.starts_with(['+', '~']));
+        if !matches {
+            push(
+                IssueSeverity::Warning,
+                format!(
+                    "Latest release {} does not match the packaged upstream version {}",
+                    latest, upstream
+                ),
+            );
+        }
+    } else if component.releases.is_empty() {
+        push(IssueSeverity::Hint, "No <releases> listed".to_string());
+    }
+
+    component.issues.extend(issues);
+}
@@ -0,0 +1,204 @@
+use crate::extractors::elf::ELFInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Expected ELF properties for a Debian architecture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchTuple {
+    /// Machine name as printed by `readelf -h`
+    pub machine: String,
+    pub class: String,
+    pub endian: String,
+    /// Required ARM float ABI, "hard" or "soft"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub float_abi: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchitectureCheck {
+    pub declared: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<ArchTuple>,
+    pub issues: Vec<ArchIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchIssue {
+    pub kind: ArchIssueKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchIssueKind {
+    /// `Architecture: all` package that ships ELF objects
+    ArchAllWithElf,
+    MachineMismatch,
+    ClassMismatch,
+    EndianMismatch,
+    FloatAbiMismatch,
+    /// ELF objects for more than one machine/class in the payload
+    MixedArchitectures,
+    /// Declared architecture is not in the tuple table
+    UnknownArchitecture,
+}
+
+/// Look up the ELF properties of a Debian architecture name
+///
+/// OS-prefixed names such as `hurd-i386` or `kfreebsd-amd64` resolve to
+/// their CPU part.
+pub fn arch_tuple(arch: &str) -> Option<ArchTuple> {
+    let cpu = arch
+        .strip_prefix("hurd-")
+        .or_else(|| arch.strip_prefix("kfreebsd-"))
+        .or_else(|| arch.strip_prefix("musl-linux-"))
+        .unwrap_or(arch);
+
+    let (machine, bits, little, float_abi) = match cpu {
+        "amd64" => ("Advanced Micro Devices X86-64", 64, true, None),
+        "x32" => ("Advanced Micro Devices X86-64", 32, true, None),
+        "i386" => ("Intel 80386", 32, true, None),
+        "arm64" => ("AArch64", 64, true, None),
+        "armhf" => ("ARM", 32, true, Some("hard")),
+        "armel" => ("ARM", 32, true, Some("soft")),
+        "ppc64el" => ("PowerPC64", 64, true, None),
+        "ppc64" => ("PowerPC64", 64, false, None),
+        "powerpc" => ("PowerPC", 32, false, None),
+        "s390x" => ("IBM S/390", 64, false, None),
+        "mips64el" => ("MIPS R3000", 64, true, None),
+        "mipsel" => ("MIPS R3000", 32, true, None),
+        "mips" => ("MIPS R3000", 32, false, None),
+        "riscv64" => ("RISC-V", 64, true, None),
+        "loong64" => ("LoongArch", 64, true, None),
+        "sparc64" => ("Sparc v9", 64, false, None),
+        "alpha" => ("Alpha", 64, true, None),
+        "hppa" => ("HPPA", 32, false, None),
+        "m68k" => ("MC68000", 32, false, None),
+        "sh4" => ("Renesas / SuperH SH", 32, true, None),
+        "ia64" => ("Intel IA-64", 64, true, None),
+        _ => return None,
+    };
+
+    Some(ArchTuple {
+        machine: machine.to_string(),
+        class: format!("ELF{}", bits),
+        endian: if little { "little" } else { "big" }.to_string(),
+        float_abi: float_abi.map(|abi| abi.to_string()),
+    })
+}
+
+/// Float ABI encoded in the ARM e_flags, if readelf reported one
+fn arm_float_abi(flags: &str) -> Option<&'static str> {
+    if flags.contains("hard-float ABI") {
+        Some("hard")
+    } else if flags.contains("soft-float ABI") {
+        Some("soft")
+    } else {
+        None
+    }
+}
+
+/// Compare the ELF objects in the payload against the declared architecture
+pub fn check_architecture(declared: &str, elf_info: &HashMap<String, ELFInfo>) -> ArchitectureCheck {
+    let mut issues = Vec::new();
+    let expected = arch_tuple(declared);
+
+    // Sort for stable output
+    let mut paths: Vec<&String> = elf_info.keys().collect();
+    paths.sort();
+
+    if declared == "all" {
+        for path in &paths {
+            issues.push(ArchIssue {
+                kind: ArchIssueKind::ArchAllWithElf,
+                path: Some(path.to_string()),
+                message: format!(
+                    "Architecture: all package contains an ELF object for {}",
+                    elf_info[*path].machine
+                ),
+            });
+        }
+    } else if expected.is_none() && declared != "any" && !declared.is_empty() {
+        issues.push(ArchIssue {
+            kind: ArchIssueKind::UnknownArchitecture,
+            path: None,
+            message: format!("Unknown Debian architecture '{}'", declared),
+        });
+    }
+
+    if let Some(expected) = &expected {
+        for path in &paths {
+            let info = &elf_info[*path];
+            if info.machine != expected.machine {
+                issues.push(ArchIssue {
+                    kind: ArchIssueKind::MachineMismatch,
+                    path: Some(path.to_string()),
+                    message: format!(
+                        "Machine {} does not match {} (expected {})",
+                        info.machine, declared, expected.machine
+                    ),
+                });
+                // Class and ABI comparisons are meaningless across machines
+                continue;
+            }
+            if info.class != expected.class {
+                issues.push(ArchIssue {
+                    kind: ArchIssueKind::ClassMismatch,
+                    path: Some(path.to_string()),
+                    message: format!(
+                        "{} object in a {} package (expected {})",
+                        info.class, declared, expected.class
+                    ),
+                });
+            }
+            if info.endian != expected.endian {
+                issues.push(ArchIssue {
+                    kind: ArchIssueKind::EndianMismatch,
+                    path: Some(path.to_string()),
+                    message: format!(
+                        "{} endian object in a {} package (expected {} endian)",
+                        info.endian, declared, expected.endian
+                    ),
+                });
+            }
+            if let (Some(required), Some(actual)) = (&expected.float_abi, arm_float_abi(&info.flags)) {
+                if required != actual {
+                    issues.push(ArchIssue {
+                        kind: ArchIssueKind::FloatAbiMismatch,
+                        path: Some(path.to_string()),
+                        message: format!(
+                            "{}-float ABI object in a {} package (expected {}-float)",
+                            actual, declared, required
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    let variants: BTreeSet<String> = elf_info
+        .values()
+        .map(|info| format!("{} {}", info.machine, info.class))
+        .collect();
+    if variants.len() > 1 {
+        issues.push(ArchIssue {
+            kind: ArchIssueKind::MixedArchitectures,
+            path: None,
+            message: format!(
+                "Payload mixes ELF objects for {}",
+                variants.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+        });
+    }
+
+    ArchitectureCheck {
+        declared: declared.to_string(),
+        expected,
+        issues,
+    }
+}
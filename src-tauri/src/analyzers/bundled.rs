@@ -0,0 +1,420 @@
+use crate::extractors::deb::{FileInfo, FileType};
+use flate2::read::DeflateDecoder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Manifests bigger than this are not metadata
+const MAX_MANIFEST_SIZE: u64 = 1024 * 1024;
+
+const ZIP_END_OF_CENTRAL_DIRECTORY: u32 = 0x06054b50;
+const ZIP_CENTRAL_DIRECTORY_HEADER: u32 = 0x02014b50;
+const ZIP_LOCAL_HEADER: u32 = 0x04034b50;
+/// End of central directory record plus the longest possible comment
+const ZIP_TAIL_SEARCH: u64 = 22 + 0xffff;
+/// Stands in for a size or offset that only the ZIP64 extra field holds
+const ZIP64_SENTINEL: u32 = 0xffff_ffff;
+
+/// A dependency vendored into the package by a language package manager
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledComponent {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Installed manifest, or the jar the component was read from
+    pub path: String,
+    /// Entry inside the jar, for components shaded into another jar
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+}
+
+fn npm_manifest_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?:^|/)node_modules/(?:@[^/]+/)?[^/@][^/]*/package\.json$").unwrap())
+}
+
+fn gem_licenses_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\.licenses?\s*=\s*\[?([^\n\]]*)").unwrap())
+}
+
+fn quoted_string_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"["']([^"']+)["']"#).unwrap())
+}
+
+fn gem_name_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\.name\s*=\s*["']([^"']+)["']"#).unwrap())
+}
+
+fn gem_version_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\.version\s*=\s*(?:Gem::Version\.new\()?["']([^"']+)["']"#).unwrap())
+}
+
+/// Find npm, Python, Ruby and Java components, keyed by ecosystem
+pub fn inventory_bundled(files: &[FileInfo], extract_path: &Path) -> BTreeMap<String, Vec<BundledComponent>> {
+    let mut ecosystems: BTreeMap<String, Vec<BundledComponent>> = BTreeMap::new();
+
+    for file in files.iter().filter(|f| f.file_type != FileType::Directory && f.file_type != FileType::Symlink) {
+        let path = file.path.as_str();
+        let full_path = extract_path.join(path);
+        let (ecosystem, components) = if npm_manifest_regex().is_match(path) {
+            ("npm", read_manifest(&full_path).and_then(|m| npm_component(&m, path)).into_iter().collect())
+        } else if path.ends_with(".dist-info/METADATA") || path.ends_with(".egg-info/PKG-INFO") || path.ends_with(".egg-info") {
+            ("pypi", read_manifest(&full_path).and_then(|m| python_component(&m, path)).into_iter().collect())
+        } else if path.ends_with(".gemspec") {
+            ("gem", read_manifest(&full_path).and_then(|m| gem_component(&m, path)).into_iter().collect())
+        } else if path.ends_with(".jar") {
+            ("maven", jar_components(&full_path, path))
+        } else if path.contains("META-INF/maven/") && path.ends_with("/pom.properties") {
+            // Jars unpacked into the payload
+            let component = read_manifest(&full_path).and_then(|m| pom_component(&m, path, None));
+            ("maven", component.into_iter().collect())
+        } else {
+            continue;
+        };
+        if !components.is_empty() {
+            ecosystems.entry(ecosystem.to_string()).or_default().extend(components);
+        }
+    }
+
+    for components in ecosystems.values_mut() {
+        components.sort_by(|a, b| a.name.cmp(&b.name).then(a.version.cmp(&b.version)));
+    }
+    ecosystems
+}
+
+fn read_manifest(path: &Path) -> Option<String> {
+    if fs::metadata(path).ok()?.len() > MAX_MANIFEST_SIZE {
+        return None;
+    }
+    fs::read(path).ok().map(|data| String::from_utf8_lossy(&data).into_owned())
+}
+
+/// `node_modules/<name>/package.json`
+fn npm_component(manifest: &str, path: &str) -> Option<BundledComponent> {
+    let json: serde_json::Value = serde_json::from_str(manifest).ok()?;
+    let name = json.get("name")?.as_str()?.to_string();
+    let version = json.get("version").and_then(|v| v.as_str()).map(|v| v.to_string());
+    // "license": "MIT", the old {"type": "MIT"} form, or a "licenses" array of those
+    let license_name = |value: &serde_json::Value| {
+        value
+            .as_str()
+            .or_else(|| value.get("type").and_then(|t| t.as_str()))
+            .map(|l| l.to_string())
+    };
+    let license = json.get("license").and_then(license_name).or_else(|| {
+        let licenses: Vec<String> = json.get("licenses")?.as_array()?.iter().filter_map(license_name).collect();
+        match licenses.len() {
+            0 => None,
+            1 => licenses.into_iter().next(),
+            _ => Some(format!("({})", licenses.join(" OR "))),
+        }
+    });
+    let purl = version
+        .as_ref()
+        .map(|version| format!("pkg:npm/{}@{}", name.replace('@', "%40"), version));
+
+    Some(BundledComponent {
+        name,
+        version,
+        license,
+        path: path.to_string(),
+        entry: None,
+        purl,
+    })
+}
+
+/// Core metadata headers from `METADATA` or `PKG-INFO`
+fn python_component(manifest: &str, path: &str) -> Option<BundledComponent> {
+    let mut headers: HashMap<String, String> = HashMap::new();
+    let mut classifiers = Vec::new();
+    for line in manifest.lines() {
+        // The body after the first blank line is the long description
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        if key == "Classifier" {
+            classifiers.push(value);
+        } else {
+            headers.entry(key.to_string()).or_insert(value);
+        }
+    }
+
+    let name = headers.remove("Name")?;
+    let version = headers.remove("Version");
+    // Older packages put the whole licence text in License
+    let license = headers
+        .remove("License-Expression")
+        .or_else(|| headers.remove("License").filter(|l| !l.is_empty() && l != "UNKNOWN" && l.len() <= 64))
+        .or_else(|| {
+            classifiers
+                .iter()
+                .filter_map(|c| c.strip_prefix("License :: "))
+                .map(|c| c.rsplit(" :: ").next().unwrap_or(c).to_string())
+                .next()
+        });
+    let normalized = name.to_lowercase().replace(['_', '.'], "-");
+    let purl = version.as_ref().map(|version| format!("pkg:pypi/{}@{}", normalized, version));
+
+    Some(BundledComponent {
+        name,
+        version,
+        license,
+        path: path.to_string(),
+        entry: None,
+        purl,
+    })
+}
+
+/// Installed `specifications/*.gemspec`, falling back to the `<name>-<version>.gemspec` file name
+fn gem_component(manifest: &str, path: &str) -> Option<BundledComponent> {
+    let licenses: Vec<String> = gem_licenses_regex()
+        .captures(manifest)
+        .map(|c| quoted_string_regex().captures_iter(&c[1]).map(|l| l[1].to_string()).collect())
+        .unwrap_or_default();
+
+    let file_name = path.rsplit('/').next()?.trim_end_matches(".gemspec");
+    let from_file_name = file_name
+        .rsplit_once('-')
+        .filter(|(_, version)| version.starts_with(|c: char| c.is_ascii_digit()));
+    let name = gem_name_regex()
+        .captures(manifest)
+        .map(|c| c[1].to_string())
+        .or_else(|| from_file_name.map(|(name, _)| name.to_string()))
+        .unwrap_or_else(|| file_name.to_string());
+    let version = gem_version_regex()
+        .captures(manifest)
+        .map(|c| c[1].to_string())
+        .or_else(|| from_file_name.map(|(_, version)| version.to_string()));
+    let license = match licenses.len() {
+        0 => None,
+        1 => licenses.into_iter().next(),
+        _ => Some(format!("({})", licenses.join(" OR "))),
+    };
+    let purl = version.as_ref().map(|version| format!("pkg:gem/{}@{}", name, version));
+
+    Some(BundledComponent {
+        name,
+        version,
+        license,
+        path: path.to_string(),
+        entry: None,
+        purl,
+    })
+}
+
+/// Every `pom.properties` in a jar is a component, shaded jars carry several
+fn jar_components(full_path: &Path, path: &str) -> Vec<BundledComponent> {
+    let entries = read_zip_entries(full_path, |name| {
+        name == "META-INF/MANIFEST.MF" || (name.starts_with("META-INF/maven/") && name.ends_with("/pom.properties"))
+    });
+    let manifest = entries
+        .iter()
+        .find(|(name, _)| name == "META-INF/MANIFEST.MF")
+        .map(|(_, data)| manifest_attributes(&String::from_utf8_lossy(data)))
+        .unwrap_or_default();
+    let license = manifest.get("Bundle-License").map(|l| l.split(';').next().unwrap_or(l).to_string());
+
+    let mut components: Vec<BundledComponent> = entries
+        .iter()
+        .filter(|(name, _)| name.ends_with("/pom.properties"))
+        .filter_map(|(name, data)| pom_component(&String::from_utf8_lossy(data), path, Some(name)))
+        .collect();
+    // Only the jar's own artifact gets the licence from its manifest
+    let file_name = path.rsplit('/').next().unwrap_or(path).trim_end_matches(".jar");
+    let single = components.len() == 1;
+    let own = components.iter_mut().find(|component| {
+        let artifact = component.name.rsplit(':').next().unwrap_or_default();
+        single || file_name == artifact || file_name.starts_with(&format!("{}-", artifact))
+    });
+    if let Some(component) = own {
+        component.license = license.clone();
+    }
+    if !components.is_empty() {
+        return components;
+    }
+
+    // No Maven metadata: OSGi or plain manifest attributes, then the file name
+    let from_file_name = file_name
+        .rsplit_once('-')
+        .filter(|(_, version)| version.starts_with(|c: char| c.is_ascii_digit()));
+    let name = manifest
+        .get("Bundle-SymbolicName")
+        .map(|n| n.split(';').next().unwrap_or(n).trim().to_string())
+        .or_else(|| manifest.get("Automatic-Module-Name").cloned())
+        .or_else(|| manifest.get("Implementation-Title").cloned())
+        .or_else(|| from_file_name.map(|(name, _)| name.to_string()))
+        .unwrap_or_else(|| file_name.to_string());
+    let version = manifest
+        .get("Bundle-Version")
+        .or_else(|| manifest.get("Implementation-Version"))
+        .cloned()
+        .or_else(|| from_file_name.map(|(_, version)| version.to_string()));
+
+    vec![BundledComponent {
+        name,
+        version,
+        license,
+        path: path.to_string(),
+        entry: None,
+        purl: None,
+    }]
+}
+
+/// `groupId`, `artifactId` and `version` from a Maven `pom.properties`
+fn pom_component(properties: &str, path: &str, entry: Option<&str>) -> Option<BundledComponent> {
+    let properties: HashMap<&str, &str> = properties
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect();
+    let group = properties.get("groupId")?;
+    let artifact = properties.get("artifactId")?;
+    let version = properties.get("version").map(|v| v.to_string());
+    let purl = version
+        .as_ref()
+        .map(|version| format!("pkg:maven/{}/{}@{}", group, artifact, version));
+
+    Some(BundledComponent {
+        name: format!("{}:{}", group, artifact),
+        version,
+        license: None,
+        path: path.to_string(),
+        entry: entry.map(|e| e.to_string()),
+        purl,
+    })
+}
+
+/// Main section of a jar manifest, continuation lines start with one space
+fn manifest_attributes(manifest: &str) -> HashMap<String, String> {
+    let mut attributes: HashMap<String, String> = HashMap::new();
+    let mut last_key: Option<String> = None;
+    for line in manifest.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            break;
+        }
+        if let Some(continuation) = line.strip_prefix(' ') {
+            if let Some(value) = last_key.as_ref().and_then(|key| attributes.get_mut(key)) {
+                value.push_str(continuation);
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            attributes.insert(key.trim().to_string(), value.trim().to_string());
+            last_key = Some(key.trim().to_string());
+        }
+    }
+    attributes
+}
+
+fn le_u16(data: &[u8], offset: usize) -> usize {
+    u16::from_le_bytes([data[offset], data[offset + 1]]) as usize
+}
+
+fn le_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
+}
+
+/// Read the stored or deflated zip entries whose names match, via the central directory
+///
+/// ZIP64 is not supported: jars whose central directory lies past 4 GiB yield nothing, and
+/// entries whose size or offset only the ZIP64 extra field holds are skipped.
+fn read_zip_entries(path: &Path, wanted: impl Fn(&str) -> bool) -> Vec<(String, Vec<u8>)> {
+    let mut entries = Vec::new();
+    let Ok(mut file) = File::open(path) else {
+        return entries;
+    };
+    let Ok(length) = file.metadata().map(|m| m.len()) else {
+        return entries;
+    };
+
+    let tail_start = length.saturating_sub(ZIP_TAIL_SEARCH);
+    let mut tail = Vec::new();
+    if file.seek(SeekFrom::Start(tail_start)).is_err() || file.read_to_end(&mut tail).is_err() || tail.len() < 22 {
+        return entries;
+    }
+    let Some(end) = (0..=tail.len() - 22).rev().find(|&i| le_u32(&tail, i) == ZIP_END_OF_CENTRAL_DIRECTORY) else {
+        return entries;
+    };
+    let directory_size = le_u32(&tail, end + 12);
+    let directory_offset = le_u32(&tail, end + 16);
+    if directory_size == ZIP64_SENTINEL || directory_offset == ZIP64_SENTINEL {
+        return entries;
+    }
+    let (directory_size, directory_offset) = (directory_size as u64, directory_offset as u64);
+    if directory_offset + directory_size > length {
+        return entries;
+    }
+
+    let mut directory = vec![0u8; directory_size as usize];
+    if file.seek(SeekFrom::Start(directory_offset)).is_err() || file.read_exact(&mut directory).is_err() {
+        return entries;
+    }
+
+    let mut offset = 0;
+    while offset + 46 <= directory.len() && le_u32(&directory, offset) == ZIP_CENTRAL_DIRECTORY_HEADER {
+        let method = le_u16(&directory, offset + 10);
+        let compressed_size = le_u32(&directory, offset + 20);
+        let size = le_u32(&directory, offset + 24);
+        let name_length = le_u16(&directory, offset + 28);
+        let extra_length = le_u16(&directory, offset + 30);
+        let comment_length = le_u16(&directory, offset + 32);
+        let local_offset = le_u32(&directory, offset + 42);
+        let Some(name) = directory.get(offset + 46..offset + 46 + name_length) else {
+            break;
+        };
+        let name = String::from_utf8_lossy(name).into_owned();
+        offset += 46 + name_length + extra_length + comment_length;
+
+        let zip64 = [compressed_size, size, local_offset].contains(&ZIP64_SENTINEL);
+        if !wanted(&name) || zip64 || size as u64 > MAX_MANIFEST_SIZE {
+            continue;
+        }
+        if let Some(data) = read_zip_entry(&mut file, local_offset as u64, method, compressed_size as u64) {
+            entries.push((name, data));
+        }
+    }
+
+    entries
+}
+
+fn read_zip_entry(file: &mut File, local_offset: u64, method: usize, compressed_size: u64) -> Option<Vec<u8>> {
+    let mut header = [0u8; 30];
+    file.seek(SeekFrom::Start(local_offset)).ok()?;
+    file.read_exact(&mut header).ok()?;
+    if le_u32(&header, 0) != ZIP_LOCAL_HEADER {
+        return None;
+    }
+    let data_offset = local_offset + 30 + le_u16(&header, 26) as u64 + le_u16(&header, 28) as u64;
+    file.seek(SeekFrom::Start(data_offset)).ok()?;
+    let compressed = file.take(compressed_size);
+
+    let mut data = Vec::new();
+    match method {
+        0 => compressed.take(MAX_MANIFEST_SIZE).read_to_end(&mut data).ok()?,
+        8 => DeflateDecoder::new(compressed).take(MAX_MANIFEST_SIZE).read_to_end(&mut data).ok()?,
+        _ => return None,
+    };
+    Some(data)
+}
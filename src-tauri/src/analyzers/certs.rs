@@ -0,0 +1,634 @@
+use crate::extractors::deb::{is_regular_file, FileInfo, FileType};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use x509_parser::der_parser::ber::BerObject;
+use x509_parser::der_parser::oid::Oid;
+use x509_parser::der_parser::parse_der;
+use x509_parser::objects::{oid2sn, oid_registry};
+use x509_parser::prelude::*;
+use x509_parser::public_key::PublicKey;
+
+/// Files larger than this are not inspected
+const MAX_CERT_FILE_SIZE: u64 = 4 * 1024 * 1024;
+/// Cap on inventory entries, a CA bundle holds a few hundred at most
+const MAX_ENTRIES: usize = 2000;
+/// Certificates expiring within this many days get a warning
+const EXPIRY_WARNING_DAYS: i64 = 30;
+const MIN_RSA_BITS: usize = 2048;
+const MIN_EC_BITS: usize = 224;
+
+const CERT_EXTENSIONS: &[&str] = &[".pem", ".crt", ".cer", ".der", ".key", ".pub", ".p12", ".pfx"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateInventory {
+    pub certificates: Vec<CertificateInfo>,
+    pub keys: Vec<KeyInfo>,
+    /// PKCS#12 bundles, usually password protected so only listed
+    pub pkcs12_bundles: Vec<String>,
+}
+
+impl CertificateInventory {
+    pub fn is_empty(&self) -> bool {
+        self.certificates.is_empty() && self.keys.is_empty() && self.pkcs12_bundles.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.certificates.len() + self.keys.len() + self.pkcs12_bundles.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CertEncoding {
+    Pem,
+    Der,
+    Openssh,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateInfo {
+    pub path: String,
+    /// Position of the certificate within the file
+    pub index: usize,
+    pub encoding: CertEncoding,
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    /// Unix timestamps (seconds)
+    pub not_before: i64,
+    pub not_after: i64,
+    pub key_type: String,
+    pub key_size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    pub signature_algorithm: String,
+    pub subject_alt_names: Vec<String>,
+    pub is_ca: bool,
+    pub self_signed: bool,
+    pub issues: Vec<CertIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyInfo {
+    pub path: String,
+    pub index: usize,
+    pub encoding: CertEncoding,
+    pub private: bool,
+    pub key_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    pub encrypted: bool,
+    pub issues: Vec<CertIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertIssue {
+    pub kind: CertIssueKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CertIssueKind {
+    Expired,
+    ExpiresSoon,
+    NotYetValid,
+    WeakKey,
+    WeakSignature,
+}
+
+/// Key algorithm details shared by certificates and bare keys
+struct KeyDetails {
+    key_type: String,
+    key_size: Option<usize>,
+    curve: Option<String>,
+}
+
+/// One `-----BEGIN ...-----` block
+struct PemBlock {
+    label: String,
+    encrypted: bool,
+    der: Vec<u8>,
+}
+
+/// Find certificates, keys and PKCS#12 bundles among the package files
+pub fn inventory_certificates(files: &[FileInfo], extract_path: &Path) -> CertificateInventory {
+    let mut inventory = CertificateInventory::default();
+    let now = ASN1Time::now().timestamp();
+
+    for file in files {
+        if inventory.len() >= MAX_ENTRIES {
+            break;
+        }
+        let path = extract_path.join(&file.path);
+        if file.file_type != FileType::File || file.size > MAX_CERT_FILE_SIZE || !is_regular_file(&path) {
+            continue;
+        }
+        let lower = file.path.to_lowercase();
+        let by_extension = CERT_EXTENSIONS.iter().any(|ext| lower.ends_with(ext));
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(_) => continue,
+        };
+
+        if contains(&data, b"-----BEGIN ") {
+            for (index, block) in parse_pem_blocks(&data).into_iter().enumerate() {
+                inspect_pem_block(&file.path, index, block, now, &mut inventory);
+            }
+        } else if by_extension && data.starts_with(b"ssh-") {
+            if let Some(key) = parse_openssh_public(&file.path, &data) {
+                inventory.keys.push(key);
+            }
+        } else if by_extension && data.first() == Some(&0x30) {
+            if lower.ends_with(".p12") || lower.ends_with(".pfx") || is_pkcs12(&data) {
+                inventory.pkcs12_bundles.push(file.path.clone());
+            } else if let Ok((_, cert)) = parse_x509_certificate(&data) {
+                inventory
+                    .certificates
+                    .push(describe_certificate(&file.path, 0, CertEncoding::Der, &cert, now));
+            } else if let Some(key) = describe_der_key(&data, true) {
+                inventory.keys.push(key_info(&file.path, 0, CertEncoding::Der, key, true, false));
+            } else if let Some(key) = describe_der_key(&data, false) {
+                inventory.keys.push(key_info(&file.path, 0, CertEncoding::Der, key, false, false));
+            }
+        }
+    }
+
+    inventory
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+/// Split PEM text into blocks, tolerating RFC 1421 headers such as Proc-Type
+fn parse_pem_blocks(data: &[u8]) -> Vec<PemBlock> {
+    let text = String::from_utf8_lossy(data);
+    let mut blocks = Vec::new();
+    let mut current: Option<(String, bool, String)> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(label) = line.strip_prefix("-----BEGIN ").and_then(|l| l.strip_suffix("-----")) {
+            current = Some((label.to_string(), false, String::new()));
+        } else if line.starts_with("-----END ") {
+            if let Some((label, encrypted, body)) = current.take() {
+                if let Ok(der) = base64::engine::general_purpose::STANDARD.decode(body.as_bytes()) {
+                    blocks.push(PemBlock { label, encrypted, der });
+                }
+            }
+        } else if let Some((_, encrypted, body)) = current.as_mut() {
+            if line.contains(':') {
+                if line.starts_with("Proc-Type:") && line.contains("ENCRYPTED") {
+                    *encrypted = true;
+                }
+            } else {
+                body.push_str(line);
+            }
+        }
+    }
+
+    blocks
+}
+
+fn inspect_pem_block(path: &str, index: usize, block: PemBlock, now: i64, inventory: &mut CertificateInventory) {
+    match block.label.as_str() {
+        "CERTIFICATE" | "TRUSTED CERTIFICATE" | "X509 CERTIFICATE" => {
+            if let Ok((_, cert)) = parse_x509_certificate(&block.der) {
+                inventory
+                    .certificates
+                    .push(describe_certificate(path, index, CertEncoding::Pem, &cert, now));
+            }
+        }
+        "PRIVATE KEY" | "PUBLIC KEY" | "RSA PRIVATE KEY" | "RSA PUBLIC KEY" | "EC PRIVATE KEY"
+        | "DSA PRIVATE KEY" => {
+            let private = block.label.ends_with("PRIVATE KEY");
+            let details = if block.encrypted {
+                Some(KeyDetails {
+                    key_type: block.label.trim_end_matches(" PRIVATE KEY").to_string(),
+                    key_size: None,
+                    curve: None,
+                })
+            } else {
+                describe_labelled_key(&block.label, &block.der)
+            };
+            if let Some(details) = details {
+                inventory.keys.push(key_info(path, index, CertEncoding::Pem, details, private, block.encrypted));
+            }
+        }
+        "ENCRYPTED PRIVATE KEY" => {
+            let details = KeyDetails {
+                key_type: "unknown".to_string(),
+                key_size: None,
+                curve: None,
+            };
+            inventory.keys.push(key_info(path, index, CertEncoding::Pem, details, true, true));
+        }
+        "OPENSSH PRIVATE KEY" => {
+            if let Some(key) = parse_openssh_private(path, index, &block.der) {
+                inventory.keys.push(key);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn describe_certificate(
+    path: &str,
+    index: usize,
+    encoding: CertEncoding,
+    cert: &X509Certificate,
+    now: i64,
+) -> CertificateInfo {
+    let key = describe_spki(cert.public_key());
+    let validity = cert.validity();
+    let not_before = validity.not_before.timestamp();
+    let not_after = validity.not_after.timestamp();
+    let signature_oid = &cert.signature_algorithm.algorithm;
+    let signature_algorithm = oid2sn(signature_oid, oid_registry())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| signature_oid.to_id_string());
+
+    let subject_alt_names = match cert.subject_alternative_name() {
+        Ok(Some(san)) => san.value.general_names.iter().map(format_general_name).collect(),
+        _ => Vec::new(),
+    };
+
+    let mut issues = Vec::new();
+    if not_after < now {
+        issues.push(CertIssue {
+            kind: CertIssueKind::Expired,
+            message: format!("Certificate expired {} days ago", (now - not_after) / 86400),
+        });
+    } else if not_after - now < EXPIRY_WARNING_DAYS * 86400 {
+        issues.push(CertIssue {
+            kind: CertIssueKind::ExpiresSoon,
+            message: format!("Certificate expires in {} days", (not_after - now) / 86400),
+        });
+    }
+    if not_before > now {
+        issues.push(CertIssue {
+            kind: CertIssueKind::NotYetValid,
+            message: "Certificate is not valid yet".to_string(),
+        });
+    }
+    if let Some(issue) = weak_key_issue(&key) {
+        issues.push(issue);
+    }
+    if is_weak_signature(&signature_oid.to_id_string()) {
+        issues.push(CertIssue {
+            kind: CertIssueKind::WeakSignature,
+            message: format!("Signed with weak algorithm {}", signature_algorithm),
+        });
+    }
+
+    CertificateInfo {
+        path: path.to_string(),
+        index,
+        encoding,
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        serial: cert.raw_serial_as_string(),
+        not_before,
+        not_after,
+        key_type: key.key_type,
+        key_size: key.key_size.unwrap_or(0),
+        curve: key.curve,
+        signature_algorithm,
+        subject_alt_names,
+        is_ca: cert.is_ca(),
+        self_signed: cert.subject() == cert.issuer(),
+        issues,
+    }
+}
+
+fn format_general_name(name: &GeneralName) -> String {
+    match name {
+        GeneralName::DNSName(s) => format!("DNS:{}", s),
+        GeneralName::RFC822Name(s) => format!("email:{}", s),
+        GeneralName::URI(s) => format!("URI:{}", s),
+        GeneralName::IPAddress(bytes) => match bytes.len() {
+            4 => format!("IP:{}", std::net::Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+            16 => {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(bytes);
+                format!("IP:{}", std::net::Ipv6Addr::from(octets))
+            }
+            _ => name.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+fn key_info(
+    path: &str,
+    index: usize,
+    encoding: CertEncoding,
+    key: KeyDetails,
+    private: bool,
+    encrypted: bool,
+) -> KeyInfo {
+    let issues = weak_key_issue(&key).into_iter().collect();
+    KeyInfo {
+        path: path.to_string(),
+        index,
+        encoding,
+        private,
+        key_type: key.key_type,
+        key_size: key.key_size,
+        curve: key.curve,
+        encrypted,
+        issues,
+    }
+}
+
+fn weak_key_issue(key: &KeyDetails) -> Option<CertIssue> {
+    let size = key.key_size?;
+    let minimum = match key.key_type.as_str() {
+        "RSA" | "DSA" => MIN_RSA_BITS,
+        "EC" => MIN_EC_BITS,
+        _ => return None,
+    };
+    if size >= minimum {
+        return None;
+    }
+    Some(CertIssue {
+        kind: CertIssueKind::WeakKey,
+        message: format!("{} key of {} bits is below the {} bit minimum", key.key_type, size, minimum),
+    })
+}
+
+fn is_weak_signature(oid: &str) -> bool {
+    matches!(
+        oid,
+        "1.2.840.113549.1.1.2" // md2WithRSAEncryption
+            | "1.2.840.113549.1.1.4" // md5WithRSAEncryption
+            | "1.2.840.113549.1.1.5" // sha1WithRSAEncryption
+            | "1.2.840.10040.4.3" // dsa-with-sha1
+            | "1.2.840.10045.4.1" // ecdsa-with-SHA1
+    )
+}
+
+fn key_type_name(oid: &str) -> &'static str {
+    match oid {
+        "1.2.840.113549.1.1.1" | "1.2.840.113549.1.1.10" => "RSA",
+        "1.2.840.10045.2.1" => "EC",
+        "1.2.840.10040.4.1" => "DSA",
+        "1.3.101.110" => "X25519",
+        "1.3.101.111" => "X448",
+        "1.3.101.112" => "Ed25519",
+        "1.3.101.113" => "Ed448",
+        _ => "unknown",
+    }
+}
+
+fn curve_name(oid: &str) -> String {
+    match oid {
+        "1.2.840.10045.3.1.1" => "P-192".to_string(),
+        "1.3.132.0.33" => "P-224".to_string(),
+        "1.2.840.10045.3.1.7" => "P-256".to_string(),
+        "1.3.132.0.34" => "P-384".to_string(),
+        "1.3.132.0.35" => "P-521".to_string(),
+        "1.3.132.0.10" => "secp256k1".to_string(),
+        "1.3.36.3.3.2.8.1.1.7" => "brainpoolP256r1".to_string(),
+        "1.3.36.3.3.2.8.1.1.11" => "brainpoolP384r1".to_string(),
+        "1.3.36.3.3.2.8.1.1.13" => "brainpoolP512r1".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn curve_bits(curve: &str) -> Option<usize> {
+    match curve {
+        "P-192" => Some(192),
+        "P-224" => Some(224),
+        "P-256" | "secp256k1" | "brainpoolP256r1" => Some(256),
+        "P-384" | "brainpoolP384r1" => Some(384),
+        "P-521" => Some(521),
+        "brainpoolP512r1" => Some(512),
+        _ => None,
+    }
+}
+
+/// Bit length of an unsigned big-endian integer
+fn integer_bits(bytes: &[u8]) -> usize {
+    let bytes = match bytes.iter().position(|&b| b != 0) {
+        Some(start) => &bytes[start..],
+        None => return 0,
+    };
+    (bytes.len() - 1) * 8 + (8 - bytes[0].leading_zeros() as usize)
+}
+
+fn oid_from_der(data: &[u8]) -> Option<String> {
+    let (_, obj) = parse_der(data).ok()?;
+    obj.as_oid().ok().map(|oid| oid.to_id_string())
+}
+
+fn describe_spki(spki: &SubjectPublicKeyInfo) -> KeyDetails {
+    let algorithm = spki.algorithm.algorithm.to_id_string();
+    let key_type = key_type_name(&algorithm);
+    let parameters = spki.algorithm.parameters.as_ref();
+    let curve = match key_type {
+        "EC" => parameters.map(|p| curve_name(&Oid::new(p.data.into()).to_id_string())),
+        _ => None,
+    };
+    let key_size = match spki.parsed() {
+        Ok(PublicKey::RSA(rsa)) => Some(integer_bits(rsa.modulus)),
+        Ok(PublicKey::EC(_)) => curve.as_deref().and_then(curve_bits),
+        Ok(PublicKey::DSA(_)) => parameters.and_then(|p| dsa_prime_bits(p.data)),
+        _ => match key_type {
+            "Ed25519" | "X25519" => Some(256),
+            "Ed448" | "X448" => Some(448),
+            _ => None,
+        },
+    };
+
+    KeyDetails {
+        key_type: key_type.to_string(),
+        key_size,
+        curve,
+    }
+}
+
+/// DSA parameters are SEQUENCE { p, q, g }, the key size is the size of p
+fn dsa_prime_bits(parameters: &[u8]) -> Option<usize> {
+    let (_, obj) = parse_der(parameters).ok()?;
+    let seq = obj.as_sequence().ok()?;
+    seq.first()?.as_slice().ok().map(integer_bits)
+}
+
+fn sequence<'a>(obj: &'a BerObject<'a>) -> Option<&'a Vec<BerObject<'a>>> {
+    obj.as_sequence().ok()
+}
+
+fn describe_labelled_key(label: &str, der: &[u8]) -> Option<KeyDetails> {
+    match label {
+        "PRIVATE KEY" => describe_der_key(der, true),
+        "PUBLIC KEY" => describe_der_key(der, false),
+        "RSA PRIVATE KEY" => describe_pkcs1(der, 1),
+        "RSA PUBLIC KEY" => describe_pkcs1(der, 0),
+        "EC PRIVATE KEY" => describe_sec1(der),
+        "DSA PRIVATE KEY" => {
+            // SEQUENCE { version, p, q, g, y, x }
+            let (_, obj) = parse_der(der).ok()?;
+            let p = sequence(&obj)?.get(1)?.as_slice().ok()?;
+            Some(KeyDetails {
+                key_type: "DSA".to_string(),
+                key_size: Some(integer_bits(p)),
+                curve: None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// PKCS#8 PrivateKeyInfo or SubjectPublicKeyInfo
+fn describe_der_key(der: &[u8], private: bool) -> Option<KeyDetails> {
+    if !private {
+        let (_, spki) = SubjectPublicKeyInfo::from_der(der).ok()?;
+        return Some(describe_spki(&spki));
+    }
+
+    // SEQUENCE { version, AlgorithmIdentifier, OCTET STRING privateKey, ... }
+    let (_, obj) = parse_der(der).ok()?;
+    let items = sequence(&obj)?;
+    let algorithm = sequence(items.get(1)?)?;
+    let oid = algorithm.first()?.as_oid().ok()?.to_id_string();
+    let key_type = key_type_name(&oid);
+    let inner = items.get(2)?.as_slice().ok()?;
+
+    match key_type {
+        "RSA" => describe_pkcs1(inner, 1),
+        "EC" => {
+            let curve = algorithm
+                .get(1)
+                .and_then(|p| p.as_oid().ok())
+                .map(|oid| curve_name(&oid.to_id_string()));
+            Some(KeyDetails {
+                key_type: "EC".to_string(),
+                key_size: curve.as_deref().and_then(curve_bits),
+                curve,
+            })
+        }
+        "DSA" => {
+            let p = algorithm.get(1).and_then(|p| sequence(p)?.first()?.as_slice().ok());
+            Some(KeyDetails {
+                key_type: "DSA".to_string(),
+                key_size: p.map(integer_bits),
+                curve: None,
+            })
+        }
+        _ => Some(KeyDetails {
+            key_type: key_type.to_string(),
+            key_size: match key_type {
+                "Ed25519" | "X25519" => Some(256),
+                "Ed448" | "X448" => Some(448),
+                _ => None,
+            },
+            curve: None,
+        }),
+    }
+}
+
+/// PKCS#1 RSA key, the modulus sits at `modulus_index` in the sequence
+fn describe_pkcs1(der: &[u8], modulus_index: usize) -> Option<KeyDetails> {
+    let (_, obj) = parse_der(der).ok()?;
+    let modulus = sequence(&obj)?.get(modulus_index)?.as_slice().ok()?;
+    Some(KeyDetails {
+        key_type: "RSA".to_string(),
+        key_size: Some(integer_bits(modulus)),
+        curve: None,
+    })
+}
+
+/// SEC1 ECPrivateKey: SEQUENCE { version, privateKey, [0] parameters, [1] publicKey }
+fn describe_sec1(der: &[u8]) -> Option<KeyDetails> {
+    let (_, obj) = parse_der(der).ok()?;
+    let items = sequence(&obj)?;
+    let curve = items
+        .iter()
+        .skip(2)
+        .find(|item| item.tag().0 == 0)
+        .and_then(|item| item.as_slice().ok())
+        .and_then(oid_from_der)
+        .map(|oid| curve_name(&oid));
+    let key_size = curve
+        .as_deref()
+        .and_then(curve_bits)
+        .or_else(|| items.get(1)?.as_slice().ok().map(|k| k.len() * 8));
+    Some(KeyDetails {
+        key_type: "EC".to_string(),
+        key_size,
+        curve,
+    })
+}
+
+/// PFX ::= SEQUENCE { version INTEGER (3), authSafe ContentInfo, ... }
+fn is_pkcs12(der: &[u8]) -> bool {
+    let Ok((_, obj)) = parse_der(der) else {
+        return false;
+    };
+    let Some(items) = sequence(&obj) else {
+        return false;
+    };
+    items.first().and_then(|v| v.as_u32().ok()) == Some(3)
+        && items.get(1).and_then(|c| sequence(c)?.first()?.as_oid().ok().map(|o| o.to_id_string()))
+            == Some("1.2.840.113549.1.7.1".to_string())
+}
+
+/// Read a length-prefixed SSH wire string
+fn ssh_string<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = u32::from_be_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let value = data.get(4..4 + len)?;
+    *data = &data[4 + len..];
+    Some(value)
+}
+
+/// Decode an SSH public key blob: string type, then algorithm specific fields
+fn describe_ssh_key_blob(mut blob: &[u8]) -> Option<KeyDetails> {
+    let key_type = String::from_utf8_lossy(ssh_string(&mut blob)?).to_string();
+    let (name, key_size, curve) = match key_type.as_str() {
+        "ssh-rsa" => {
+            let _exponent = ssh_string(&mut blob)?;
+            ("RSA", Some(integer_bits(ssh_string(&mut blob)?)), None)
+        }
+        "ssh-dss" => ("DSA", Some(integer_bits(ssh_string(&mut blob)?)), None),
+        "ssh-ed25519" => ("Ed25519", Some(256), None),
+        t if t.starts_with("ecdsa-sha2-nistp") => {
+            let curve = format!("P-{}", t.trim_start_matches("ecdsa-sha2-nistp"));
+            ("EC", curve_bits(&curve), Some(curve))
+        }
+        _ => return None,
+    };
+    Some(KeyDetails {
+        key_type: name.to_string(),
+        key_size,
+        curve,
+    })
+}
+
+fn parse_openssh_public(path: &str, data: &[u8]) -> Option<KeyInfo> {
+    let text = String::from_utf8_lossy(data);
+    let blob = text.split_whitespace().nth(1)?;
+    let blob = base64::engine::general_purpose::STANDARD.decode(blob).ok()?;
+    let details = describe_ssh_key_blob(&blob)?;
+    Some(key_info(path, 0, CertEncoding::Openssh, details, false, false))
+}
+
+/// openssh-key-v1: magic, cipher, kdf, kdf options, key count, public key blobs
+fn parse_openssh_private(path: &str, index: usize, der: &[u8]) -> Option<KeyInfo> {
+    let mut data = der.strip_prefix(b"openssh-key-v1\0")?;
+    let cipher = ssh_string(&mut data)?;
+    let _kdf = ssh_string(&mut data)?;
+    let _kdf_options = ssh_string(&mut data)?;
+    data = data.get(4..)?;
+    let details = describe_ssh_key_blob(ssh_string(&mut data)?)?;
+    Some(key_info(path, index, CertEncoding::Openssh, details, true, cipher != b"none"))
+}
@@ -0,0 +1,262 @@
+use crate::analyzers::script_commands::{dependency_names, DpkgDatabase};
+use crate::extractors::deb::DebianPackageMetadata;
+use crate::extractors::desktop::{split_exec, DesktopEntry, DesktopIssue, ExecResolution, IconResolution};
+use crate::utils::issue::IssueSeverity;
+use base64::Engine;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Directories on the default PATH, relative to the package root
+const BIN_DIRS: &[&str] = &["usr/local/bin", "usr/bin", "bin", "usr/local/sbin", "usr/sbin", "sbin", "usr/games"];
+const ICON_EXTENSIONS: &[&str] = &["png", "svg", "xpm"];
+/// Icons larger than this are located but not embedded
+const MAX_ICON_SIZE: u64 = 1024 * 1024;
+
+/// Installed packages that guarantee programs the package runs but does not ship
+pub struct InstalledPrograms<'a> {
+    database: &'a DpkgDatabase,
+    dependencies: Vec<String>,
+}
+
+impl<'a> InstalledPrograms<'a> {
+    /// Look programs up in the local dpkg database against the package's dependencies
+    pub fn new(metadata: &DebianPackageMetadata, database: &'a DpkgDatabase) -> InstalledPrograms<'a> {
+        let mut dependencies = dependency_names(metadata.pre_depends.as_deref());
+        dependencies.extend(dependency_names(metadata.depends.as_deref()));
+        InstalledPrograms { database, dependencies }
+    }
+
+    /// Essential package or dependency that ships the program
+    pub fn guaranteed_by(&self, program: &str) -> Option<String> {
+        let providers = self.database.providers.get(program)?;
+        self.database
+            .essential_provider(providers)
+            .or_else(|| self.database.satisfying_dependency(providers, &self.dependencies))
+            .cloned()
+    }
+}
+
+/// Programs a desktop entry runs, for looking them up with [`DpkgDatabase::load`]
+pub fn desktop_entry_programs(entry: &DesktopEntry) -> Vec<String> {
+    let try_exec = entry.try_exec.as_ref().map(|t| t.trim().to_string());
+    let execs = entry.exec.iter().chain(entry.actions.iter().filter_map(|a| a.exec.as_ref()));
+    execs.filter_map(|exec| exec_program(exec)).chain(try_exec).collect()
+}
+
+/// Resolve the Icon, Exec and TryExec of a desktop entry against the package files
+pub fn resolve_desktop_entry(entry: &mut DesktopEntry, extract_path: &Path, installed: &InstalledPrograms) {
+    let mut issues = Vec::new();
+
+    if let Some(icon) = entry.icon.as_ref().map(|i| i.value.clone()).filter(|i| !i.is_empty()) {
+        let resolution = resolve_icon(&icon, extract_path);
+        if resolution.path.is_none() {
+            issues.push(DesktopIssue {
+                severity: IssueSeverity::Error,
+                line: None,
+                message: format!("Icon \"{}\" is not shipped in the package", icon),
+            });
+        }
+        if !icon.starts_with('/') && ICON_EXTENSIONS.iter().any(|ext| icon.ends_with(&format!(".{}", ext))) {
+            issues.push(DesktopIssue {
+                severity: IssueSeverity::Warning,
+                line: None,
+                message: format!("Icon \"{}\" should be a name without a file extension", icon),
+            });
+        }
+        entry.icon_resolution = Some(resolution);
+    }
+
+    if let Some(exec) = entry.exec.clone() {
+        entry.exec_resolution = resolve_program(&exec, extract_path, installed, "Exec", &mut issues);
+    }
+    if let Some(try_exec) = entry.try_exec.clone() {
+        // TryExec is a bare path or program name, not a command line
+        let program = try_exec.trim().to_string();
+        entry.try_exec_resolution = Some(resolve_installed(&program, extract_path, installed, "TryExec", &mut issues));
+    }
+    for action in &entry.actions {
+        if let Some(exec) = &action.exec {
+            let key = format!("Action \"{}\" Exec", action.id);
+            resolve_program(exec, extract_path, installed, &key, &mut issues);
+        }
+    }
+
+    entry.issues.extend(issues);
+}
+
+fn missing_program_issue(key: &str, program: &str) -> DesktopIssue {
+    DesktopIssue {
+        severity: IssueSeverity::Error,
+        line: None,
+        message: format!(
+            "{} program \"{}\" is not shipped in the package nor guaranteed by an Essential package or Depends",
+            key, program
+        ),
+    }
+}
+
+fn resolve_program(
+    exec: &str,
+    extract_path: &Path,
+    installed: &InstalledPrograms,
+    key: &str,
+    issues: &mut Vec<DesktopIssue>,
+) -> Option<ExecResolution> {
+    let program = exec_program(exec)?;
+    Some(resolve_installed(&program, extract_path, installed, key, issues))
+}
+
+/// Locate a program in the package, falling back to the packages guaranteed to be installed
+fn resolve_installed(
+    program: &str,
+    extract_path: &Path,
+    installed: &InstalledPrograms,
+    key: &str,
+    issues: &mut Vec<DesktopIssue>,
+) -> ExecResolution {
+    let mut resolution = locate_program(program, extract_path);
+    if !resolution.found {
+        resolution.provided_by = installed.guaranteed_by(program);
+        if resolution.provided_by.is_none() {
+            issues.push(missing_program_issue(key, program));
+        }
+    }
+    resolution
+}
+
+/// First argument of an Exec line, looking through an `env VAR=value` prefix
+fn exec_program(exec: &str) -> Option<String> {
+    let args = split_exec(exec)?;
+    let mut args = args.into_iter().map(|a| a.text);
+    let first = args.next()?;
+    if first != "env" && first != "/usr/bin/env" {
+        return Some(first);
+    }
+    args.find(|arg| !arg.contains('=') && !arg.starts_with('-'))
+}
+
+/// Whether a path exists in the package, without following symlinks out of it
+fn exists_in_package(extract_path: &Path, relative: &str) -> bool {
+    fs::symlink_metadata(extract_path.join(relative)).is_ok()
+}
+
+/// Find a program by absolute path or on the package's PATH directories
+pub fn locate_program(program: &str, extract_path: &Path) -> ExecResolution {
+    let candidate = if let Some(relative) = program.strip_prefix('/') {
+        exists_in_package(extract_path, relative).then(|| relative.to_string())
+    } else if program.contains('/') {
+        None
+    } else {
+        BIN_DIRS
+            .iter()
+            .map(|dir| format!("{}/{}", dir, program))
+            .find(|relative| exists_in_package(extract_path, relative))
+    };
+
+    ExecResolution {
+        program: program.to_string(),
+        found: candidate.is_some(),
+        path: candidate,
+        provided_by: None,
+    }
+}
+
+fn resolve_icon(icon: &str, extract_path: &Path) -> IconResolution {
+    let mut candidates = Vec::new();
+
+    if let Some(relative) = icon.strip_prefix('/') {
+        if exists_in_package(extract_path, relative) {
+            candidates.push((u32::MAX, relative.to_string()));
+        }
+    } else {
+        let name = ICON_EXTENSIONS
+            .iter()
+            .find_map(|ext| icon.strip_suffix(&format!(".{}", ext)))
+            .unwrap_or(icon);
+        candidates.extend(theme_icon_candidates(name, extract_path));
+
+        // /usr/share/pixmaps is the last-resort fallback
+        for ext in ICON_EXTENSIONS {
+            let relative = format!("usr/share/pixmaps/{}.{}", name, ext);
+            if exists_in_package(extract_path, &relative) {
+                candidates.push((0, relative));
+            }
+        }
+    }
+
+    // Prefer hicolor, then the largest (scalable counts as largest) size
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    let path = candidates.first().map(|(_, path)| path.clone());
+    let data_url = path.as_deref().and_then(|p| icon_data_url(extract_path, p));
+
+    IconResolution {
+        name: icon.to_string(),
+        path,
+        candidates: candidates.into_iter().map(|(_, path)| path).collect(),
+        data_url,
+    }
+}
+
+/// Icons under usr/share/icons/<theme>/<size>/<context>/<name>.<ext>, ranked for best match
+fn theme_icon_candidates(name: &str, extract_path: &Path) -> Vec<(u32, String)> {
+    let icons_root = extract_path.join("usr/share/icons");
+    let mut candidates = Vec::new();
+
+    for entry in WalkDir::new(&icons_root).min_depth(2).max_depth(4).into_iter().flatten() {
+        let path = entry.path();
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let matches = ICON_EXTENSIONS.iter().any(|ext| file_name == format!("{}.{}", name, ext));
+        if !matches {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(extract_path) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().to_string();
+        let mut parts = relative.split('/').skip(3);
+        let theme = parts.next().unwrap_or_default();
+        let size = parts
+            .next()
+            .map(|dir| {
+                if dir == "scalable" {
+                    100_000
+                } else {
+                    dir.split(['x', '@']).next().and_then(|s| s.parse().ok()).unwrap_or(1)
+                }
+            })
+            .unwrap_or(1);
+        // hicolor outranks every other theme
+        let rank = if theme == "hicolor" { 1_000_000 + size } else { size };
+        candidates.push((rank, relative));
+    }
+
+    candidates
+}
+
+/// Embed an icon, refusing symlinks and anything that resolves outside the package
+fn icon_data_url(extract_path: &Path, relative: &str) -> Option<String> {
+    let path = extract_path.join(relative);
+    let metadata = fs::symlink_metadata(&path).ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_ICON_SIZE {
+        return None;
+    }
+    // A symlinked parent directory could still point out of the package
+    let root = extract_path.canonicalize().ok()?;
+    let path = path.canonicalize().ok()?;
+    if !path.starts_with(&root) {
+        return None;
+    }
+    let mime = match path.extension()?.to_str()? {
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        "xpm" => "image/x-xpixmap",
+        _ => return None,
+    };
+    let data = fs::read(&path).ok()?;
+    Some(format!(
+        "data:{};base64,{}",
+        mime,
+        base64::engine::general_purpose::STANDARD.encode(data)
+    ))
+}
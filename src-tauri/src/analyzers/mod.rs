@@ -0,0 +1,13 @@
+pub mod accounts;
+pub mod alternatives;
+pub mod appstream;
+pub mod arch;
+pub mod bundled;
+pub mod certs;
+pub mod desktop;
+pub mod packer;
+pub mod script_commands;
+pub mod secrets;
+pub mod shebang;
+pub mod systemd;
+pub mod yara;
@@ -0,0 +1,144 @@
+use crate::extractors::deb::{FileInfo, FileType};
+use crate::extractors::elf::ELFInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Entropy above which data is almost certainly compressed or encrypted
+pub const HIGH_ENTROPY_THRESHOLD: f64 = 7.2;
+/// Small files have unreliable entropy
+const MIN_ENTROPY_SIZE: u64 = 4096;
+/// A writable+executable LOAD segment whose memory size exceeds its file
+/// size by this factor is typical of a self-unpacking stub
+const OVERSIZED_SEGMENT_RATIO: u64 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackerFinding {
+    pub path: String,
+    pub kind: PackerFindingKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PackerFindingKind {
+    /// A known packer signature, e.g. UPX
+    KnownPacker,
+    /// Writable+executable LOAD segment much larger in memory than on disk
+    WritableExecutableSegment,
+    /// ELF object with no section header table
+    NoSectionTable,
+    /// High-entropy data that is not a known compressed format
+    HighEntropy,
+}
+
+/// Magic numbers of formats that are expected to have high entropy
+const COMPRESSED_MAGICS: &[&[u8]] = &[
+    b"\x1f\x8b",             // gzip
+    b"\xfd7zXZ\x00",         // xz
+    b"BZh",                  // bzip2
+    b"\x28\xb5\x2f\xfd",     // zstd
+    b"\x5d\x00\x00",         // lzma
+    b"\x04\x22\x4d\x18",     // lz4
+    b"PK\x03\x04",           // zip, jar, apk
+    b"7z\xbc\xaf\x27\x1c",   // 7z
+    b"\x89PNG",
+    b"\xff\xd8\xff",         // jpeg
+    b"GIF8",
+    b"RIFF",                 // webp, wav, avi
+    b"OggS",
+    b"fLaC",
+    b"ID3",                  // mp3
+    b"wOFF",
+    b"wOF2",
+    b"\x00\x00\x00\x1cftyp", // mp4, heif
+    b"\x00\x00\x00\x20ftyp",
+    b"\x00\x01\x00\x00\x00", // truetype
+    b"OTTO",                 // opentype
+    b"%PDF",
+    b"hsqs",                 // squashfs
+    b"\x30\x82",             // DER certificates and keys
+];
+
+/// Whether the leading bytes identify a format that is compressed by design
+pub fn is_known_compressed(header: &[u8]) -> bool {
+    COMPRESSED_MAGICS.iter().any(|magic| header.starts_with(magic))
+}
+
+fn read_header(path: &Path) -> Vec<u8> {
+    let mut header = vec![0u8; 16];
+    match File::open(path).and_then(|mut f| f.read(&mut header)) {
+        Ok(read) => {
+            header.truncate(read);
+            header
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Flag packed binaries and unexplained high-entropy blobs
+pub fn detect_packers(
+    files: &[FileInfo],
+    elf_info: &HashMap<String, ELFInfo>,
+    extract_path: &Path,
+) -> Vec<PackerFinding> {
+    let mut findings = Vec::new();
+
+    let mut paths: Vec<&String> = elf_info.keys().collect();
+    paths.sort();
+    for path in paths {
+        let info = &elf_info[path];
+        if let Some(packer) = &info.packer {
+            findings.push(PackerFinding {
+                path: path.clone(),
+                kind: PackerFindingKind::KnownPacker,
+                message: format!("Packed with {}", packer),
+            });
+        }
+        for segment in info.segments.iter().filter(|s| s.segment_type == "LOAD") {
+            let writable_executable = segment.flags.contains('W') && segment.flags.contains('E');
+            let oversized =
+                segment.mem_size > segment.file_size.saturating_mul(OVERSIZED_SEGMENT_RATIO);
+            if writable_executable && oversized {
+                findings.push(PackerFinding {
+                    path: path.clone(),
+                    kind: PackerFindingKind::WritableExecutableSegment,
+                    message: format!(
+                        "Oversized writable and executable LOAD segment at offset 0x{:x} (file 0x{:x}, memory 0x{:x})",
+                        segment.offset, segment.file_size, segment.mem_size
+                    ),
+                });
+            }
+        }
+        if info.section_header_count == Some(0) {
+            findings.push(PackerFinding {
+                path: path.clone(),
+                kind: PackerFindingKind::NoSectionTable,
+                message: "ELF object has no section header table".to_string(),
+            });
+        }
+    }
+
+    for file in files {
+        if file.file_type == FileType::Directory || file.size < MIN_ENTROPY_SIZE {
+            continue;
+        }
+        let entropy = match file.entropy {
+            Some(entropy) if entropy >= HIGH_ENTROPY_THRESHOLD => entropy,
+            _ => continue,
+        };
+        if is_known_compressed(&read_header(&extract_path.join(&file.path))) {
+            continue;
+        }
+        findings.push(PackerFinding {
+            path: file.path.clone(),
+            kind: PackerFindingKind::HighEntropy,
+            message: format!("High entropy ({:.2} bits/byte) in an unrecognised format", entropy),
+        });
+    }
+
+    findings
+}
@@ -0,0 +1,425 @@
+use crate::analyzers::desktop::locate_program;
+use crate::extractors::deb::{DebianPackageMetadata, Scripts};
+use crate::utils::shell_words::{is_operator, script_lines, shell_words};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub(crate) const DPKG_ADMIN_DIR: &str = "/var/lib/dpkg";
+const BIN_DIRS: &[&str] = &["/usr/local/sbin", "/usr/local/bin", "/usr/sbin", "/usr/bin", "/sbin", "/bin", "/usr/games"];
+
+/// Builtins and keywords of POSIX sh, dash and bash
+const SHELL_BUILTINS: &[&str] = &[
+    ":", ".", "[", "[[", "alias", "bg", "break", "cd", "command", "continue", "declare", "echo", "eval", "exec",
+    "exit", "export", "false", "fg", "getopts", "hash", "jobs", "kill", "let", "local", "printf", "pwd", "read",
+    "readonly", "return", "set", "shift", "shopt", "source", "test", "times", "trap", "true", "type", "typeset",
+    "ulimit", "umask", "unalias", "unset", "wait",
+];
+/// Words that may precede the command name
+const PREFIX_WORDS: &[&str] = &["if", "then", "else", "elif", "do", "while", "until", "!", "{", "(", "time", "exec"];
+/// Words that end a compound command or start one without a command name
+const SKIP_WORDS: &[&str] = &["fi", "done", "esac", "}", ")", "for", "case", "in", "function", "select"];
+/// Shell libraries scripts source, with the prefixes of the functions they define
+const SOURCED_LIBRARIES: &[(&str, &[&str])] = &[
+    ("/debconf/confmodule", &["db_"]),
+    (
+        "/lsb/init-functions",
+        &["log_", "start_daemon", "killproc", "pidofproc", "status_of_proc", "init_is_upstart"],
+    ),
+    ("/dbconfig-common/dpkg/", &["dbc_"]),
+];
+
+/// External commands run by the maintainer scripts and whether the package guarantees them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptCommandReport {
+    /// Whether the local dpkg database could be used to map commands to packages
+    pub database_available: bool,
+    pub commands: Vec<ScriptCommand>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CommandCoverage {
+    /// Shipped by the package itself
+    Package,
+    Essential,
+    Dependency,
+    /// The script checks for the command before running it
+    Guarded,
+    /// Installed packages provide it, but none that is guaranteed
+    Missing,
+    /// No installed package provides it
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptCommand {
+    pub command: String,
+    pub script: String,
+    pub lines: Vec<usize>,
+    /// Installed packages shipping the command
+    pub providers: Vec<String>,
+    pub coverage: CommandCoverage,
+    /// Package or dependency that guarantees the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub covered_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// What the local dpkg database knows about installed packages
+#[derive(Default)]
+pub(crate) struct DpkgDatabase {
+    pub(crate) available: bool,
+    pub(crate) essential: HashSet<String>,
+    /// Virtual packages each installed package provides
+    provides: HashMap<String, Vec<String>>,
+    /// Depends and Pre-Depends of each installed package
+    depends: HashMap<String, Vec<String>>,
+    /// Command name or absolute path to the packages shipping it
+    pub(crate) providers: HashMap<String, Vec<String>>,
+}
+
+/// Commands the maintainer scripts run, for looking them up with [`DpkgDatabase::load`]
+pub fn script_command_names(scripts: &Scripts) -> Vec<String> {
+    script_contents(scripts)
+        .into_iter()
+        .filter_map(|(_, content)| content.as_deref())
+        .flat_map(|content| extract_commands(content).into_iter().map(|(command, _)| command))
+        .collect()
+}
+
+fn script_contents(scripts: &Scripts) -> [(&'static str, &Option<String>); 5] {
+    [
+        ("preinst", &scripts.preinst),
+        ("config", &scripts.config),
+        ("postinst", &scripts.postinst),
+        ("prerm", &scripts.prerm),
+        ("postrm", &scripts.postrm),
+    ]
+}
+
+/// Extract the external commands each maintainer script runs and check they are guaranteed to exist
+pub fn check_script_commands(
+    scripts: &Scripts,
+    metadata: &DebianPackageMetadata,
+    extract_path: &Path,
+    database: &DpkgDatabase,
+) -> ScriptCommandReport {
+    let scripts = script_contents(scripts);
+
+    let mut commands: Vec<ScriptCommand> = Vec::new();
+    let mut guards: HashMap<&str, HashSet<String>> = HashMap::new();
+    for (script, content) in scripts {
+        let Some(content) = content else {
+            continue;
+        };
+        guards.insert(script, guarded_commands(content));
+        for (command, line) in extract_commands(content) {
+            match commands.iter_mut().find(|c| c.script == script && c.command == command) {
+                Some(existing) => existing.lines.push(line),
+                None => commands.push(ScriptCommand {
+                    command,
+                    script: script.to_string(),
+                    lines: vec![line],
+                    providers: Vec::new(),
+                    coverage: CommandCoverage::Unknown,
+                    covered_by: None,
+                    note: None,
+                }),
+            }
+        }
+    }
+
+    let pre_depends = dependency_names(metadata.pre_depends.as_deref());
+    let depends = dependency_names(metadata.depends.as_deref());
+
+    for command in commands.iter_mut() {
+        command.providers = database.providers.get(&command.command).cloned().unwrap_or_default();
+
+        if locate_program(&command.command, extract_path).found {
+            command.coverage = CommandCoverage::Package;
+            command.covered_by = Some(metadata.package.clone());
+            continue;
+        }
+        if let Some(essential) = database.essential_provider(&command.providers) {
+            command.coverage = CommandCoverage::Essential;
+            command.covered_by = Some(essential.clone());
+            continue;
+        }
+
+        // preinst and config run before Depends are unpacked, postrm after they may be gone
+        let guaranteed: Vec<&String> = match command.script.as_str() {
+            "preinst" | "config" => pre_depends.iter().collect(),
+            "postinst" | "prerm" => pre_depends.iter().chain(depends.iter()).collect(),
+            _ => Vec::new(),
+        };
+        if let Some(dependency) = database.satisfying_dependency(&command.providers, guaranteed) {
+            command.coverage = CommandCoverage::Dependency;
+            command.covered_by = Some(dependency.clone());
+            continue;
+        }
+        if database.satisfying_dependency(&command.providers, &depends).is_some() {
+            command.note = Some(format!("Depends is not guaranteed while {} runs", command.script));
+        }
+
+        let basename = command.command.rsplit('/').next().unwrap_or_default();
+        if guards.get(command.script.as_str()).is_some_and(|g| g.contains(basename)) {
+            command.coverage = CommandCoverage::Guarded;
+        } else if !command.providers.is_empty() {
+            command.coverage = CommandCoverage::Missing;
+        }
+    }
+
+    ScriptCommandReport {
+        database_available: database.available,
+        commands,
+    }
+}
+
+/// Package names from a Depends-style field, flattening alternatives
+pub(crate) fn dependency_names(field: Option<&str>) -> Vec<String> {
+    field
+        .unwrap_or_default()
+        .split([',', '|'])
+        .filter_map(|dependency| {
+            let name = dependency.trim().split([' ', '(', '[', ':']).next()?;
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+fn case_pattern_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"^\(?[\w\-*"'.|]+\)\s*"#).unwrap())
+}
+
+fn function_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^(?:function\s+)?([\w\-.:]+)\s*\(\s*\)").unwrap())
+}
+
+fn source_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"(?:^|[\s;&|])(?:\.|source)\s+["']?(/[^\s"';]+)"#).unwrap())
+}
+
+fn heredoc_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"(?:^|[^<])<<-?\s*['"]?(\w+)['"]?"#).unwrap())
+}
+
+fn substitution_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\$\(([^()]*)\)|`([^`]*)`").unwrap())
+}
+
+fn guard_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?:command\s+-v|which|type|hash)\s+["']?([\w.+\-/]+)|-x\s+["']?(/[\w.+\-/]+)"#).unwrap()
+    })
+}
+
+/// Command names the script tests for with `command -v`, `which` or `[ -x /path ]`
+fn guarded_commands(content: &str) -> HashSet<String> {
+    let mut guarded: HashSet<String> = guard_regex()
+        .captures_iter(content)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .map(|m| m.as_str().rsplit('/').next().unwrap_or_default().to_string())
+        .collect();
+    // debhelper only calls systemctl once it has checked systemd is running
+    if content.contains("/run/systemd/system") {
+        guarded.insert("systemctl".to_string());
+    }
+    guarded
+}
+
+/// External command names with the lines they appear on
+fn extract_commands(content: &str) -> Vec<(String, usize)> {
+    let lines = script_lines(content);
+    let functions: HashSet<String> = lines
+        .iter()
+        .filter_map(|(_, line)| function_regex().captures(line))
+        .map(|caps| caps[1].to_string())
+        .collect();
+    // Functions of sourced libraries such as debconf's db_get are not commands
+    let library_functions: Vec<&str> = lines
+        .iter()
+        .flat_map(|(_, line)| source_regex().captures_iter(line))
+        .flat_map(|caps| {
+            let path = caps.get(1).map_or("", |m| m.as_str());
+            SOURCED_LIBRARIES
+                .iter()
+                .filter(move |(library, _)| path.contains(library))
+                .flat_map(|(_, prefixes)| prefixes.iter().copied())
+        })
+        .collect();
+
+    let mut commands = Vec::new();
+    let mut heredoc_end: Option<String> = None;
+    for (number, line) in &lines {
+        if let Some(end) = &heredoc_end {
+            if line == end {
+                heredoc_end = None;
+            }
+            continue;
+        }
+        heredoc_end = heredoc_regex().captures(line).map(|caps| caps[1].to_string());
+        if function_regex().is_match(line) {
+            continue;
+        }
+        let line = case_pattern_regex().replace(line, "");
+        let mut sources = vec![line.to_string()];
+        sources.extend(
+            substitution_regex()
+                .captures_iter(&line)
+                .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+                .map(|m| m.as_str().to_string()),
+        );
+        for source in sources {
+            let words = shell_words(&source);
+            for segment in words.split(|w| is_operator(w)) {
+                if let Some(command) = command_name(segment) {
+                    if !functions.contains(command) && !library_functions.iter().any(|p| command.starts_with(p)) {
+                        commands.push((command.to_string(), *number));
+                    }
+                }
+            }
+        }
+    }
+
+    commands
+}
+
+/// The command a simple command runs, if it is an external one
+fn command_name(segment: &[String]) -> Option<&str> {
+    let mut words = segment.iter().map(|w| w.as_str()).peekable();
+    while let Some(word) = words.next() {
+        let is_assignment = word.split_once('=').is_some_and(|(name, _)| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        if is_assignment || PREFIX_WORDS.contains(&word) || word.starts_with(['>', '<']) || word.starts_with("2>") {
+            continue;
+        }
+        // `command -v foo` is a lookup, `command foo` runs foo
+        if word == "command" && words.peek().is_some_and(|next| !next.starts_with('-')) {
+            continue;
+        }
+        if SKIP_WORDS.contains(&word)
+            || SHELL_BUILTINS.contains(&word)
+            || word.is_empty()
+            || word.starts_with(['-', '#', '('])
+            || word.ends_with(')')
+            || word.contains(['$', '`', '=', '*', '?'])
+        {
+            return None;
+        }
+        return Some(word);
+    }
+    None
+}
+
+impl DpkgDatabase {
+    /// Read the installed packages and the files shipping the wanted commands
+    pub(crate) fn load(admin_dir: &Path, wanted: &HashSet<&str>) -> DpkgDatabase {
+        let Ok(status) = fs::read_to_string(admin_dir.join("status")) else {
+            return DpkgDatabase::default();
+        };
+        let mut database = DpkgDatabase {
+            available: true,
+            ..Default::default()
+        };
+
+        for stanza in status.split("\n\n") {
+            let fields = control_fields(stanza);
+            let Some(package) = fields.get("Package") else {
+                continue;
+            };
+            let installed = fields.get("Status").is_some_and(|s| s.ends_with(" installed"));
+            if !installed {
+                continue;
+            }
+            if fields.get("Essential").is_some_and(|e| e == "yes") {
+                database.essential.insert(package.clone());
+            }
+            let depends = [fields.get("Depends"), fields.get("Pre-Depends")];
+            let depends = depends.into_iter().flatten().flat_map(|field| dependency_names(Some(field)));
+            database.depends.insert(package.clone(), depends.collect());
+            if let Some(provides) = fields.get("Provides") {
+                database.provides.insert(package.clone(), dependency_names(Some(provides)));
+            }
+        }
+
+        let Ok(entries) = fs::read_dir(admin_dir.join("info")) else {
+            return database;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(package) = file_name.strip_suffix(".list") else {
+                continue;
+            };
+            let package = package.split(':').next().unwrap_or(package).to_string();
+            let Ok(list) = fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            for path in list.lines() {
+                let Some((dir, name)) = path.rsplit_once('/') else {
+                    continue;
+                };
+                // Commands by name are looked up on PATH, absolute ones as-is or across the /usr merge
+                let merged = match path.strip_prefix("/usr") {
+                    Some(root) => root.to_string(),
+                    None => format!("/usr{}", path),
+                };
+                let keys = [
+                    (BIN_DIRS.contains(&dir) && wanted.contains(name)).then_some(name),
+                    wanted.contains(path).then_some(path),
+                    wanted.contains(merged.as_str()).then_some(merged.as_str()),
+                ];
+                for key in keys.into_iter().flatten() {
+                    let providers = database.providers.entry(key.to_string()).or_default();
+                    if !providers.contains(&package) {
+                        providers.push(package.clone());
+                    }
+                }
+            }
+        }
+
+        database
+    }
+
+    pub(crate) fn essential_provider<'a>(&self, providers: &'a [String]) -> Option<&'a String> {
+        providers.iter().find(|p| self.essential.contains(*p))
+    }
+
+    /// First dependency that is, provides or directly depends on one of the providers
+    pub(crate) fn satisfying_dependency<'a>(
+        &self,
+        providers: &[String],
+        dependencies: impl IntoIterator<Item = &'a String>,
+    ) -> Option<&'a String> {
+        dependencies.into_iter().find(|dependency| {
+            providers.iter().any(|provider| {
+                provider == *dependency
+                    || self.provides.get(provider).is_some_and(|virtuals| virtuals.contains(dependency))
+                    || self.depends.get(*dependency).is_some_and(|depends| depends.contains(provider))
+            })
+        })
+    }
+}
+
+/// Fields of one deb822 stanza, continuation lines are dropped
+fn control_fields(stanza: &str) -> BTreeMap<String, String> {
+    stanza
+        .lines()
+        .filter(|line| !line.starts_with([' ', '\t']))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
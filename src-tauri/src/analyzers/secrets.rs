@@ -0,0 +1,300 @@
+use crate::extractors::deb::{is_regular_file, FileInfo, FileType};
+use crate::utils::entropy::shannon_entropy;
+use regex::bytes::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Files larger than this are not scanned
+const MAX_SCAN_SIZE: u64 = 16 * 1024 * 1024;
+/// Minimum entropy (bits per byte) for a password/secret assignment value
+const ASSIGNMENT_ENTROPY_THRESHOLD: f64 = 3.5;
+/// Findings per file, so one leaked dump does not flood the result
+const MAX_FINDINGS_PER_FILE: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretFinding {
+    pub path: String,
+    pub kind: SecretKind,
+    /// What matched, e.g. "AWS access key ID"
+    pub rule: String,
+    /// 1-based line number for text files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Byte offset for binary files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SecretKind {
+    PrivateKey,
+    Token,
+    /// High-entropy value assigned to a password/secret variable
+    Assignment,
+    CredentialFile,
+}
+
+struct TokenRule {
+    name: &'static str,
+    regex: Regex,
+}
+
+fn private_key_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"-----BEGIN (?:RSA |EC |DSA |OPENSSH |ENCRYPTED |PGP )?PRIVATE KEY(?: BLOCK)?-----")
+            .unwrap()
+    })
+}
+
+/// A key whose identifier parts include a secret-like word, assigned a quoted
+/// literal or, with `=` only, an unquoted one
+fn assignment_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(?i)(?:^|[^a-z0-9_.-])(?P<key>(?:[a-z0-9]+[_.-])*(?:password|passwd|pwd|secret|token|api[_-]?key|access[_-]?key|auth[_-]?key)(?:[_.-][a-z0-9]+)*)["']?\s*(?:(?::=|[:=])\s*(?:"(?P<double>[^"\s]{8,})"|'(?P<single>[^'\s]{8,})')|=\s*(?P<bare>[^\s"'`,;=][^\s"'`,;]{7,}))"#,
+        )
+        .unwrap()
+    })
+}
+
+/// Unquoted values that are code rather than a literal, e.g. `getpass.getpass()` or `kwargs.get(`
+fn is_expression(value: &[u8]) -> bool {
+    let is_identifier = |part: &[u8]| {
+        part.first().is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_')
+            && part.iter().all(|b| b.is_ascii_alphanumeric() || *b == b'_')
+    };
+    value.iter().any(|b| matches!(b, b'(' | b'['))
+        || (value.contains(&b'.') && value.split(|&b| b == b'.').all(is_identifier))
+}
+
+fn token_rules() -> &'static [TokenRule] {
+    static RULES: OnceLock<Vec<TokenRule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        [
+            ("AWS access key ID", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+            ("GitHub token", r"\bgh[pousr]_[A-Za-z0-9]{36}\b"),
+            ("GitHub fine-grained token", r"\bgithub_pat_[A-Za-z0-9_]{82}\b"),
+            ("GitLab token", r"\bglpat-[A-Za-z0-9_-]{20}\b"),
+            ("Slack token", r"\bxox[abposr]-[A-Za-z0-9-]{10,}"),
+            ("Google API key", r"\bAIza[0-9A-Za-z_-]{35}\b"),
+            ("Stripe secret key", r"\b[sr]k_live_[0-9A-Za-z]{24,}\b"),
+            ("npm token", r"\bnpm_[A-Za-z0-9]{36}\b"),
+            ("PyPI token", r"\bpypi-AgEIcHlwaS5vcmc[A-Za-z0-9_-]{50,}"),
+            ("OpenAI API key", r"\bsk-(?:proj-)?[A-Za-z0-9_-]{32,}\b"),
+        ]
+        .into_iter()
+        .map(|(name, pattern)| TokenRule {
+            name,
+            regex: Regex::new(pattern).unwrap(),
+        })
+        .collect()
+    })
+}
+
+/// File names that hold credentials by convention
+fn credential_file_rule(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name {
+        ".netrc" | "_netrc" => Some("netrc credentials"),
+        ".npmrc" => Some("npm configuration"),
+        ".pypirc" => Some("PyPI configuration"),
+        ".git-credentials" => Some("git credentials"),
+        ".env" => Some("dotenv file"),
+        "id_rsa" | "id_dsa" | "id_ecdsa" | "id_ed25519" => Some("SSH private key file"),
+        _ if name.starts_with(".env.") => Some("dotenv file"),
+        _ => None,
+    }
+}
+
+/// Keep the first few characters of a secret and mask the rest
+fn redact(secret: &[u8]) -> String {
+    let secret = String::from_utf8_lossy(secret);
+    let visible: String = secret.chars().take(4).collect();
+    let hidden = secret.chars().count().saturating_sub(4).min(24);
+    format!("{}{}", visible, "*".repeat(hidden))
+}
+
+/// Scan the payload for private keys, tokens and credential files
+pub fn detect_secrets(files: &[FileInfo], extract_path: &Path) -> Vec<SecretFinding> {
+    let mut findings = Vec::new();
+
+    for file in files {
+        let path = extract_path.join(&file.path);
+        if matches!(file.file_type, FileType::Directory | FileType::Symlink)
+            || file.size > MAX_SCAN_SIZE
+            || !is_regular_file(&path)
+        {
+            continue;
+        }
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(_) => continue,
+        };
+        findings.extend(scan_file(&file.path, &data));
+    }
+
+    findings
+}
+
+/// Line numbers for match offsets, counted on from the previous match
+struct LineLocator<'a> {
+    data: &'a [u8],
+    is_text: bool,
+    offset: usize,
+    line: usize,
+}
+
+impl LineLocator<'_> {
+    fn locate(&mut self, offset: usize) -> (Option<usize>, Option<u64>) {
+        if !self.is_text {
+            return (None, Some(offset as u64));
+        }
+        // Each rule scans from the start again
+        if offset < self.offset {
+            self.offset = 0;
+            self.line = 1;
+        }
+        self.line += self.data[self.offset..offset].iter().filter(|&&b| b == b'\n').count();
+        self.offset = offset;
+        (Some(self.line), None)
+    }
+}
+
+fn scan_file(path: &str, data: &[u8]) -> Vec<SecretFinding> {
+    let mut findings = Vec::new();
+    // Text files get line numbers, binaries get byte offsets
+    let is_text = !data[..data.len().min(8192)].contains(&0);
+    let mut locator = LineLocator {
+        data,
+        is_text,
+        offset: 0,
+        line: 1,
+    };
+
+    if let Some(rule) = credential_file_rule(path) {
+        let (line, offset) = locator.locate(0);
+        findings.push(SecretFinding {
+            path: path.to_string(),
+            kind: SecretKind::CredentialFile,
+            rule: rule.to_string(),
+            line,
+            offset,
+            snippet: String::new(),
+        });
+    }
+
+    for m in private_key_regex().find_iter(data) {
+        let (line, offset) = locator.locate(m.start());
+        findings.push(SecretFinding {
+            path: path.to_string(),
+            kind: SecretKind::PrivateKey,
+            rule: "Private key".to_string(),
+            line,
+            offset,
+            snippet: String::from_utf8_lossy(m.as_bytes()).to_string(),
+        });
+        if findings.len() >= MAX_FINDINGS_PER_FILE {
+            return findings;
+        }
+    }
+
+    for rule in token_rules() {
+        for m in rule.regex.find_iter(data) {
+            let (line, offset) = locator.locate(m.start());
+            findings.push(SecretFinding {
+                path: path.to_string(),
+                kind: SecretKind::Token,
+                rule: rule.name.to_string(),
+                line,
+                offset,
+                snippet: redact(m.as_bytes()),
+            });
+            if findings.len() >= MAX_FINDINGS_PER_FILE {
+                return findings;
+            }
+        }
+    }
+
+    // Assignments only make sense in text
+    if is_text {
+        for caps in assignment_regex().captures_iter(data) {
+            let Some(key) = caps.name("key") else {
+                continue;
+            };
+            let (value, quoted) = match (caps.name("double").or(caps.name("single")), caps.name("bare")) {
+                (Some(value), _) => (value, true),
+                (None, Some(value)) => (value, false),
+                (None, None) => continue,
+            };
+            let value_bytes = value.as_bytes();
+            // Skip placeholders, variable references and code
+            if matches!(value_bytes[0], b'$' | b'%' | b'<' | b'{' | b'(')
+                || (!quoted && is_expression(value_bytes))
+                || shannon_entropy(value_bytes) < ASSIGNMENT_ENTROPY_THRESHOLD
+            {
+                continue;
+            }
+            let (line, offset) = locator.locate(key.start());
+            let key = &data[key.start()..value.start()];
+            findings.push(SecretFinding {
+                path: path.to_string(),
+                kind: SecretKind::Assignment,
+                rule: "Secret assignment".to_string(),
+                line,
+                offset,
+                snippet: format!("{}{}", String::from_utf8_lossy(key), redact(value_bytes)),
+            });
+            if findings.len() >= MAX_FINDINGS_PER_FILE {
+                return findings;
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assignments(text: &str) -> Vec<String> {
+        scan_file("etc/app.conf", text.as_bytes())
+            .into_iter()
+            .filter(|f| f.kind == SecretKind::Assignment)
+            .map(|f| f.snippet)
+            .collect()
+    }
+
+    #[test]
+    fn finds_literal_assignments() {
+        assert_eq!(assignments("DB_PASSWORD=Xk9#mQ2vLp7w\n"), vec!["DB_PASSWORD=Xk9#********"]);
+        assert_eq!(assignments("api_key: \"Zr8TqW3nVb6Y\"\n").len(), 1);
+        assert_eq!(assignments("client_secret = 'a7Fq0LmZ3xRk'\n").len(), 1);
+    }
+
+    #[test]
+    fn key_must_be_a_whole_identifier_part() {
+        assert!(assignments("tokenizer = Xk9mQ2vLp7wR\n").is_empty());
+        assert!(assignments("passwords_seen=Xk9mQ2vLp7wR\n").is_empty());
+    }
+
+    #[test]
+    fn skips_calls_and_member_access() {
+        assert!(assignments("password = getpass.getpass()\n").is_empty());
+        assert!(assignments("secret_name = kwargs.get(\"name\")\n").is_empty());
+        assert!(assignments("pwd_dir = os.getcwd()\n").is_empty());
+        assert!(assignments("token = self.session.token\n").is_empty());
+    }
+
+    #[test]
+    fn unquoted_values_need_an_equals_sign() {
+        assert!(assignments("password: Xk9mQ2vLp7wR\n").is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::analyzers::desktop::locate_program;
+use crate::analyzers::script_commands::{dependency_names, DpkgDatabase};
+use crate::extractors::deb::{is_regular_file, DebianPackageMetadata, FileInfo, FileType};
+use crate::utils::issue::{Issue, IssueSeverity};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Only the start of a script is needed for the shebang and line ending checks
+const MAX_SCRIPT_READ: u64 = 1024 * 1024;
+/// Kernels before 5.1 truncate the shebang line at 127 bytes
+const MAX_SHEBANG_LENGTH: usize = 127;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShebangInfo {
+    /// The first line without `#!`
+    pub line: String,
+    /// Interpreter path as written
+    pub interpreter: String,
+    /// Program that runs the script, the `env` target when env is used
+    pub program: String,
+    pub arguments: Vec<String>,
+    pub uses_env: bool,
+    /// sh, bash, python, perl, ...
+    pub language: String,
+    pub crlf: bool,
+    /// Installed packages shipping the program
+    pub providers: Vec<String>,
+    /// This package, an Essential package or the dependency that guarantees the program
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provided_by: Option<String>,
+    pub issues: Vec<Issue>,
+}
+
+/// Parse the shebang of every executable text file in the payload
+pub fn parse_shebangs(files: &[FileInfo], extract_path: &Path) -> HashMap<String, ShebangInfo> {
+    let mut scripts = HashMap::new();
+    for file in files.iter().filter(|f| f.file_type == FileType::File && is_executable(&f.mode)) {
+        let Some(content) = read_start(&extract_path.join(&file.path)) else {
+            continue;
+        };
+        if let Some(info) = parse_shebang(&content) {
+            scripts.insert(file.path.clone(), info);
+        }
+    }
+    scripts
+}
+
+/// Check every interpreter is shipped, Essential or covered by Depends/Pre-Depends
+pub fn check_interpreters(
+    scripts: &mut HashMap<String, ShebangInfo>,
+    extract_path: &Path,
+    metadata: &DebianPackageMetadata,
+    database: &DpkgDatabase,
+) {
+    let mut dependencies = dependency_names(metadata.pre_depends.as_deref());
+    dependencies.extend(dependency_names(metadata.depends.as_deref()));
+
+    for info in scripts.values_mut() {
+        check_interpreter(info, extract_path, metadata, database, &dependencies);
+    }
+}
+
+fn is_executable(mode: &str) -> bool {
+    u32::from_str_radix(mode, 8).is_ok_and(|mode| mode & 0o111 != 0)
+}
+
+fn read_start(path: &Path) -> Option<Vec<u8>> {
+    if !is_regular_file(path) {
+        return None;
+    }
+    let mut content = Vec::new();
+    File::open(path).ok()?.take(MAX_SCRIPT_READ).read_to_end(&mut content).ok()?;
+    content.starts_with(b"#!").then_some(content)
+}
+
+/// Split the shebang the way the kernel does: interpreter, then the rest as one argument
+pub fn parse_shebang(content: &[u8]) -> Option<ShebangInfo> {
+    let first_line_end = content.iter().position(|&b| b == b'\n').unwrap_or(content.len());
+    let first_line = String::from_utf8_lossy(content[..first_line_end].strip_prefix(b"#!")?);
+    let crlf = first_line.ends_with('\r');
+    let line = first_line.trim_end_matches('\r').trim().to_string();
+
+    let (interpreter, rest) = line.split_once([' ', '\t']).unwrap_or((&line, ""));
+    let interpreter = interpreter.to_string();
+    let rest = rest.trim();
+    let uses_env = interpreter.rsplit('/').next() == Some("env");
+    let mut issues = Vec::new();
+
+    let (program, arguments) = if uses_env {
+        let mut words = rest.split_whitespace().peekable();
+        let split_string = words.peek().is_some_and(|w| *w == "-S" || w.starts_with("-S"));
+        // env options and VAR=value assignments come before the program
+        let mut program = String::new();
+        for word in words.by_ref() {
+            if word.starts_with('-') || word.contains('=') {
+                continue;
+            }
+            program = word.to_string();
+            break;
+        }
+        let arguments: Vec<String> = words.map(|w| w.to_string()).collect();
+        if !arguments.is_empty() && !split_string {
+            issues.push(Issue {
+                severity: IssueSeverity::Error,
+                message: format!(
+                    "The kernel passes \"{}\" to env as a single argument, use \"env -S\" or drop the arguments",
+                    rest
+                ),
+            });
+        }
+        (program, arguments)
+    } else {
+        let arguments = if rest.is_empty() { Vec::new() } else { vec![rest.to_string()] };
+        (interpreter.clone(), arguments)
+    };
+
+    if crlf {
+        issues.push(Issue {
+            severity: IssueSeverity::Error,
+            message: format!(
+                "The shebang ends in CRLF, the kernel looks for an interpreter named \"{}\\r\"",
+                line.split_whitespace().last().unwrap_or(&interpreter)
+            ),
+        });
+    }
+    // A lone "#!/bin/sh\r" without a newline has no CRLF pair to discount
+    let crlf_lines = content
+        .windows(2)
+        .filter(|w| w == b"\r\n")
+        .count()
+        .saturating_sub(usize::from(crlf));
+    if crlf_lines > 0 {
+        issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: format!("{} more lines end in CRLF", crlf_lines),
+        });
+    }
+    if first_line_end > MAX_SHEBANG_LENGTH {
+        issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: format!(
+                "The shebang is {} bytes long, kernels before 5.1 truncate it at {}",
+                first_line_end, MAX_SHEBANG_LENGTH
+            ),
+        });
+    }
+    if !interpreter.starts_with('/') {
+        issues.push(Issue {
+            severity: IssueSeverity::Error,
+            message: format!("Interpreter \"{}\" is not an absolute path", interpreter),
+        });
+    }
+    if uses_env {
+        issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: "Debian Policy discourages /usr/bin/env, use the interpreter's absolute path so the packaged \
+                      interpreter is always used"
+                .to_string(),
+        });
+    }
+    if program.rsplit('/').next() == Some("python") {
+        issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: "Unversioned \"python\" is not provided by current Debian releases, use python3".to_string(),
+        });
+    }
+
+    Some(ShebangInfo {
+        language: language(&program),
+        line,
+        interpreter,
+        program,
+        arguments,
+        uses_env,
+        crlf,
+        providers: Vec::new(),
+        provided_by: None,
+        issues,
+    })
+}
+
+fn language(program: &str) -> String {
+    let name = program.rsplit('/').next().unwrap_or_default();
+    let stem = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let language = match stem {
+        "sh" | "dash" => "sh",
+        "node" | "nodejs" => "node",
+        "tclsh" | "wish" => "tcl",
+        "gawk" | "mawk" | "nawk" => "awk",
+        "Rscript" => "r",
+        _ => stem,
+    };
+    language.to_string()
+}
+
+/// Check the program is shipped, Essential or covered by Depends/Pre-Depends
+fn check_interpreter(
+    info: &mut ShebangInfo,
+    extract_path: &Path,
+    metadata: &DebianPackageMetadata,
+    database: &DpkgDatabase,
+    dependencies: &[String],
+) {
+    if info.program.is_empty() {
+        return;
+    }
+    if locate_program(&info.program, extract_path).found {
+        info.provided_by = Some(metadata.package.clone());
+        return;
+    }
+    if !database.available {
+        return;
+    }
+
+    info.providers = database.providers.get(&info.program).cloned().unwrap_or_default();
+    if info.providers.is_empty() {
+        info.issues.push(Issue {
+            severity: IssueSeverity::Error,
+            message: format!(
+                "Interpreter \"{}\" is not shipped in the package and no installed package provides it",
+                info.program
+            ),
+        });
+        return;
+    }
+
+    info.provided_by = database
+        .essential_provider(&info.providers)
+        .or_else(|| database.satisfying_dependency(&info.providers, dependencies))
+        .cloned();
+    if info.provided_by.is_none() {
+        info.issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: format!(
+                "Interpreter \"{}\" comes from {}, which is not in Depends or Pre-Depends",
+                info.program,
+                info.providers.join(", ")
+            ),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(info: &ShebangInfo) -> Vec<&str> {
+        info.issues.iter().map(|issue| issue.message.as_str()).collect()
+    }
+
+    #[test]
+    fn crlf_shebang_without_newline() {
+        let info = parse_shebang(b"#!/bin/sh\r").unwrap();
+        assert!(info.crlf);
+        assert_eq!(info.program, "/bin/sh");
+        assert!(!messages(&info).iter().any(|m| m.contains("more lines")));
+    }
+
+    #[test]
+    fn counts_crlf_lines_after_the_shebang() {
+        let info = parse_shebang(b"#!/bin/sh\r\necho a\r\necho b\r\n").unwrap();
+        assert!(info.crlf);
+        assert!(messages(&info).contains(&"2 more lines end in CRLF"));
+    }
+
+    #[test]
+    fn env_with_arguments_needs_split_string() {
+        let info = parse_shebang(b"#!/usr/bin/env python3 -u\n").unwrap();
+        assert!(info.uses_env);
+        assert_eq!(info.program, "python3");
+        assert_eq!(info.arguments, vec!["-u"]);
+        assert!(info.issues.iter().any(|i| matches!(i.severity, IssueSeverity::Error)));
+
+        let info = parse_shebang(b"#!/usr/bin/env -S python3 -u\n").unwrap();
+        assert!(!info.issues.iter().any(|i| matches!(i.severity, IssueSeverity::Error)));
+    }
+
+    #[test]
+    fn not_a_script() {
+        assert!(parse_shebang(b"\x7fELF").is_none());
+    }
+}
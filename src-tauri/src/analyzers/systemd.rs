@@ -0,0 +1,259 @@
+use crate::analyzers::desktop::{locate_program, InstalledPrograms};
+use crate::extractors::systemd::{parse_boolean, ExposureCheck, ExposureScore, SystemdUnit};
+use crate::utils::issue::{Issue, IssueSeverity};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Exposure levels, highest threshold first, as `systemd-analyze security` names them
+const EXPOSURE_LEVELS: &[(f64, &str)] = &[
+    (10.0, "DANGEROUS"),
+    (9.0, "UNSAFE"),
+    (7.5, "EXPOSED"),
+    (5.0, "MEDIUM"),
+    (1.0, "OK"),
+    (0.1, "SAFE"),
+    (0.0, "PERFECT"),
+];
+
+/// Programs the units run, for looking them up with [`DpkgDatabase::load`]
+pub fn unit_programs(units: &HashMap<String, SystemdUnit>) -> Vec<String> {
+    units
+        .values()
+        .flat_map(|unit| unit.exec.iter().map(|exec| exec.program.clone()))
+        .collect()
+}
+
+/// Resolve Exec programs, score service exposure and check how units reference each other
+pub fn analyze_units(units: &mut HashMap<String, SystemdUnit>, extract_path: &Path, installed: &InstalledPrograms) {
+    let names: HashSet<String> = units.values().map(|u| u.name.clone()).collect();
+
+    for (path, unit) in units.iter_mut() {
+        let mut issues = Vec::new();
+        let mut push = |severity: IssueSeverity, message: String| issues.push(Issue { severity, message });
+
+        if path.starts_with("etc/") {
+            push(
+                IssueSeverity::Warning,
+                "Unit is installed under /etc, which is reserved for the administrator".to_string(),
+            );
+        }
+
+        for exec in unit.exec.iter_mut() {
+            // Specifiers and variables only resolve at runtime
+            if exec.program.is_empty() || exec.program.contains(['%', '$']) {
+                continue;
+            }
+            let resolution = locate_program(&exec.program, extract_path);
+            exec.found = resolution.found;
+            exec.path = resolution.path;
+            if exec.found {
+                continue;
+            }
+            exec.provided_by = installed.guaranteed_by(&exec.program);
+            if exec.provided_by.is_none() {
+                push(
+                    IssueSeverity::Warning,
+                    format!(
+                        "{} program \"{}\" is not shipped in the package nor guaranteed by an Essential package or Depends",
+                        exec.kind, exec.program
+                    ),
+                );
+            }
+        }
+
+        let service_type = unit.value("Service", "Type").unwrap_or_else(|| "simple".to_string());
+        match unit.unit_type.as_str() {
+            "service" => {
+                let starts = unit.exec.iter().filter(|e| e.kind == "ExecStart").count();
+                if starts == 0 && service_type != "oneshot" {
+                    push(IssueSeverity::Error, "Service has no ExecStart".to_string());
+                }
+                if starts > 1 && service_type != "oneshot" {
+                    push(
+                        IssueSeverity::Error,
+                        format!("Only Type=oneshot services may have more than one ExecStart, this is {}", service_type),
+                    );
+                }
+                unit.exposure = Some(exposure_score(unit));
+            }
+            "socket" | "timer" | "path" => {
+                let stem = unit.name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(&unit.name);
+                let accept = unit
+                    .value("Socket", "Accept")
+                    .and_then(|v| parse_boolean(&v))
+                    .unwrap_or(false);
+                let target = unit.value(&capitalize(&unit.unit_type), "Unit").unwrap_or_else(|| {
+                    if accept {
+                        format!("{}@.service", stem)
+                    } else {
+                        format!("{}.service", stem)
+                    }
+                });
+                if !names.contains(&target) {
+                    push(
+                        IssueSeverity::Warning,
+                        format!("Triggered unit \"{}\" is not shipped in the package", target),
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        let installable = !unit.wanted_by.is_empty() || !unit.required_by.is_empty() || !unit.alias.is_empty() || !unit.also.is_empty();
+        if !installable && unit.unit_type != "mount" && !unit.name.contains('@') {
+            push(
+                IssueSeverity::Hint,
+                "Unit has no [Install] section and can only be started by dependency".to_string(),
+            );
+        }
+
+        unit.issues.extend(issues);
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Weighted hardening checks in the spirit of `systemd-analyze security`
+fn exposure_score(unit: &SystemdUnit) -> ExposureScore {
+    let value = |key: &str| unit.value("Service", key);
+    let enabled = |key: &str| value(key).and_then(|v| parse_boolean(&v)).unwrap_or(false);
+    let dynamic = unit.dynamic_user;
+    let mut checks = Vec::new();
+    let mut check = |setting: &str, weight: u32, badness: u32, description: &str| {
+        checks.push(ExposureCheck {
+            setting: setting.to_string(),
+            description: description.to_string(),
+            weight,
+            badness,
+        });
+    };
+    let boolean = |check: &mut dyn FnMut(&str, u32, u32, &str), key: &str, weight: u32, implied: bool, description: &str| {
+        let on = enabled(key) || implied;
+        check(key, weight, if on { 0 } else { 10 }, description);
+    };
+
+    let root = !dynamic && unit.user.as_deref().is_none_or(|u| u == "root" || u == "0");
+    check(
+        "User=/DynamicUser=",
+        2000,
+        if root { 10 } else { 0 },
+        if root { "Service runs as root" } else { "Service runs as an unprivileged user" },
+    );
+    boolean(&mut check, "NoNewPrivileges", 1000, dynamic, "Service processes cannot gain new privileges");
+    boolean(&mut check, "PrivateTmp", 1000, dynamic, "Service has its own /tmp");
+    boolean(&mut check, "PrivateDevices", 1000, false, "Service has no access to hardware devices");
+    boolean(&mut check, "PrivateNetwork", 2500, false, "Service has no access to the host network");
+    boolean(&mut check, "PrivateUsers", 1000, false, "Service runs in its own user namespace");
+    boolean(&mut check, "ProtectKernelTunables", 1000, false, "Service cannot change kernel tunables");
+    boolean(&mut check, "ProtectKernelModules", 1000, false, "Service cannot load kernel modules");
+    boolean(&mut check, "ProtectKernelLogs", 1000, false, "Service cannot read the kernel log ring buffer");
+    boolean(&mut check, "ProtectControlGroups", 1000, false, "Service cannot modify the control group hierarchy");
+    boolean(&mut check, "ProtectClock", 1000, false, "Service cannot change the system clock");
+    boolean(&mut check, "ProtectHostname", 50, false, "Service cannot change the hostname");
+    boolean(&mut check, "RestrictSUIDSGID", 1000, dynamic, "Service cannot create SUID/SGID files");
+    boolean(&mut check, "RestrictRealtime", 500, false, "Service cannot get realtime scheduling");
+    boolean(&mut check, "LockPersonality", 100, false, "Service cannot change its execution domain");
+    boolean(&mut check, "MemoryDenyWriteExecute", 100, false, "Service cannot create writable executable memory");
+
+    let protect_system = value("ProtectSystem").unwrap_or_default().to_ascii_lowercase();
+    let badness = match protect_system.as_str() {
+        "strict" => 0,
+        "full" => 3,
+        _ if dynamic => 0,
+        v if parse_boolean(v) == Some(true) => 5,
+        _ => 10,
+    };
+    check("ProtectSystem", 1000, badness, "Service has a read-only view of the OS");
+
+    let protect_home = value("ProtectHome").unwrap_or_default().to_ascii_lowercase();
+    let badness = match protect_home.as_str() {
+        "tmpfs" => 1,
+        "read-only" => 5,
+        v if parse_boolean(v) == Some(true) => 0,
+        _ if dynamic => 5,
+        _ => 10,
+    };
+    check("ProtectHome", 1000, badness, "Service has no access to home directories");
+
+    let restricted = |key: &str| value(key).is_some_and(|v| parse_boolean(&v) != Some(false));
+    check(
+        "RestrictNamespaces",
+        500,
+        if restricted("RestrictNamespaces") { 0 } else { 10 },
+        "Service cannot create namespaces",
+    );
+    check(
+        "RestrictAddressFamilies",
+        1500,
+        if value("RestrictAddressFamilies").is_some() { 0 } else { 10 },
+        "Service is limited to specific socket address families",
+    );
+
+    let filter_badness = |key: &str| match value(key) {
+        Some(v) if v.starts_with('~') => 5,
+        Some(_) => 0,
+        None => 10,
+    };
+    check("SystemCallFilter", 1000, filter_badness("SystemCallFilter"), "System calls are filtered");
+    check(
+        "CapabilityBoundingSet",
+        1500,
+        filter_badness("CapabilityBoundingSet"),
+        "Service capabilities are restricted",
+    );
+    check(
+        "SystemCallArchitectures",
+        1000,
+        if value("SystemCallArchitectures").as_deref() == Some("native") { 0 } else { 10 },
+        "Service may only use native system calls",
+    );
+
+    let device_closed = matches!(value("DevicePolicy").as_deref(), Some("closed") | Some("strict"));
+    check(
+        "DevicePolicy",
+        1000,
+        if device_closed || enabled("PrivateDevices") { 0 } else { 10 },
+        "Device access is restricted",
+    );
+    check(
+        "IPAddressDeny",
+        1000,
+        if value("IPAddressDeny").is_some() { 0 } else { 10 },
+        "Network traffic is limited by IP address",
+    );
+    check(
+        "ProtectProc",
+        1000,
+        if matches!(value("ProtectProc").as_deref(), Some("invisible") | Some("noaccess")) { 0 } else { 10 },
+        "Service cannot see other processes in /proc",
+    );
+    check(
+        "ProcSubset",
+        1000,
+        if value("ProcSubset").as_deref() == Some("pid") { 0 } else { 10 },
+        "Service only sees process entries in /proc",
+    );
+    check(
+        "UMask",
+        100,
+        if matches!(value("UMask").as_deref(), Some("0027") | Some("0077") | Some("0007")) { 0 } else { 10 },
+        "Files created by the service are not world-readable",
+    );
+
+    let total: u32 = checks.iter().map(|c| c.weight * 10).sum();
+    let bad: u32 = checks.iter().map(|c| c.weight * c.badness).sum();
+    let score = (bad as f64 / total as f64 * 100.0).round() / 10.0;
+    let level = EXPOSURE_LEVELS
+        .iter()
+        .find(|(threshold, _)| score >= *threshold)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_default();
+
+    ExposureScore { score, level, checks }
+}
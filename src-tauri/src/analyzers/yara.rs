@@ -0,0 +1,207 @@
+use crate::extractors::deb::{is_regular_file, FileInfo, FileType, YaraMatch, YaraStringMatch};
+use boreal::scanner::{EvaluatedRule, ScanParams};
+use boreal::{Compiler, Scanner};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Environment variable holding the default rules directory
+pub const YARA_RULES_ENV: &str = "VIEWDEB_YARA_RULES";
+/// Matched string data is cut to this many characters
+const MAX_MATCH_DATA_LEN: usize = 64;
+/// Files bigger than this are not scanned
+const MAX_SCAN_SIZE: u64 = 256 * 1024 * 1024;
+/// Matches reported per string of a rule
+const MAX_MATCHES_PER_STRING: u32 = 16;
+/// A single pathological rule must not stall the whole parse
+const SCAN_TIMEOUT: Duration = Duration::from_secs(30);
+/// Time for scanning one whole package; files left over are reported as skipped
+const SCAN_BUDGET: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YaraScan {
+    pub rules_dir: String,
+    pub rule_files: Vec<String>,
+    /// Matches in maintainer scripts, which have no `FileInfo` entry
+    pub script_matches: Vec<YaraMatch>,
+    /// Files not scanned because the time budget ran out
+    pub skipped: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Collect the `.yar`/`.yara` rule files of a rules directory, sorted
+pub fn collect_rule_files(rules_dir: &Path) -> Vec<String> {
+    let mut rule_files: Vec<String> = walkdir::WalkDir::new(rules_dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_lowercase();
+            name.ends_with(".yar") || name.ends_with(".yara")
+        })
+        .map(|e| e.path().to_string_lossy().to_string())
+        .collect();
+    rule_files.sort();
+    rule_files
+}
+
+/// Compile the rule files with boreal, each in its own namespace so rule
+/// names may repeat across files
+///
+/// Files that fail to compile are skipped and reported in the error list.
+pub fn compile_rules(rule_files: &[String]) -> (Scanner, Vec<String>) {
+    let mut compiler = Compiler::new();
+    let mut errors = Vec::new();
+    for (index, rule_file) in rule_files.iter().enumerate() {
+        if let Err(e) = compiler.add_rules_file_in_namespace(rule_file, format!("file{}", index)) {
+            errors.push(format!("Failed to compile {}: {}", rule_file, e));
+        }
+    }
+
+    let mut scanner = compiler.finalize();
+    scanner.set_scan_params(scan_params(SCAN_TIMEOUT));
+    (scanner, errors)
+}
+
+fn scan_params(timeout: Duration) -> ScanParams {
+    ScanParams::default()
+        .string_max_nb_matches(MAX_MATCHES_PER_STRING)
+        .timeout_duration(Some(timeout))
+}
+
+/// Scan one file; `path` is the name reported in the matches
+pub fn scan_file(scanner: &Scanner, file_path: &Path, path: &str) -> Result<Vec<YaraMatch>, String> {
+    let data = fs::read(file_path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    // A timeout still returns the rules matched so far
+    let (result, error) = match scanner.scan_mem(&data) {
+        Ok(result) => (result, None),
+        Err((e, result)) => (result, Some(format!("YARA scan of {} stopped: {}", path, e))),
+    };
+    let matches = result
+        .rules
+        .iter()
+        .map(|rule| to_match(scanner, rule, path))
+        .collect();
+    match error {
+        Some(e) if result.rules.is_empty() => Err(e),
+        _ => Ok(matches),
+    }
+}
+
+fn to_match(scanner: &Scanner, rule: &EvaluatedRule, path: &str) -> YaraMatch {
+    let mut strings: Vec<YaraStringMatch> = rule
+        .matches
+        .iter()
+        .flat_map(|string| {
+            string.matches.iter().map(|m| YaraStringMatch {
+                offset: m.offset as u64,
+                identifier: format!("${}", string.name),
+                data: printable(&m.data),
+            })
+        })
+        .collect();
+    strings.sort_by_key(|s| s.offset);
+
+    YaraMatch {
+        path: path.to_string(),
+        rule: rule.name.to_string(),
+        tags: rule
+            .tags
+            .iter()
+            .map(|tag| scanner.get_string_symbol(*tag).to_string())
+            .collect(),
+        strings,
+    }
+}
+
+/// Show matched bytes like the yara CLI: printable ASCII as is, the rest escaped
+fn printable(data: &[u8]) -> String {
+    let mut text = String::new();
+    for &byte in data {
+        if text.chars().count() >= MAX_MATCH_DATA_LEN {
+            break;
+        }
+        if byte.is_ascii_graphic() || byte == b' ' {
+            text.push(byte as char);
+        } else {
+            text.push_str(&format!("\\x{:02x}", byte));
+        }
+    }
+    text
+}
+
+/// Scan the package payload and maintainer scripts with a rules directory
+///
+/// Returns the payload matches (to be attached to `FileInfo` entries) and
+/// the scan summary including script matches.
+pub fn scan_package(
+    rules_dir: &Path,
+    files: &[FileInfo],
+    extract_path: &Path,
+    control_path: &Path,
+) -> (Vec<YaraMatch>, YaraScan) {
+    let rule_files = collect_rule_files(rules_dir);
+    let mut scan = YaraScan {
+        rules_dir: rules_dir.to_string_lossy().to_string(),
+        rule_files: rule_files.clone(),
+        script_matches: Vec::new(),
+        skipped: Vec::new(),
+        error: None,
+    };
+
+    if rule_files.is_empty() {
+        scan.error = Some("No .yar or .yara rule files found".to_string());
+        return (Vec::new(), scan);
+    }
+
+    let (mut scanner, mut errors) = compile_rules(&rule_files);
+    if errors.len() == rule_files.len() {
+        scan.error = Some(errors.join("\n"));
+        return (Vec::new(), scan);
+    }
+
+    let mut targets: Vec<(PathBuf, &str, bool)> = Vec::new();
+    // Scripts first: they are few, small and the most interesting
+    let scripts = ["preinst", "postinst", "prerm", "postrm", "config"];
+    for script in scripts {
+        let script_path = control_path.join(script);
+        if is_regular_file(&script_path) {
+            targets.push((script_path, script, true));
+        }
+    }
+    for file in files {
+        let file_path = extract_path.join(&file.path);
+        if matches!(file.file_type, FileType::Directory | FileType::Symlink)
+            || file.size > MAX_SCAN_SIZE
+            || !is_regular_file(&file_path)
+        {
+            continue;
+        }
+        targets.push((file_path, &file.path, false));
+    }
+
+    let deadline = Instant::now() + SCAN_BUDGET;
+    let mut payload_matches = Vec::new();
+    for (file_path, path, is_script) in targets {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            scan.skipped.push(path.to_string());
+            continue;
+        }
+        scanner.set_scan_params(scan_params(SCAN_TIMEOUT.min(remaining)));
+        match scan_file(&scanner, &file_path, path) {
+            Ok(matches) if is_script => scan.script_matches.extend(matches),
+            Ok(matches) => payload_matches.extend(matches),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if !errors.is_empty() {
+        scan.error = Some(errors.join("\n"));
+    }
+    (payload_matches, scan)
+}
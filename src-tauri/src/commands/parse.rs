@@ -1,5 +1,30 @@
-use crate::extractors::deb::{DebExtractor, FileInfo};
-use crate::extractors::elf::{analyze_elf, analyze_desktop};
+use crate::analyzers::accounts::{inventory_accounts, AccountInventory};
+use crate::analyzers::alternatives::{inventory_alternatives, AlternativesInventory};
+use crate::analyzers::appstream::cross_check_appstream;
+use crate::analyzers::arch::{check_architecture, ArchitectureCheck};
+use crate::analyzers::bundled::{inventory_bundled, BundledComponent};
+use crate::analyzers::certs::{inventory_certificates, CertificateInventory};
+use crate::analyzers::desktop::{desktop_entry_programs, resolve_desktop_entry, InstalledPrograms};
+use crate::analyzers::packer::{detect_packers, PackerFinding};
+use crate::analyzers::script_commands::{
+    check_script_commands, script_command_names, DpkgDatabase, ScriptCommandReport, DPKG_ADMIN_DIR,
+};
+use crate::analyzers::secrets::{detect_secrets, SecretFinding};
+use crate::analyzers::shebang::{check_interpreters, parse_shebangs, ShebangInfo};
+use crate::analyzers::systemd::{analyze_units, unit_programs};
+use crate::analyzers::yara::{scan_package, YaraScan, YARA_RULES_ENV};
+use crate::extractors::appstream::{analyze_appstream, is_metainfo_file, AppStreamComponent};
+use crate::extractors::archive::{analyze_archive, ArchiveInfo};
+use crate::extractors::changelog::{analyze_changelogs, ChangelogInfo};
+use crate::extractors::copyright::{analyze_copyright, CopyrightInfo};
+use crate::extractors::deb::{is_regular_file, DebExtractor, FileInfo};
+use crate::extractors::desktop::{analyze_desktop, DesktopEntry};
+use crate::extractors::elf::{analyze_elf, collect_build_id_files};
+use crate::extractors::integration::{analyze_integration, is_integration_file, IntegrationEntry};
+use crate::extractors::kmod::{decompress_module, is_compressed_module};
+use crate::extractors::systemd::{analyze_unit, drop_in_location, unit_location, SystemdUnit};
+use crate::utils::checksum::file_hashes;
+use crate::utils::entropy::file_entropy;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::fs;
@@ -17,7 +42,59 @@ pub struct ParseResult {
     pub elf_info: Option<std::collections::HashMap<String, crate::extractors::elf::ELFInfo>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "desktopInfo")]
-    pub desktop_info: Option<std::collections::HashMap<String, std::collections::HashMap<String, String>>>,
+    pub desktop_info: Option<std::collections::HashMap<String, DesktopEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "appstreamInfo")]
+    pub appstream_info: Option<std::collections::HashMap<String, AppStreamComponent>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "systemdUnits")]
+    pub systemd_units: Option<std::collections::HashMap<String, SystemdUnit>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrations: Option<std::collections::HashMap<String, IntegrationEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "archiveInfo")]
+    pub archive_info: Option<std::collections::HashMap<String, ArchiveInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "debugPackage")]
+    pub debug_package: Option<String>,
+    /// Why the debug package could not be used; it is optional, so this is only a note
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "debugPackageError")]
+    pub debug_package_error: Option<String>,
+    #[serde(rename = "architectureCheck")]
+    pub architecture_check: ArchitectureCheck,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "packerFindings")]
+    pub packer_findings: Option<Vec<PackerFinding>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "secretFindings")]
+    pub secret_findings: Option<Vec<SecretFinding>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "certificateInventory")]
+    pub certificate_inventory: Option<CertificateInventory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "accountInventory")]
+    pub account_inventory: Option<AccountInventory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "alternativesInventory")]
+    pub alternatives_inventory: Option<AlternativesInventory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "scriptCommands")]
+    pub script_commands: Option<ScriptCommandReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "scriptInfo")]
+    pub script_info: Option<std::collections::HashMap<String, ShebangInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<ChangelogInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copyright: Option<CopyrightInfo>,
+    /// Vendored npm, Python, Ruby and Java components keyed by ecosystem
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "bundledComponents")]
+    pub bundled_components: Option<std::collections::BTreeMap<String, Vec<BundledComponent>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "yaraScan")]
+    pub yara_scan: Option<YaraScan>,
     pub stats: ParseStats,
 }
 
@@ -35,20 +112,26 @@ pub struct ParseStats {
     pub elf_count: usize,
     #[serde(rename = "desktopCount")]
     pub desktop_count: usize,
+    #[serde(rename = "archiveCount")]
+    pub archive_count: usize,
 }
 
 #[tauri::command]
-pub async fn parse_deb_package(file_path: String) -> Result<ParseResult, String> {
+pub async fn parse_deb_package(
+    file_path: String,
+    debug_path: Option<String>,
+    yara_rules_dir: Option<String>,
+) -> Result<ParseResult, String> {
     let start_time = std::time::Instant::now();
 
     // Validate file extension
     let path = Path::new(&file_path);
     if let Some(ext) = path.extension() {
         let ext_lower = ext.to_string_lossy().to_lowercase();
-        if ext_lower != "deb" && ext_lower != "udeb" {
-            return Err(format!(
-                "Invalid file type. Only .deb and .udeb files are supported"
-            ));
+        if ext_lower != "deb" && ext_lower != "udeb" && ext_lower != "ddeb" {
+            return Err(
+                "Invalid file type. Only .deb, .udeb and .ddeb files are supported".to_string()
+            );
         }
     } else {
         return Err("Invalid file path".to_string());
@@ -95,7 +178,21 @@ pub async fn parse_deb_package(file_path: String) -> Result<ParseResult, String>
     let metadata = DebExtractor::parse_metadata(&control_path)?;
 
     // List files
-    let files = DebExtractor::list_files(&extract_path)?;
+    let mut files = DebExtractor::list_files(&extract_path)?;
+
+    // Measure per-file entropy and checksum
+    for file in files.iter_mut() {
+        // FIFOs are listed as files and would block on open
+        let path = extract_path.join(&file.path);
+        if !is_regular_file(&path) {
+            continue;
+        }
+        file.entropy = file_entropy(&path);
+        if let Some(hashes) = file_hashes(&path) {
+            file.sha1 = Some(hashes.sha1);
+            file.sha256 = Some(hashes.sha256);
+        }
+    }
 
     // Parse scripts
     let scripts = DebExtractor::parse_scripts(&extract_path).ok();
@@ -113,12 +210,128 @@ pub async fn parse_deb_package(file_path: String) -> Result<ParseResult, String>
     let mut elf_info = std::collections::HashMap::new();
     for file in elf_files {
         let full_path = extract_path.join(&file.path);
-        if let Ok(info) = analyze_elf(full_path.to_str().unwrap()) {
+        if let Ok(info) = analyze_elf(full_path.to_str().unwrap(), &extract_path) {
             elf_info.insert(file.path.clone(), info);
         }
     }
 
-    // Analyze desktop files
+    // Compressed kernel modules are inflated into the work directory for readelf
+    let module_work_dir = temp_dir.join("modules");
+    let compressed_modules = files
+        .iter()
+        .filter(|f| is_compressed_module(&f.path) && is_regular_file(&extract_path.join(&f.path)))
+        .take(20);
+    for (index, file) in compressed_modules.enumerate() {
+        let module_path = module_work_dir.join(format!("{}.ko", index));
+        let inflated = fs::create_dir_all(&module_work_dir)
+            .map_err(|e| e.to_string())
+            .and_then(|_| decompress_module(&extract_path.join(&file.path), &module_path));
+        if inflated.is_ok() {
+            if let Ok(info) = analyze_elf(module_path.to_str().unwrap(), &extract_path) {
+                elf_info.insert(file.path.clone(), info);
+            }
+        }
+        let _ = fs::remove_file(&module_path);
+    }
+
+    // Pair binaries with their detached debug files by build ID, looking in
+    // the package itself and in a matching -dbgsym package
+    let mut build_id_files = collect_build_id_files(&extract_path);
+    let debug_package = match debug_path {
+        Some(debug_path) => Some(PathBuf::from(debug_path)),
+        None => DebExtractor::find_debug_package(path, &metadata),
+    };
+    let mut debug_package_name = None;
+    let mut debug_package_error = None;
+    if let Some(debug_package) = debug_package {
+        let debug_extract_path = temp_dir.join("dbgsym");
+        match DebExtractor::extract_data(&debug_package.to_string_lossy(), &debug_extract_path) {
+            Ok(_) => {
+                build_id_files.extend(collect_build_id_files(&debug_extract_path));
+                debug_package_name = debug_package
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string());
+            }
+            Err(e) => {
+                debug_package_error = Some(format!(
+                    "Debug package {} extract failed: {}",
+                    debug_package.display(),
+                    e
+                ))
+            }
+        }
+    }
+
+    for info in elf_info.values_mut() {
+        if let Some(build_id) = &info.build_id {
+            info.debug_file = build_id_files.get(build_id).cloned();
+        }
+    }
+
+    // Analyze static libraries (limit to first 20)
+    let archive_files: Vec<_> = files
+        .iter()
+        .filter(|f| f.is_archive)
+        .take(20)
+        .collect();
+
+    let mut archive_info = std::collections::HashMap::new();
+    let archive_work_dir = temp_dir.join("archive");
+    for file in archive_files {
+        let full_path = extract_path.join(&file.path);
+        if let Ok(info) = analyze_archive(&full_path, &archive_work_dir, &extract_path) {
+            archive_info.insert(file.path.clone(), info);
+        }
+    }
+
+    // Compare ELF machine types against the declared architecture
+    let architecture_check = check_architecture(&metadata.architecture, &elf_info);
+
+    // Look for packed binaries and unexplained high-entropy data
+    let packer_findings = detect_packers(&files, &elf_info, &extract_path);
+
+    // Look for leaked keys, tokens and credential files
+    let secret_findings = detect_secrets(&files, &extract_path);
+
+    // Inventory certificates and keys
+    let certificate_inventory = inventory_certificates(&files, &extract_path);
+
+    // Inventory users, groups and paths the package creates
+    let account_inventory = inventory_accounts(&files, &extract_path, scripts.as_ref());
+
+    // Find alternatives and diversions set up by maintainer scripts
+    let alternatives_inventory = scripts
+        .as_ref()
+        .map(|scripts| inventory_alternatives(scripts, &extract_path))
+        .filter(|inventory| !inventory.is_empty());
+
+    // Parse the shebangs of scripts shipped in the payload
+    let mut script_info = parse_shebangs(&files, &extract_path);
+
+    // Parse the Debian and upstream changelogs
+    let changelog = analyze_changelogs(&files, &extract_path, &metadata);
+
+    // Parse the copyright file and map licences onto the payload
+    let copyright = analyze_copyright(&files, &extract_path, &metadata);
+
+    // Find dependencies vendored by language package managers
+    let bundled_components = inventory_bundled(&files, &extract_path);
+
+    // Run YARA rules over the payload and maintainer scripts
+    let yara_rules_dir = yara_rules_dir
+        .or_else(|| std::env::var(YARA_RULES_ENV).ok())
+        .filter(|dir| !dir.is_empty());
+    let yara_scan = yara_rules_dir.map(|rules_dir| {
+        let (matches, scan) = scan_package(Path::new(&rules_dir), &files, &extract_path, &control_path);
+        for yara_match in matches {
+            if let Some(file) = files.iter_mut().find(|f| f.path == yara_match.path) {
+                file.yara_matches.get_or_insert_with(Vec::new).push(yara_match);
+            }
+        }
+        scan
+    });
+
+    // Parse desktop files
     let desktop_files: Vec<_> = files
         .iter()
         .filter(|f| f.is_desktop)
@@ -132,6 +345,76 @@ pub async fn parse_deb_package(file_path: String) -> Result<ParseResult, String>
         }
     }
 
+    // Parse systemd units with their drop-ins, applied in file name order across unit directories
+    let mut drop_ins: std::collections::HashMap<_, Vec<&str>> = std::collections::HashMap::new();
+    for file in &files {
+        if let Some(location) = drop_in_location(&file.path) {
+            drop_ins.entry(location).or_default().push(&file.path);
+        }
+    }
+    for paths in drop_ins.values_mut() {
+        paths.sort_by_key(|path| path.rsplit('/').next().unwrap_or_default());
+    }
+    let mut systemd_units = std::collections::HashMap::new();
+    for file in &files {
+        let Some((name, scope)) = unit_location(&file.path) else {
+            continue;
+        };
+        let full_path = extract_path.join(&file.path);
+        let unit_drop_ins = drop_ins.get(&(name, scope)).cloned().unwrap_or_default();
+        let drop_in_paths: Vec<_> = unit_drop_ins.iter().map(|path| extract_path.join(path)).collect();
+        if let Ok(mut unit) = analyze_unit(full_path.to_str().unwrap(), &drop_in_paths, name, scope) {
+            unit.drop_ins = unit_drop_ins.iter().map(|path| path.to_string()).collect();
+            systemd_units.insert(file.path.clone(), unit);
+        }
+    }
+
+    // Read the dpkg database once for every program the package runs but may not ship
+    let programs: Vec<String> = desktop_info
+        .values()
+        .flat_map(desktop_entry_programs)
+        .chain(unit_programs(&systemd_units))
+        .chain(scripts.as_ref().map(script_command_names).unwrap_or_default())
+        .chain(script_info.values().map(|info| info.program.clone()))
+        .collect();
+    let database = DpkgDatabase::load(Path::new(DPKG_ADMIN_DIR), &programs.iter().map(|p| p.as_str()).collect());
+
+    // Check that commands run by maintainer scripts are guaranteed to be installed
+    let script_commands = scripts
+        .as_ref()
+        .map(|scripts| check_script_commands(scripts, &metadata, &extract_path, &database))
+        .filter(|report| !report.commands.is_empty());
+
+    // Check the interpreters of scripts shipped in the payload
+    check_interpreters(&mut script_info, &extract_path, &metadata, &database);
+
+    // Resolve the programs desktop entries and units run, outside the package only
+    // Essential packages and dependencies count as installed
+    let installed = InstalledPrograms::new(&metadata, &database);
+    for info in desktop_info.values_mut() {
+        resolve_desktop_entry(info, &extract_path, &installed);
+    }
+    analyze_units(&mut systemd_units, &extract_path, &installed);
+
+    // Analyze AppStream metainfo
+    let mut appstream_info = std::collections::HashMap::new();
+    for file in files.iter().filter(|f| is_metainfo_file(&f.path)) {
+        let full_path = extract_path.join(&file.path);
+        if let Ok(mut component) = analyze_appstream(full_path.to_str().unwrap()) {
+            cross_check_appstream(&mut component, &file.path, &desktop_info, &metadata.version, &extract_path);
+            appstream_info.insert(file.path.clone(), component);
+        }
+    }
+
+    // Parse system integration files
+    let mut integrations = std::collections::HashMap::new();
+    for file in files.iter().filter(|f| is_integration_file(&f.path)) {
+        let full_path = extract_path.join(&file.path);
+        if let Ok(entry) = analyze_integration(full_path.to_str().unwrap(), &file.path) {
+            integrations.insert(file.path.clone(), entry);
+        }
+    }
+
     // Calculate extracted size
     let extracted_size = files
         .iter()
@@ -141,6 +424,7 @@ pub async fn parse_deb_package(file_path: String) -> Result<ParseResult, String>
 
     let elf_count = files.iter().filter(|f| f.is_elf).count();
     let desktop_count = files.iter().filter(|f| f.is_desktop).count();
+    let archive_count = files.iter().filter(|f| f.is_archive).count();
     let file_count = files.len();
     let parse_time = start_time.elapsed().as_millis() as u64;
 
@@ -151,6 +435,24 @@ pub async fn parse_deb_package(file_path: String) -> Result<ParseResult, String>
         control_files,
         elf_info: if elf_info.is_empty() { None } else { Some(elf_info) },
         desktop_info: if desktop_info.is_empty() { None } else { Some(desktop_info) },
+        appstream_info: if appstream_info.is_empty() { None } else { Some(appstream_info) },
+        systemd_units: if systemd_units.is_empty() { None } else { Some(systemd_units) },
+        integrations: if integrations.is_empty() { None } else { Some(integrations) },
+        archive_info: if archive_info.is_empty() { None } else { Some(archive_info) },
+        debug_package: debug_package_name,
+        debug_package_error,
+        architecture_check,
+        packer_findings: if packer_findings.is_empty() { None } else { Some(packer_findings) },
+        secret_findings: if secret_findings.is_empty() { None } else { Some(secret_findings) },
+        certificate_inventory: if certificate_inventory.is_empty() { None } else { Some(certificate_inventory) },
+        account_inventory: if account_inventory.is_empty() { None } else { Some(account_inventory) },
+        alternatives_inventory,
+        script_commands,
+        script_info: if script_info.is_empty() { None } else { Some(script_info) },
+        changelog,
+        copyright,
+        bundled_components: if bundled_components.is_empty() { None } else { Some(bundled_components) },
+        yara_scan,
         stats: ParseStats {
             parse_time,
             original_size,
@@ -158,6 +460,7 @@ pub async fn parse_deb_package(file_path: String) -> Result<ParseResult, String>
             file_count,
             elf_count,
             desktop_count,
+            archive_count,
         },
     })
 }
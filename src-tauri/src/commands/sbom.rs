@@ -0,0 +1,598 @@
+use crate::commands::parse::ParseResult;
+use crate::extractors::copyright::is_spdx_expression;
+use crate::extractors::deb::FileType;
+use crate::utils::checksum::hex;
+use regex::Regex;
+use serde_json::{json, Value};
+use sha1::Sha1;
+use sha2::Digest;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::sync::OnceLock;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+const TOOL_NAME: &str = "viewdeb";
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Fields of the control file that declare dependencies and whether they are required
+const DEPENDENCY_FIELDS: &[(&str, bool)] = &[
+    ("Pre-Depends", true),
+    ("Depends", true),
+    ("Recommends", false),
+    ("Suggests", false),
+];
+
+/// Write a parsed package as an SPDX 2.3 or CycloneDX 1.5 JSON document
+#[tauri::command]
+pub async fn export_sbom(result: ParseResult, format: String, output_path: String) -> Result<(), String> {
+    let inventory = SbomInventory::from_result(&result);
+    let document = match format.as_str() {
+        "spdx" => spdx_document(&inventory),
+        "cyclonedx" => cyclonedx_document(&inventory),
+        _ => return Err(format!("Unsupported SBOM format: {}", format)),
+    };
+    let json = serde_json::to_string_pretty(&document).map_err(|e| format!("Failed to serialize SBOM: {}", e))?;
+    fs::write(&output_path, json).map_err(|e| format!("Failed to write SBOM: {}", e))
+}
+
+/// Everything both formats describe, gathered once from the parse result
+struct SbomInventory {
+    name: String,
+    version: String,
+    architecture: String,
+    purl: String,
+    supplier: Option<String>,
+    homepage: Option<String>,
+    description: Option<String>,
+    /// SPDX expression from the copyright file
+    license: Option<String>,
+    dependencies: Vec<DeclaredDependency>,
+    embedded: Vec<EmbeddedComponent>,
+    files: Vec<SbomFile>,
+}
+
+struct DeclaredDependency {
+    name: String,
+    constraint: Option<String>,
+    field: &'static str,
+    required: bool,
+    /// Other packages that satisfy the same relation
+    alternatives: Vec<String>,
+}
+
+struct EmbeddedComponent {
+    name: String,
+    version: Option<String>,
+    purl: Option<String>,
+    /// Declared licence, not necessarily an SPDX expression
+    license: Option<String>,
+    ecosystem: String,
+    /// Installed path the component was found in
+    path: String,
+}
+
+struct SbomFile {
+    path: String,
+    sha1: Option<String>,
+    sha256: Option<String>,
+    md5: Option<String>,
+    license: Option<String>,
+}
+
+impl SbomInventory {
+    fn from_result(result: &ParseResult) -> SbomInventory {
+        let metadata = &result.metadata;
+        let md5sums: HashMap<&str, &str> = result
+            .control_files
+            .md5sums
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split_once(char::is_whitespace))
+            .map(|(hash, path)| (path.trim(), hash))
+            .collect();
+        let file_licenses = result.copyright.as_ref().map(|c| &c.file_licenses);
+
+        let files = result
+            .files
+            .iter()
+            .filter(|f| f.file_type != FileType::Directory && f.file_type != FileType::Symlink)
+            .map(|f| SbomFile {
+                path: f.path.clone(),
+                sha1: f.sha1.clone(),
+                sha256: f.sha256.clone(),
+                md5: md5sums.get(f.path.as_str()).map(|hash| hash.to_string()),
+                license: file_licenses.and_then(|licenses| licenses.get(&f.path)).cloned(),
+            })
+            .collect();
+
+        SbomInventory {
+            purl: deb_purl(&metadata.package, Some(&metadata.version), Some(&metadata.architecture)),
+            name: metadata.package.clone(),
+            version: metadata.version.clone(),
+            architecture: metadata.architecture.clone(),
+            supplier: metadata.maintainer.clone(),
+            homepage: metadata.homepage.clone(),
+            description: metadata.description.as_ref().map(|d| d.lines().next().unwrap_or_default().to_string()),
+            license: package_license(result),
+            dependencies: declared_dependencies(result),
+            embedded: embedded_components(result),
+            files,
+        }
+    }
+}
+
+/// `pkg:deb/debian/name@version?arch=arch`, with the version percent-encoded
+fn deb_purl(name: &str, version: Option<&str>, architecture: Option<&str>) -> String {
+    let mut purl = format!("pkg:deb/debian/{}", name);
+    if let Some(version) = version {
+        purl.push('@');
+        purl.push_str(&version.replace(':', "%3A").replace('+', "%2B"));
+    }
+    if let Some(architecture) = architecture {
+        purl.push_str("?arch=");
+        purl.push_str(architecture);
+    }
+    purl
+}
+
+/// Licences of the Files stanzas that match something, or the detected free-form licences
+fn package_license(result: &ParseResult) -> Option<String> {
+    let copyright = result.copyright.as_ref()?;
+    let expressions: BTreeSet<&str> = if copyright.machine_readable {
+        copyright
+            .stanzas
+            .iter()
+            .filter(|stanza| stanza.matched > 0 && !stanza.spdx.is_empty())
+            .map(|stanza| stanza.spdx.as_str())
+            .collect()
+    } else {
+        copyright.detected_licenses.iter().map(|id| id.as_str()).collect()
+    };
+    if expressions.is_empty() {
+        return None;
+    }
+    if expressions.len() == 1 {
+        return expressions.into_iter().next().map(|e| e.to_string());
+    }
+    Some(
+        expressions
+            .iter()
+            .map(|e| if e.contains(" OR ") { format!("({})", e) } else { e.to_string() })
+            .collect::<Vec<_>>()
+            .join(" AND "),
+    )
+}
+
+fn declared_dependencies(result: &ParseResult) -> Vec<DeclaredDependency> {
+    let metadata = &result.metadata;
+    let mut dependencies = Vec::new();
+
+    for (field, required) in DEPENDENCY_FIELDS {
+        let value = match *field {
+            "Pre-Depends" => &metadata.pre_depends,
+            "Depends" => &metadata.depends,
+            "Recommends" => &metadata.recommends,
+            _ => &metadata.suggests,
+        };
+        for relation in value.as_deref().unwrap_or_default().split(',') {
+            let mut choices = relation.split('|').filter_map(|choice| {
+                let choice = choice.trim();
+                let name = choice.split([' ', '(', '[', ':']).next().filter(|n| !n.is_empty())?;
+                let constraint = choice
+                    .split_once('(')
+                    .and_then(|(_, rest)| rest.split_once(')'))
+                    .map(|(constraint, _)| constraint.trim().to_string());
+                Some((name.to_string(), constraint))
+            });
+            let Some((name, constraint)) = choices.next() else {
+                continue;
+            };
+            // A package listed in several fields keeps its strongest relation
+            if dependencies.iter().any(|d: &DeclaredDependency| d.name == name) {
+                continue;
+            }
+            dependencies.push(DeclaredDependency {
+                name,
+                constraint,
+                field,
+                required: *required,
+                alternatives: choices.map(|(name, _)| name).collect(),
+            });
+        }
+    }
+
+    dependencies
+}
+
+/// Go modules and Rust crates recorded in binaries, and vendored language packages
+fn embedded_components(result: &ParseResult) -> Vec<EmbeddedComponent> {
+    let mut components = Vec::new();
+    let mut elf_paths: Vec<&String> = result.elf_info.iter().flat_map(|info| info.keys()).collect();
+    elf_paths.sort();
+
+    for path in elf_paths {
+        let info = &result.elf_info.as_ref().unwrap()[path];
+        if let Some(build_info) = &info.go_build_info {
+            let modules = build_info.main.iter().chain(build_info.deps.iter());
+            for module in modules.filter(|m| !m.version.is_empty() && m.version != "(devel)") {
+                components.push(EmbeddedComponent {
+                    name: module.path.clone(),
+                    version: Some(module.version.clone()),
+                    purl: Some(format!("pkg:golang/{}@{}", module.path, module.version)),
+                    license: None,
+                    ecosystem: "go".to_string(),
+                    path: path.clone(),
+                });
+            }
+        }
+        // The root crate is the binary itself, build dependencies are not shipped
+        for package in info.rust_dependencies.iter().flatten().filter(|p| !p.root && p.kind != "build") {
+            components.push(EmbeddedComponent {
+                name: package.name.clone(),
+                version: Some(package.version.clone()),
+                purl: Some(format!("pkg:cargo/{}@{}", package.name, package.version)),
+                license: None,
+                ecosystem: "cargo".to_string(),
+                path: path.clone(),
+            });
+        }
+    }
+
+    for (ecosystem, bundled) in result.bundled_components.iter().flatten() {
+        for component in bundled {
+            components.push(EmbeddedComponent {
+                name: component.name.clone(),
+                version: component.version.clone(),
+                purl: component.purl.clone(),
+                license: component.license.clone(),
+                ecosystem: ecosystem.clone(),
+                path: component.path.clone(),
+            });
+        }
+    }
+
+    components
+}
+
+fn spdx_id(kind: &str, name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '-' })
+        .collect();
+    format!("SPDXRef-{}-{}", kind, name)
+}
+
+/// "Name <email>" as an SPDX `Person: Name (email)` creator/supplier
+fn spdx_person(maintainer: &str) -> String {
+    match maintainer.split_once('<') {
+        Some((name, email)) => format!("Person: {} ({})", name.trim(), email.trim_end_matches('>').trim()),
+        None => format!("Person: {}", maintainer.trim()),
+    }
+}
+
+fn spdx_document(inventory: &SbomInventory) -> Value {
+    let package_id = spdx_id("Package", &inventory.name);
+    let mut packages = Vec::new();
+    let mut relationships = vec![json!({
+        "spdxElementId": "SPDXRef-DOCUMENT",
+        "relationshipType": "DESCRIBES",
+        "relatedSpdxElement": package_id,
+    })];
+    let mut expressions = Vec::new();
+
+    // SPDX files need a SHA1 checksum, files that could not be read are left out
+    let spdx_files: Vec<(&SbomFile, &str)> = inventory
+        .files
+        .iter()
+        .filter_map(|file| Some((file, file.sha1.as_deref()?)))
+        .collect();
+    let file_ids: HashMap<&str, String> = spdx_files
+        .iter()
+        .enumerate()
+        .map(|(index, (file, _))| (file.path.as_str(), format!("SPDXRef-File-{}", index + 1)))
+        .collect();
+
+    let mut package = json!({
+        "SPDXID": package_id,
+        "name": inventory.name,
+        "versionInfo": inventory.version,
+        "supplier": inventory.supplier.as_deref().map_or("NOASSERTION".to_string(), spdx_person),
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": true,
+        "packageVerificationCode": { "packageVerificationCodeValue": package_verification_code(&spdx_files) },
+        "licenseConcluded": "NOASSERTION",
+        "licenseDeclared": inventory.license.as_deref().unwrap_or("NOASSERTION"),
+        "copyrightText": "NOASSERTION",
+        "primaryPackagePurpose": "INSTALL",
+        "externalRefs": [{
+            "referenceCategory": "PACKAGE-MANAGER",
+            "referenceType": "purl",
+            "referenceLocator": inventory.purl,
+        }],
+    });
+    if let Some(homepage) = &inventory.homepage {
+        package["homepage"] = json!(homepage);
+    }
+    if let Some(description) = &inventory.description {
+        package["summary"] = json!(description);
+    }
+    expressions.extend(inventory.license.clone());
+    packages.push(package);
+
+    for dependency in &inventory.dependencies {
+        let id = spdx_id("Dependency", &dependency.name);
+        let mut comment = format!("{}: {}", dependency.field, dependency.name);
+        if let Some(constraint) = &dependency.constraint {
+            comment.push_str(&format!(" ({})", constraint));
+        }
+        if !dependency.alternatives.is_empty() {
+            comment.push_str(&format!(" | {}", dependency.alternatives.join(" | ")));
+        }
+        packages.push(json!({
+            "SPDXID": id,
+            "name": dependency.name,
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": "NOASSERTION",
+            "copyrightText": "NOASSERTION",
+            "primaryPackagePurpose": "INSTALL",
+            "comment": comment,
+            "externalRefs": [{
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": deb_purl(&dependency.name, None, None),
+            }],
+        }));
+        relationships.push(if dependency.required {
+            json!({ "spdxElementId": package_id, "relationshipType": "DEPENDS_ON", "relatedSpdxElement": id })
+        } else {
+            json!({ "spdxElementId": id, "relationshipType": "OPTIONAL_DEPENDENCY_OF", "relatedSpdxElement": package_id })
+        });
+    }
+
+    let mut files = Vec::new();
+    for (file, sha1) in &spdx_files {
+        let mut checksums = vec![json!({ "algorithm": "SHA1", "checksumValue": sha1 })];
+        if let Some(sha256) = &file.sha256 {
+            checksums.push(json!({ "algorithm": "SHA256", "checksumValue": sha256 }));
+        }
+        if let Some(md5) = &file.md5 {
+            checksums.push(json!({ "algorithm": "MD5", "checksumValue": md5 }));
+        }
+        let id = &file_ids[file.path.as_str()];
+        files.push(json!({
+            "SPDXID": id,
+            "fileName": format!("./{}", file.path),
+            "checksums": checksums,
+            "licenseConcluded": file.license.as_deref().unwrap_or("NOASSERTION"),
+            "copyrightText": "NOASSERTION",
+        }));
+        expressions.extend(file.license.clone());
+        relationships.push(json!({ "spdxElementId": package_id, "relationshipType": "CONTAINS", "relatedSpdxElement": id }));
+    }
+
+    for (index, component) in inventory.embedded.iter().enumerate() {
+        let id = spdx_id("Embedded", &format!("{}-{}", index + 1, component.name));
+        let declared = component.license.as_deref().filter(|l| is_spdx_expression(l));
+        let mut comment = format!("{} component found in /{}", component.ecosystem, component.path);
+        if let Some(license) = component.license.as_deref().filter(|_| declared.is_none()) {
+            comment.push_str(&format!(", declared licence: {}", license));
+        }
+        let mut embedded = json!({
+            "SPDXID": id,
+            "name": component.name,
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": declared.unwrap_or("NOASSERTION"),
+            "copyrightText": "NOASSERTION",
+            "primaryPackagePurpose": "LIBRARY",
+            "comment": comment,
+        });
+        if let Some(version) = &component.version {
+            embedded["versionInfo"] = json!(version);
+        }
+        if let Some(purl) = &component.purl {
+            embedded["externalRefs"] = json!([{
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": purl,
+            }]);
+        }
+        expressions.extend(declared.map(|l| l.to_string()));
+        packages.push(embedded);
+        let container = file_ids.get(component.path.as_str()).unwrap_or(&package_id);
+        relationships.push(json!({ "spdxElementId": container, "relationshipType": "CONTAINS", "relatedSpdxElement": id }));
+    }
+
+    let mut document = json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": format!("{}_{}_{}", inventory.name, inventory.version, inventory.architecture),
+        "documentNamespace": format!(
+            "https://spdx.org/spdxdocs/{}-{}-{}",
+            inventory.name,
+            inventory.version.replace(':', "-"),
+            document_uuid()
+        ),
+        "creationInfo": {
+            "created": utc_timestamp(),
+            "creators": [format!("Tool: {}-{}", TOOL_NAME, TOOL_VERSION)],
+        },
+        "documentDescribes": [package_id],
+        "packages": packages,
+        "files": files,
+        "relationships": relationships,
+    });
+
+    // Every LicenseRef used in an expression has to be declared
+    let license_refs: BTreeSet<&str> = expressions
+        .iter()
+        .flat_map(|expression| license_ref_regex().find_iter(expression).map(|m| m.as_str()))
+        .collect();
+    if !license_refs.is_empty() {
+        document["hasExtractedLicensingInfos"] = license_refs
+            .iter()
+            .map(|id| {
+                json!({
+                    "licenseId": id,
+                    "name": id.trim_start_matches("LicenseRef-"),
+                    "extractedText": format!("See /usr/share/doc/{}/copyright", inventory.name),
+                })
+            })
+            .collect();
+    }
+
+    document
+}
+
+/// SPDX package verification code: SHA1 over the sorted file SHA1s
+fn package_verification_code(files: &[(&SbomFile, &str)]) -> String {
+    let mut hashes: Vec<&str> = files.iter().map(|(_, sha1)| *sha1).collect();
+    hashes.sort_unstable();
+    hex(&Sha1::digest(hashes.concat().as_bytes()))
+}
+
+fn cyclonedx_document(inventory: &SbomInventory) -> Value {
+    let licenses = |expression: &Option<String>| match expression {
+        Some(expression) => json!([{ "expression": expression }]),
+        None => json!([]),
+    };
+
+    let mut main = json!({
+        "type": "application",
+        "bom-ref": inventory.purl,
+        "name": inventory.name,
+        "version": inventory.version,
+        "purl": inventory.purl,
+        "licenses": licenses(&inventory.license),
+        "properties": [{ "name": "deb:architecture", "value": inventory.architecture }],
+    });
+    if let Some(supplier) = &inventory.supplier {
+        main["supplier"] = json!({ "name": supplier });
+    }
+    if let Some(description) = &inventory.description {
+        main["description"] = json!(description);
+    }
+    if let Some(homepage) = &inventory.homepage {
+        main["externalReferences"] = json!([{ "type": "website", "url": homepage }]);
+    }
+
+    let mut components = Vec::new();
+    let mut depends_on = Vec::new();
+    for dependency in &inventory.dependencies {
+        let purl = deb_purl(&dependency.name, None, None);
+        let mut properties = vec![json!({ "name": "deb:dependencyField", "value": dependency.field })];
+        if let Some(constraint) = &dependency.constraint {
+            properties.push(json!({ "name": "deb:versionConstraint", "value": constraint }));
+        }
+        if !dependency.alternatives.is_empty() {
+            properties.push(json!({ "name": "deb:alternatives", "value": dependency.alternatives.join(" | ") }));
+        }
+        components.push(json!({
+            "type": "library",
+            "bom-ref": purl,
+            "name": dependency.name,
+            "purl": purl,
+            "scope": if dependency.required { "required" } else { "optional" },
+            "properties": properties,
+        }));
+        if !depends_on.contains(&purl) {
+            depends_on.push(purl);
+        }
+    }
+
+    let mut dependencies = Vec::new();
+    let mut file_dependencies: HashMap<&str, Vec<String>> = HashMap::new();
+    for (index, component) in inventory.embedded.iter().enumerate() {
+        let bom_ref = match &component.purl {
+            Some(purl) => format!("{}#{}", purl, index + 1),
+            None => format!("{}:{}#{}", component.ecosystem, component.name, index + 1),
+        };
+        let mut embedded = json!({
+            "type": "library",
+            "bom-ref": bom_ref,
+            "name": component.name,
+            "properties": [
+                { "name": "viewdeb:ecosystem", "value": component.ecosystem },
+                { "name": "viewdeb:foundIn", "value": format!("/{}", component.path) },
+            ],
+        });
+        if let Some(version) = &component.version {
+            embedded["version"] = json!(version);
+        }
+        if let Some(purl) = &component.purl {
+            embedded["purl"] = json!(purl);
+        }
+        match component.license.as_deref() {
+            Some(license) if is_spdx_expression(license) => embedded["licenses"] = json!([{ "expression": license }]),
+            Some(license) => embedded["licenses"] = json!([{ "license": { "name": license } }]),
+            None => {}
+        }
+        components.push(embedded);
+        file_dependencies.entry(component.path.as_str()).or_default().push(bom_ref);
+    }
+
+    for file in &inventory.files {
+        let mut hashes = Vec::new();
+        if let Some(sha1) = &file.sha1 {
+            hashes.push(json!({ "alg": "SHA-1", "content": sha1 }));
+        }
+        if let Some(sha256) = &file.sha256 {
+            hashes.push(json!({ "alg": "SHA-256", "content": sha256 }));
+        }
+        if let Some(md5) = &file.md5 {
+            hashes.push(json!({ "alg": "MD5", "content": md5 }));
+        }
+        let bom_ref = format!("file:/{}", file.path);
+        components.push(json!({
+            "type": "file",
+            "bom-ref": bom_ref,
+            "name": format!("/{}", file.path),
+            "hashes": hashes,
+            "licenses": licenses(&file.license),
+        }));
+        if let Some(embedded) = file_dependencies.remove(file.path.as_str()) {
+            dependencies.push(json!({ "ref": bom_ref, "dependsOn": embedded }));
+        }
+    }
+    dependencies.insert(0, json!({ "ref": inventory.purl, "dependsOn": depends_on }));
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{}", document_uuid()),
+        "version": 1,
+        "metadata": {
+            "timestamp": utc_timestamp(),
+            "tools": { "components": [{ "type": "application", "name": TOOL_NAME, "version": TOOL_VERSION }] },
+            "component": main,
+        },
+        "components": components,
+        "dependencies": dependencies,
+    })
+}
+
+/// Random version 4 UUID identifying one export
+fn document_uuid() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Current time as `YYYY-MM-DDTHH:MM:SSZ`, SPDX does not allow fractional seconds
+fn utc_timestamp() -> String {
+    OffsetDateTime::now_utc()
+        .replace_nanosecond(0)
+        .ok()
+        .and_then(|now| now.format(&Rfc3339).ok())
+        .unwrap_or_default()
+}
+
+fn license_ref_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"LicenseRef-[A-Za-z0-9.-]+").unwrap())
+}
@@ -0,0 +1,358 @@
+use crate::extractors::desktop::LocalizedString;
+use crate::utils::issue::{Issue, IssueSeverity};
+use crate::utils::xml::{parse_xml, XmlElement};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Metadata licenses AppStream accepts for the metainfo file itself
+const METADATA_LICENSES: &[&str] = &[
+    "FSFAP", "MIT", "0BSD", "CC0-1.0", "CC-BY-3.0", "CC-BY-4.0", "CC-BY-SA-3.0", "CC-BY-SA-4.0",
+    "GFDL-1.1", "GFDL-1.2", "GFDL-1.3", "GFDL-1.1-or-later", "GFDL-1.2-or-later", "GFDL-1.3-or-later",
+    "BSL-1.0", "FTL", "FSFUL",
+];
+
+/// A parsed AppStream metainfo component
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStreamComponent {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub component_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<LocalizedString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<LocalizedString>,
+    /// Untranslated description as plain text, paragraphs separated by blank lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub developer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata_license: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_license: Option<String>,
+    pub urls: Vec<AppStreamUrl>,
+    pub launchables: Vec<Launchable>,
+    pub releases: Vec<AppStreamRelease>,
+    pub screenshots: Vec<Screenshot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_rating: Option<ContentRating>,
+    pub provides: Provides,
+    pub categories: Vec<String>,
+    pub keywords: Vec<String>,
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStreamUrl {
+    #[serde(rename = "type")]
+    pub url_type: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Launchable {
+    #[serde(rename = "type")]
+    pub launchable_type: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStreamRelease {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub release_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Screenshot {
+    pub default: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    pub images: Vec<String>,
+    pub videos: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentRating {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub rating_type: Option<String>,
+    pub attributes: Vec<ContentAttribute>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentAttribute {
+    pub id: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Provides {
+    pub binaries: Vec<String>,
+    pub mediatypes: Vec<String>,
+    pub libraries: Vec<String>,
+    pub ids: Vec<String>,
+    pub dbus: Vec<String>,
+}
+
+/// Localized child text, `xml:lang` variants keyed by locale
+fn localized(element: &XmlElement, name: &str) -> Option<LocalizedString> {
+    let value = element.child_text(name)?;
+    let localized = element
+        .children_named(name)
+        .filter_map(|c| Some((c.lang()?.to_string(), c.full_text())))
+        .collect();
+    Some(LocalizedString { value, localized })
+}
+
+/// Render a `<description>` block as plain text
+fn render_description(description: &XmlElement) -> String {
+    let mut blocks = Vec::new();
+    for child in description.children.iter().filter(|c| c.lang().is_none()) {
+        match child.name.as_str() {
+            "p" => blocks.push(child.full_text()),
+            "ul" | "ol" => {
+                let items: Vec<String> = child
+                    .children_named("li")
+                    .filter(|li| li.lang().is_none())
+                    .map(|li| format!("• {}", li.full_text()))
+                    .collect();
+                blocks.push(items.join("\n"));
+            }
+            _ => {}
+        }
+    }
+    if blocks.is_empty() && !description.text.trim().is_empty() {
+        blocks.push(description.text.trim().to_string());
+    }
+    blocks.join("\n\n")
+}
+
+/// Metainfo files in the current and legacy install locations
+pub fn is_metainfo_file(path: &str) -> bool {
+    (path.starts_with("usr/share/metainfo/") || path.starts_with("usr/share/appdata/"))
+        && (path.ends_with(".metainfo.xml") || path.ends_with(".appdata.xml"))
+}
+
+/// Parse and validate a metainfo file
+pub fn analyze_appstream(file_path: &str) -> Result<AppStreamComponent, String> {
+    let content = fs::read_to_string(file_path).map_err(|e| format!("Failed to read metainfo file: {}", e))?;
+    Ok(parse_appstream(&content))
+}
+
+pub fn parse_appstream(content: &str) -> AppStreamComponent {
+    let root = match parse_xml(content) {
+        Ok(root) => root,
+        Err(e) => {
+            return AppStreamComponent {
+                issues: vec![Issue {
+                    severity: IssueSeverity::Error,
+                    message: e,
+                }],
+                ..Default::default()
+            };
+        }
+    };
+
+    let mut component = AppStreamComponent::default();
+    if root.name != "component" {
+        component.issues.push(Issue {
+            severity: IssueSeverity::Error,
+            message: format!("Root element is <{}>, expected <component>", root.name),
+        });
+        return component;
+    }
+
+    component.component_type = root.attr("type").map(|t| t.to_string());
+    component.id = root.child_text("id");
+    component.name = localized(&root, "name");
+    component.summary = localized(&root, "summary");
+    component.description = root.child("description").map(render_description).filter(|d| !d.is_empty());
+    component.developer = root
+        .child("developer")
+        .and_then(|d| d.child_text("name"))
+        .or_else(|| root.child_text("developer_name"));
+    component.metadata_license = root.child_text("metadata_license");
+    component.project_license = root.child_text("project_license");
+    component.urls = root
+        .children_named("url")
+        .map(|u| AppStreamUrl {
+            url_type: u.attr("type").unwrap_or("homepage").to_string(),
+            url: u.full_text(),
+        })
+        .collect();
+    component.launchables = root
+        .children_named("launchable")
+        .map(|l| Launchable {
+            launchable_type: l.attr("type").unwrap_or_default().to_string(),
+            value: l.full_text(),
+        })
+        .collect();
+    component.releases = root
+        .child("releases")
+        .map(|r| r.children_named("release").map(parse_release).collect())
+        .unwrap_or_default();
+    component.screenshots = root
+        .child("screenshots")
+        .map(|s| s.children_named("screenshot").map(parse_screenshot).collect())
+        .unwrap_or_default();
+    component.content_rating = root.child("content_rating").map(|r| ContentRating {
+        rating_type: r.attr("type").map(|t| t.to_string()),
+        attributes: r
+            .children_named("content_attribute")
+            .map(|a| ContentAttribute {
+                id: a.attr("id").unwrap_or_default().to_string(),
+                value: a.full_text(),
+            })
+            .collect(),
+    });
+    if let Some(provides) = root.child("provides") {
+        let texts = |name: &str| provides.children_named(name).map(|c| c.full_text()).collect::<Vec<_>>();
+        component.provides = Provides {
+            binaries: texts("binary"),
+            mediatypes: texts("mediatype"),
+            libraries: texts("library"),
+            ids: texts("id"),
+            dbus: texts("dbus"),
+        };
+    }
+    component.categories = root
+        .child("categories")
+        .map(|c| c.children_named("category").map(|c| c.full_text()).collect())
+        .unwrap_or_default();
+    component.keywords = root
+        .child("keywords")
+        .map(|k| k.children_named("keyword").filter(|k| k.lang().is_none()).map(|k| k.full_text()).collect())
+        .unwrap_or_default();
+
+    validate_component(&mut component);
+    component
+}
+
+fn parse_release(release: &XmlElement) -> AppStreamRelease {
+    AppStreamRelease {
+        version: release.attr("version").map(|v| v.to_string()),
+        date: release.attr("date").map(|d| d.to_string()),
+        timestamp: release.attr("timestamp").and_then(|t| t.parse().ok()),
+        release_type: release.attr("type").map(|t| t.to_string()),
+        description: release.child("description").map(render_description).filter(|d| !d.is_empty()),
+    }
+}
+
+fn parse_screenshot(screenshot: &XmlElement) -> Screenshot {
+    let mut images: Vec<String> = screenshot
+        .children_named("image")
+        .filter(|i| i.lang().is_none())
+        .map(|i| i.full_text())
+        .collect();
+    // Legacy form puts the URL directly inside <screenshot>
+    if images.is_empty() && !screenshot.text.trim().is_empty() {
+        images.push(screenshot.text.trim().to_string());
+    }
+    Screenshot {
+        default: screenshot.attr("type") == Some("default"),
+        caption: screenshot.child_text("caption"),
+        images,
+        videos: screenshot.children_named("video").map(|v| v.full_text()).collect(),
+    }
+}
+
+/// Checks in the spirit of `appstreamcli validate`
+fn validate_component(component: &mut AppStreamComponent) {
+    let mut issues = Vec::new();
+    let mut push = |severity: IssueSeverity, message: String| issues.push(Issue { severity, message });
+
+    match &component.id {
+        None => push(IssueSeverity::Error, "Missing required <id>".to_string()),
+        Some(id) => {
+            if id.matches('.').count() < 2 {
+                push(IssueSeverity::Warning, format!("Component id \"{}\" is not a reverse-DNS name", id));
+            }
+            if id.chars().any(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))) {
+                push(IssueSeverity::Error, format!("Component id \"{}\" contains invalid characters", id));
+            }
+        }
+    }
+    if component.name.is_none() {
+        push(IssueSeverity::Error, "Missing required <name>".to_string());
+    }
+    match &component.summary {
+        None => push(IssueSeverity::Error, "Missing required <summary>".to_string()),
+        Some(summary) if summary.value.ends_with('.') => {
+            push(IssueSeverity::Hint, "Summary should not end with a period".to_string())
+        }
+        Some(_) => {}
+    }
+    match &component.metadata_license {
+        None => push(IssueSeverity::Error, "Missing required <metadata_license>".to_string()),
+        Some(license) if !METADATA_LICENSES.contains(&license.as_str()) => push(
+            IssueSeverity::Error,
+            format!("Metadata license \"{}\" is not a permissive license AppStream accepts", license),
+        ),
+        Some(_) => {}
+    }
+    if component.project_license.is_none() {
+        push(IssueSeverity::Warning, "Missing <project_license>".to_string());
+    }
+
+    let is_desktop_app = matches!(component.component_type.as_deref(), Some("desktop-application") | Some("desktop"));
+    if is_desktop_app {
+        if component.description.is_none() {
+            push(IssueSeverity::Error, "Desktop application has no <description>".to_string());
+        }
+        if !component.launchables.iter().any(|l| l.launchable_type == "desktop-id") {
+            push(IssueSeverity::Warning, "Desktop application has no desktop-id <launchable>".to_string());
+        }
+        if component.content_rating.is_none() {
+            push(IssueSeverity::Warning, "Desktop application has no <content_rating>".to_string());
+        }
+        if component.screenshots.is_empty() {
+            push(IssueSeverity::Hint, "Desktop application has no screenshots".to_string());
+        }
+    }
+    if component.component_type.as_deref() == Some("desktop") {
+        push(IssueSeverity::Warning, "Component type \"desktop\" is deprecated, use \"desktop-application\"".to_string());
+    }
+
+    for release in &component.releases {
+        if release.version.is_none() {
+            push(IssueSeverity::Error, "Release without a version".to_string());
+        }
+        if release.date.is_none() && release.timestamp.is_none() {
+            push(
+                IssueSeverity::Warning,
+                format!("Release {} has no date", release.version.as_deref().unwrap_or("?")),
+            );
+        }
+    }
+    let dates: Vec<&str> = component.releases.iter().filter_map(|r| r.date.as_deref()).collect();
+    if dates.windows(2).any(|w| w[0] < w[1]) {
+        push(IssueSeverity::Warning, "Releases are not ordered newest first".to_string());
+    }
+
+    if !component.screenshots.is_empty() && !component.screenshots.iter().any(|s| s.default) {
+        push(IssueSeverity::Hint, "No screenshot is marked as default".to_string());
+    }
+    for url in component.screenshots.iter().flat_map(|s| s.images.iter().chain(&s.videos)) {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            push(IssueSeverity::Error, format!("Screenshot URL \"{}\" is not a web URL", url));
+        }
+    }
+
+    component.issues = issues;
+}
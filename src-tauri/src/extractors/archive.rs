@@ -0,0 +1,332 @@
+use crate::extractors::elf::{analyze_elf, ELFInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+const AR_MAGIC: &[u8; 8] = b"!<arch>\n";
+const AR_THIN_MAGIC: &[u8; 8] = b"!<thin>\n";
+const AR_HEADER_SIZE: u64 = 60;
+
+/// Limit on members run through readelf per archive
+const MAX_ANALYZED_MEMBERS: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveInfo {
+    /// Thin archives only reference their members by path
+    pub thin: bool,
+    pub members: Vec<ArchiveMember>,
+    /// Contents of the `/` (or `/SYM64/`) archive symbol index
+    pub symbol_index: Vec<ArchiveSymbol>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveMember {
+    pub name: String,
+    pub size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elf: Option<ELFInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveSymbol {
+    pub symbol: String,
+    pub member: String,
+}
+
+/// Check for the ar archive magic at the start of a file
+pub fn is_ar_archive(file_path: &Path) -> bool {
+    let mut magic = [0u8; 8];
+    File::open(file_path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .map(|_| &magic == AR_MAGIC || &magic == AR_THIN_MAGIC)
+        .unwrap_or(false)
+}
+
+/// Raw member header position, before long names are resolved
+struct RawMember {
+    name: String,
+    header_offset: u64,
+    data_offset: u64,
+    size: u64,
+}
+
+/// List the members of an ar archive and analyze the ELF objects inside it
+///
+/// Members are copied into `work_dir` so they can be handed to readelf.
+pub fn analyze_archive(file_path: &Path, work_dir: &Path, package_root: &Path) -> Result<ArchiveInfo, String> {
+    let mut file = File::open(file_path)
+        .map_err(|e| format!("Failed to open archive: {}", e))?;
+    let file_len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read archive metadata: {}", e))?
+        .len();
+
+    let mut magic = [0u8; 8];
+    file.read_exact(&mut magic)
+        .map_err(|e| format!("Failed to read archive header: {}", e))?;
+    let thin = &magic == AR_THIN_MAGIC;
+    if !thin && &magic != AR_MAGIC {
+        return Err("Not an ar archive".to_string());
+    }
+
+    let mut raw_members = Vec::new();
+    let mut long_names: Vec<u8> = Vec::new();
+    let mut symbol_table: Option<(Vec<u8>, bool)> = None;
+    let mut offset = 8u64;
+
+    while offset + AR_HEADER_SIZE <= file_len {
+        let mut header = [0u8; AR_HEADER_SIZE as usize];
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| file.read_exact(&mut header))
+            .map_err(|e| format!("Failed to read member header: {}", e))?;
+        if &header[58..60] != b"`\n" {
+            return Err(format!("Corrupt member header at offset {}", offset));
+        }
+
+        let raw_name = String::from_utf8_lossy(&header[0..16]).trim_end().to_string();
+        let size: u64 = String::from_utf8_lossy(&header[48..58])
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid member size at offset {}", offset))?;
+        let data_offset = offset + AR_HEADER_SIZE;
+
+        let special = raw_name == "/" || raw_name == "/SYM64/" || raw_name == "//";
+        // Thin archives still store their index and name table inline
+        let stored_size = if thin && !special { 0 } else { size };
+        if stored_size > file_len - data_offset {
+            return Err(format!(
+                "Member at offset {} claims {} bytes, more than the archive holds",
+                offset, size
+            ));
+        }
+
+        match raw_name.as_str() {
+            "/" | "/SYM64/" => {
+                let data = read_exact_at(&mut file, file_len, data_offset, size)?;
+                symbol_table = Some((data, raw_name == "/SYM64/"));
+            }
+            "//" => {
+                long_names = read_exact_at(&mut file, file_len, data_offset, size)?;
+            }
+            _ => {
+                let (name, name_len) = if let Some(len) = raw_name.strip_prefix("#1/") {
+                    // BSD: the name is stored at the start of the member data
+                    let len: u64 = len.parse().unwrap_or(0).min(size);
+                    let bytes = read_exact_at(&mut file, file_len, data_offset, len)?;
+                    let name = String::from_utf8_lossy(&bytes)
+                        .trim_end_matches('\0')
+                        .to_string();
+                    (name, len)
+                } else {
+                    (raw_name.clone(), 0)
+                };
+                raw_members.push(RawMember {
+                    name,
+                    header_offset: offset,
+                    data_offset: data_offset + name_len,
+                    size: size - name_len,
+                });
+            }
+        }
+
+        // Member data is padded to an even offset
+        offset = data_offset + stored_size + (stored_size % 2);
+    }
+
+    // Resolve GNU "/123" long name references and trailing slashes
+    for member in &mut raw_members {
+        if let Some(index) = member.name.strip_prefix('/').and_then(|i| i.parse::<usize>().ok()) {
+            if let Some(rest) = long_names.get(index..) {
+                let end = rest
+                    .windows(2)
+                    .position(|w| w == b"/\n")
+                    .or_else(|| rest.iter().position(|&b| b == b'\n'))
+                    .unwrap_or(rest.len());
+                member.name = String::from_utf8_lossy(&rest[..end]).to_string();
+            }
+        } else if let Some(stripped) = member.name.strip_suffix('/') {
+            member.name = stripped.to_string();
+        }
+    }
+
+    let symbol_index = match symbol_table {
+        Some((data, is_64)) => parse_symbol_index(&data, is_64, &raw_members),
+        None => Vec::new(),
+    };
+
+    let mut members = Vec::new();
+    for (index, raw) in raw_members.iter().enumerate() {
+        let elf = if !thin && index < MAX_ANALYZED_MEMBERS {
+            analyze_member(&mut file, file_len, raw, index, work_dir, package_root)
+        } else {
+            None
+        };
+        members.push(ArchiveMember {
+            name: raw.name.clone(),
+            size: raw.size,
+            elf,
+        });
+    }
+
+    Ok(ArchiveInfo {
+        thin,
+        members,
+        symbol_index,
+    })
+}
+
+/// Read `size` bytes at `offset`, refusing sizes that run past the end of
+/// the file so a forged header cannot trigger a huge allocation
+fn read_exact_at(file: &mut File, file_len: u64, offset: u64, size: u64) -> Result<Vec<u8>, String> {
+    if offset.checked_add(size).is_none_or(|end| end > file_len) {
+        return Err(format!("Archive member of {} bytes at offset {} is truncated", size, offset));
+    }
+    let mut data = vec![0u8; size as usize];
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(&mut data))
+        .map_err(|e| format!("Failed to read archive data: {}", e))?;
+    Ok(data)
+}
+
+/// Decode the GNU symbol index: a big-endian count, one member header
+/// offset per symbol, then the NUL-terminated symbol names
+fn parse_symbol_index(data: &[u8], is_64: bool, members: &[RawMember]) -> Vec<ArchiveSymbol> {
+    let word = if is_64 { 8 } else { 4 };
+    let read_word = |pos: usize| -> Option<u64> {
+        let bytes = data.get(pos..pos + word)?;
+        Some(bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+    };
+
+    let count = match read_word(0) {
+        Some(count) => count as usize,
+        None => return Vec::new(),
+    };
+    // The count is untrusted, a forged one must not overflow the offset
+    let names_start = match count.checked_add(1).and_then(|n| n.checked_mul(word)) {
+        Some(start) => start,
+        None => return Vec::new(),
+    };
+    let names = match data.get(names_start..) {
+        Some(names) => names,
+        None => return Vec::new(),
+    };
+
+    let by_offset: HashMap<u64, &str> = members
+        .iter()
+        .map(|m| (m.header_offset, m.name.as_str()))
+        .collect();
+
+    names
+        .split(|&b| b == 0)
+        .take(count)
+        .enumerate()
+        .filter_map(|(i, name)| {
+            let member_offset = read_word(word * (i + 1))?;
+            Some(ArchiveSymbol {
+                symbol: String::from_utf8_lossy(name).to_string(),
+                member: by_offset.get(&member_offset).unwrap_or(&"?").to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Copy a member out of the archive and run the ELF analysis on it
+fn analyze_member(
+    file: &mut File,
+    file_len: u64,
+    member: &RawMember,
+    index: usize,
+    work_dir: &Path,
+    package_root: &Path,
+) -> Option<ELFInfo> {
+    let data = read_exact_at(file, file_len, member.data_offset, member.size).ok()?;
+    if !data.starts_with(b"\x7fELF") {
+        return None;
+    }
+
+    fs::create_dir_all(work_dir).ok()?;
+    // Member names may repeat, so prefix them with their position
+    let member_path = work_dir.join(format!("{}_{}", index, member.name.replace('/', "_")));
+    fs::write(&member_path, &data).ok()?;
+    let info = analyze_elf(member_path.to_str()?, package_root).ok();
+    let _ = fs::remove_file(&member_path);
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn header(name: &str, size: usize) -> Vec<u8> {
+        format!("{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n", name, 0, 0, 0, 644, size).into_bytes()
+    }
+
+    fn member(name: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = header(name, data.len());
+        bytes.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            bytes.push(b'\n');
+        }
+        bytes
+    }
+
+    fn archive(members: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = AR_MAGIC.to_vec();
+        for member in members {
+            bytes.extend_from_slice(member);
+        }
+        bytes
+    }
+
+    fn analyze(test: &str, bytes: &[u8]) -> Result<ArchiveInfo, String> {
+        let dir = std::env::temp_dir().join(format!("viewdeb_archive_{}_{}", test, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("lib.a");
+        fs::write(&path, bytes).unwrap();
+        let result = analyze_archive(&path, &dir.join("members"), &PathBuf::from("/nonexistent"));
+        let _ = fs::remove_dir_all(&dir);
+        result
+    }
+
+    fn names(info: &ArchiveInfo) -> Vec<(&str, u64)> {
+        info.members.iter().map(|m| (m.name.as_str(), m.size)).collect()
+    }
+
+    #[test]
+    fn resolves_gnu_long_names() {
+        let bytes = archive(&[
+            member("//", b"a_rather_long_object_name.o/\nsecond_long_object_name.o/\n"),
+            member("/0", b"one"),
+            member("short.o/", b"two!"),
+            member("/29", b"three"),
+        ]);
+        let info = analyze("gnu", &bytes).unwrap();
+        assert!(!info.thin);
+        assert_eq!(
+            names(&info),
+            vec![("a_rather_long_object_name.o", 3), ("short.o", 4), ("second_long_object_name.o", 5)]
+        );
+    }
+
+    #[test]
+    fn reads_bsd_names_from_member_data() {
+        let bytes = archive(&[member("#1/24", b"bsd_long_member_name.o\0\0payload")]);
+        let info = analyze("bsd", &bytes).unwrap();
+        assert_eq!(names(&info), vec![("bsd_long_member_name.o", 7)]);
+    }
+
+    #[test]
+    fn rejects_truncated_member() {
+        let mut bytes = archive(&[member("first.o/", b"data")]);
+        bytes.extend_from_slice(&header("second.o/", 4096));
+        bytes.extend_from_slice(b"only a few bytes");
+        let error = analyze("truncated", &bytes).unwrap_err();
+        assert!(error.contains("more than the archive holds"), "{}", error);
+    }
+}
@@ -0,0 +1,330 @@
+use crate::extractors::deb::{upstream_version, DebianPackageMetadata, FileInfo, FileType};
+use crate::utils::issue::{Issue, IssueSeverity};
+use flate2::read::GzDecoder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Changelogs are small, anything bigger is not worth decompressing
+const MAX_CHANGELOG_SIZE: u64 = 4 * 1024 * 1024;
+/// Entries kept from the Debian changelog, the rest is only counted
+const MAX_ENTRIES: usize = 20;
+/// Lines of the upstream changelog shown as an excerpt
+const UPSTREAM_EXCERPT_LINES: usize = 40;
+
+const DEBIAN_CHANGELOGS: &[&str] = &["changelog.Debian.gz", "changelog.Debian"];
+const UPSTREAM_CHANGELOGS: &[&str] = &[
+    "changelog.gz",
+    "changelog",
+    "CHANGELOG.md.gz",
+    "CHANGELOG.md",
+    "CHANGES.gz",
+    "CHANGES",
+    "NEWS.gz",
+    "NEWS",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debian: Option<DebianChangelog>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<UpstreamChangelog>,
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebianChangelog {
+    pub path: String,
+    /// Newest first, at most MAX_ENTRIES
+    pub entries: Vec<ChangelogEntry>,
+    pub entry_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogEntry {
+    pub package: String,
+    pub version: String,
+    pub distributions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urgency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintainer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    pub changes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamChangelog {
+    pub path: String,
+    pub excerpt: String,
+    /// Whether the upstream version of the package appears in the changelog
+    pub mentions_version: bool,
+}
+
+/// Locate and parse the Debian and upstream changelogs in /usr/share/doc/<package>
+pub fn analyze_changelogs(
+    files: &[FileInfo],
+    extract_path: &Path,
+    metadata: &DebianPackageMetadata,
+) -> Option<ChangelogInfo> {
+    let doc_dir = format!("usr/share/doc/{}/", metadata.package);
+    // Symlinks may point outside the extracted tree
+    let find = |name: &str| {
+        let path = format!("{}{}", doc_dir, name);
+        files
+            .iter()
+            .find(|f| f.path == path && f.file_type == FileType::File)
+            .map(|f| f.path.clone())
+    };
+    let mut issues = Vec::new();
+
+    let mut debian_path = DEBIAN_CHANGELOGS.iter().find_map(|name| find(name));
+    let mut native = false;
+    if debian_path.is_none() {
+        // Native packages ship their Debian changelog as changelog.gz
+        debian_path = find("changelog.gz").filter(|path| {
+            read_text(&extract_path.join(path)).is_ok_and(|text| !parse_entries(&text).is_empty())
+        });
+        native = debian_path.is_some();
+    }
+
+    let mut debian = None;
+    if let Some(path) = &debian_path {
+        let mut entries = Vec::new();
+        // A binNMU changelog.Debian.<arch>.gz holds the newest entry
+        if let Some(binnmu) = find(&format!("changelog.Debian.{}.gz", metadata.architecture)) {
+            if let Ok(text) = read_text(&extract_path.join(&binnmu)) {
+                entries.extend(parse_entries(&text));
+            }
+        }
+        match read_text(&extract_path.join(path)) {
+            Ok(text) => entries.extend(parse_entries(&text)),
+            Err(e) => issues.push(Issue {
+                severity: IssueSeverity::Error,
+                message: format!("Failed to read {}: {}", path, e),
+            }),
+        }
+        if !path.ends_with(".gz") {
+            issues.push(Issue {
+                severity: IssueSeverity::Hint,
+                message: format!("{} should be compressed with gzip -9n", path),
+            });
+        }
+        check_entries(&entries, metadata, &mut issues);
+        let entry_count = entries.len();
+        entries.truncate(MAX_ENTRIES);
+        debian = Some(DebianChangelog {
+            path: path.clone(),
+            entries,
+            entry_count,
+        });
+    } else {
+        issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: format!("No Debian changelog in /{}", doc_dir),
+        });
+    }
+
+    let upstream = UPSTREAM_CHANGELOGS
+        .iter()
+        .filter_map(|name| find(name))
+        .find(|path| Some(path) != debian_path.as_ref())
+        .and_then(|path| {
+            let text = read_text(&extract_path.join(&path)).ok()?;
+            let upstream = upstream_version(&metadata.version);
+            let mentions_version = native || text.contains(upstream);
+            if !mentions_version {
+                issues.push(Issue {
+                    severity: IssueSeverity::Hint,
+                    message: format!("{} does not mention upstream version {}", path, upstream),
+                });
+            }
+            Some(UpstreamChangelog {
+                excerpt: text.lines().take(UPSTREAM_EXCERPT_LINES).collect::<Vec<_>>().join("\n"),
+                path,
+                mentions_version,
+            })
+        });
+
+    if debian.is_none() && upstream.is_none() && !files.iter().any(|f| f.path.starts_with(&doc_dir)) {
+        return None;
+    }
+    Some(ChangelogInfo { debian, upstream, issues })
+}
+
+fn read_text(path: &Path) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open changelog: {}", e))?;
+    let mut content = Vec::new();
+    let result = if path.extension().is_some_and(|ext| ext == "gz") {
+        GzDecoder::new(file).take(MAX_CHANGELOG_SIZE).read_to_end(&mut content)
+    } else {
+        file.take(MAX_CHANGELOG_SIZE).read_to_end(&mut content)
+    };
+    result.map_err(|e| format!("Failed to read changelog: {}", e))?;
+    Ok(String::from_utf8_lossy(&content).into_owned())
+}
+
+/// Parse debian/changelog entries: header, change lines, ` -- maintainer  date` trailer
+pub fn parse_entries(text: &str) -> Vec<ChangelogEntry> {
+    let mut entries: Vec<ChangelogEntry> = Vec::new();
+    let mut current: Option<ChangelogEntry> = None;
+
+    for line in text.lines() {
+        if let Some(captures) = header_regex().captures(line) {
+            entries.extend(current.take());
+            current = Some(ChangelogEntry {
+                package: captures[1].to_string(),
+                version: captures[2].to_string(),
+                distributions: captures[3].split_whitespace().map(|d| d.to_string()).collect(),
+                urgency: urgency_regex()
+                    .captures(&captures[4])
+                    .map(|c| c[1].trim_end_matches(',').to_string()),
+                maintainer: None,
+                date: None,
+                changes: Vec::new(),
+            });
+            continue;
+        }
+        let Some(entry) = current.as_mut() else {
+            continue;
+        };
+        if let Some(captures) = trailer_regex().captures(line) {
+            entry.maintainer = Some(captures[1].trim().to_string()).filter(|m| !m.is_empty());
+            entry.date = captures.get(2).map(|m| m.as_str().trim().to_string());
+            entries.extend(current.take());
+            continue;
+        }
+        let change = line.trim();
+        if change.is_empty() {
+            continue;
+        }
+        // Bullets start a change, deeper indented lines continue the previous one
+        let starts_change = change.starts_with(['*', '-', '+', '[']) || line.len() - line.trim_start().len() <= 2;
+        match entry.changes.last_mut() {
+            Some(last) if !starts_change => {
+                last.push(' ');
+                last.push_str(change);
+            }
+            _ => entry.changes.push(change.to_string()),
+        }
+    }
+
+    entries.extend(current);
+    entries
+}
+
+fn header_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^(\S+) \(([^)\s]+)\) ([^;]*);(.*)$").unwrap())
+}
+
+fn urgency_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)urgency=(\S+)").unwrap())
+}
+
+fn trailer_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^ -- (.*?)(?:\s{2,}(\S.*))?$").unwrap())
+}
+
+fn check_entries(entries: &[ChangelogEntry], metadata: &DebianPackageMetadata, issues: &mut Vec<Issue>) {
+    let Some(top) = entries.first() else {
+        issues.push(Issue {
+            severity: IssueSeverity::Error,
+            message: "The Debian changelog has no parsable entries".to_string(),
+        });
+        return;
+    };
+    if top.version != metadata.version {
+        issues.push(Issue {
+            severity: IssueSeverity::Error,
+            message: format!(
+                "The newest changelog entry is version {}, the control file says {}",
+                top.version, metadata.version
+            ),
+        });
+    }
+    if top.distributions.iter().any(|d| d == "UNRELEASED") {
+        issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: format!("Version {} was built from an UNRELEASED changelog entry", top.version),
+        });
+    }
+    if top.maintainer.is_none() || top.date.is_none() {
+        issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: format!("The changelog entry for {} has no maintainer/date trailer", top.version),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANGELOG: &str = "\
+hello (2.10-3) unstable experimental; urgency=medium, binary-only=yes
+
+  * Fix the build with a newer compiler, which
+    needs an extra include.
+  * Update the watch file.
+    - Use the new download URL.
+
+ -- Jane Doe <jane@example.org>  Mon, 01 Jan 2024 12:00:00 +0000
+
+hello (2.10-2) unstable; urgency=low
+
+  * Initial upload.
+
+ -- 
+";
+
+    #[test]
+    fn parses_headers_and_trailers() {
+        let entries = parse_entries(CHANGELOG);
+        assert_eq!(entries.len(), 2);
+
+        let first = &entries[0];
+        assert_eq!((first.package.as_str(), first.version.as_str()), ("hello", "2.10-3"));
+        assert_eq!(first.distributions, vec!["unstable", "experimental"]);
+        assert_eq!(first.urgency.as_deref(), Some("medium"));
+        assert_eq!(first.maintainer.as_deref(), Some("Jane Doe <jane@example.org>"));
+        assert_eq!(first.date.as_deref(), Some("Mon, 01 Jan 2024 12:00:00 +0000"));
+
+        let second = &entries[1];
+        assert_eq!(second.urgency.as_deref(), Some("low"));
+        assert_eq!(second.maintainer, None);
+        assert_eq!(second.date, None);
+    }
+
+    #[test]
+    fn joins_continuation_lines() {
+        let entries = parse_entries(CHANGELOG);
+        assert_eq!(
+            entries[0].changes,
+            vec![
+                "* Fix the build with a newer compiler, which needs an extra include.",
+                "* Update the watch file.",
+                "- Use the new download URL.",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_an_entry_without_a_trailer() {
+        let entries = parse_entries("junk before the first header\nhello (1.0) unstable; urgency=high\n  * Change.\n");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].version, "1.0");
+        assert_eq!(entries[0].changes, vec!["* Change."]);
+    }
+}
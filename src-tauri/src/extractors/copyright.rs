@@ -0,0 +1,609 @@
+use crate::extractors::deb::{DebianPackageMetadata, FileInfo, FileType};
+use crate::utils::issue::{Issue, IssueSeverity};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+const DEP5_FORMAT: &str = "copyright-format/1.0";
+
+/// DEP-5 short names and their SPDX identifiers
+const SPDX_NAMES: &[(&str, &str)] = &[
+    ("GPL-1", "GPL-1.0-only"),
+    ("GPL-1+", "GPL-1.0-or-later"),
+    ("GPL-2", "GPL-2.0-only"),
+    ("GPL-2+", "GPL-2.0-or-later"),
+    ("GPL-3", "GPL-3.0-only"),
+    ("GPL-3+", "GPL-3.0-or-later"),
+    ("LGPL-2", "LGPL-2.0-only"),
+    ("LGPL-2+", "LGPL-2.0-or-later"),
+    ("LGPL-2.1", "LGPL-2.1-only"),
+    ("LGPL-2.1+", "LGPL-2.1-or-later"),
+    ("LGPL-3", "LGPL-3.0-only"),
+    ("LGPL-3+", "LGPL-3.0-or-later"),
+    ("AGPL-3", "AGPL-3.0-only"),
+    ("AGPL-3+", "AGPL-3.0-or-later"),
+    ("GFDL-1.2", "GFDL-1.2-only"),
+    ("GFDL-1.2+", "GFDL-1.2-or-later"),
+    ("GFDL-1.3", "GFDL-1.3-only"),
+    ("GFDL-1.3+", "GFDL-1.3-or-later"),
+    ("Apache-2.0", "Apache-2.0"),
+    ("Apache-2", "Apache-2.0"),
+    ("Artistic", "Artistic-1.0"),
+    ("Artistic-2.0", "Artistic-2.0"),
+    ("BSD-2-clause", "BSD-2-Clause"),
+    ("BSD-3-clause", "BSD-3-Clause"),
+    ("BSD-4-clause", "BSD-4-Clause"),
+    ("BSL-1.0", "BSL-1.0"),
+    ("CC0-1.0", "CC0-1.0"),
+    ("CC-BY-3.0", "CC-BY-3.0"),
+    ("CC-BY-4.0", "CC-BY-4.0"),
+    ("CC-BY-SA-3.0", "CC-BY-SA-3.0"),
+    ("CC-BY-SA-4.0", "CC-BY-SA-4.0"),
+    ("curl", "curl"),
+    ("EPL-1.0", "EPL-1.0"),
+    ("EPL-2.0", "EPL-2.0"),
+    ("Expat", "MIT"),
+    ("MIT", "MIT"),
+    ("ISC", "ISC"),
+    ("MPL-1.1", "MPL-1.1"),
+    ("MPL-2.0", "MPL-2.0"),
+    ("OFL-1.1", "OFL-1.1"),
+    ("SIL-OFL-1.1", "OFL-1.1"),
+    ("OpenSSL", "OpenSSL"),
+    ("PSF-2", "PSF-2.0"),
+    ("Python-2.0", "Python-2.0"),
+    ("Ruby", "Ruby"),
+    ("Unlicense", "Unlicense"),
+    ("WTFPL", "WTFPL"),
+    ("X11", "X11"),
+    ("Zlib", "Zlib"),
+    ("Zope-2.1", "ZPL-2.1"),
+];
+
+/// Exception names used after `with` and their SPDX identifiers
+const SPDX_EXCEPTIONS: &[(&str, &str)] = &[
+    ("Autoconf", "Autoconf-exception-3.0"),
+    ("Bison", "Bison-exception-2.2"),
+    ("Classpath", "Classpath-exception-2.0"),
+    ("Font", "Font-exception-2.0"),
+    ("GCC", "GCC-exception-3.1"),
+    ("LLVM", "LLVM-exception"),
+    ("Qt", "Qt-LGPL-exception-1.1"),
+];
+
+/// Licences shipped in /usr/share/common-licenses, which need no full text
+///
+/// BSD is left out: its text names the copyright holder, so Policy requires
+/// it in full even though common-licenses has a copy.
+const COMMON_LICENSES: &[&str] = &[
+    "Apache-2.0", "Artistic", "CC0-1.0", "GFDL-1.2", "GFDL-1.3", "GPL-1", "GPL-2", "GPL-3", "LGPL-2",
+    "LGPL-2.1", "LGPL-3", "MPL-1.1", "MPL-2.0",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyrightInfo {
+    pub path: String,
+    /// Whether the file follows the machine-readable DEP-5 format
+    pub machine_readable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub stanzas: Vec<FilesStanza>,
+    /// Stand-alone License stanzas
+    pub licenses: Vec<LicenseText>,
+    /// Installed path to the SPDX expression of the last stanza matching it
+    pub file_licenses: HashMap<String, String>,
+    /// Installed files no Files stanza matches
+    pub uncovered: Vec<String>,
+    /// Best-effort SPDX identifiers for files that are not machine-readable
+    pub detected_licenses: Vec<String>,
+    /// Full text of a file that is not machine-readable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilesStanza {
+    pub patterns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copyright: Option<String>,
+    /// Licence short name expression as written
+    pub license: String,
+    pub spdx: String,
+    /// Installed files this stanza is the last match for
+    pub matched: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseText {
+    pub name: String,
+    pub spdx: String,
+    pub has_text: bool,
+}
+
+/// Parse /usr/share/doc/<package>/copyright and map its Files stanzas onto the payload
+pub fn analyze_copyright(
+    files: &[FileInfo],
+    extract_path: &Path,
+    metadata: &DebianPackageMetadata,
+) -> Option<CopyrightInfo> {
+    let path = format!("usr/share/doc/{}/copyright", metadata.package);
+    // The doc directory may be a symlink to another package of the same source
+    let file = files.iter().find(|f| f.path == path && f.file_type == FileType::File)?;
+    let text = fs::read(extract_path.join(&file.path)).ok()?;
+    let text = String::from_utf8_lossy(&text).into_owned();
+
+    let mut info = CopyrightInfo {
+        path,
+        machine_readable: false,
+        upstream_name: None,
+        source: None,
+        stanzas: Vec::new(),
+        licenses: Vec::new(),
+        file_licenses: HashMap::new(),
+        uncovered: Vec::new(),
+        detected_licenses: Vec::new(),
+        text: None,
+        issues: Vec::new(),
+    };
+
+    let stanzas = parse_stanzas(&text);
+    let is_dep5 = stanzas
+        .first()
+        .and_then(|header| header.get("format"))
+        .is_some_and(|format| format.contains(DEP5_FORMAT));
+    if !is_dep5 {
+        info.detected_licenses = detect_licenses(&text);
+        info.text = Some(text);
+        return Some(info);
+    }
+
+    info.machine_readable = true;
+    let header = &stanzas[0];
+    info.upstream_name = header.get("upstream-name").cloned();
+    info.source = header.get("source").cloned();
+    let mut unmapped = BTreeSet::new();
+
+    for stanza in &stanzas[1..] {
+        let Some(license) = stanza.get("license") else {
+            continue;
+        };
+        let (name, body) = license.split_once('\n').unwrap_or((license, ""));
+        let name = name.trim().to_string();
+        let spdx = to_spdx(&name, &mut unmapped);
+        match stanza.get("files") {
+            Some(patterns) => info.stanzas.push(FilesStanza {
+                patterns: patterns.split_whitespace().map(|p| p.to_string()).collect(),
+                copyright: stanza.get("copyright").cloned(),
+                license: name,
+                spdx,
+                matched: 0,
+            }),
+            None => info.licenses.push(LicenseText {
+                name,
+                spdx,
+                has_text: !body.trim().is_empty(),
+            }),
+        }
+    }
+
+    map_files(&mut info, files, &metadata.package);
+    check_license_texts(&mut info, &stanzas[1..]);
+    for name in unmapped {
+        info.issues.push(Issue {
+            severity: IssueSeverity::Hint,
+            message: format!("Licence \"{}\" has no SPDX identifier, reported as LicenseRef", name),
+        });
+    }
+    if !info.uncovered.is_empty() {
+        info.issues.push(Issue {
+            severity: IssueSeverity::Warning,
+            message: format!("{} installed files are not covered by any Files stanza", info.uncovered.len()),
+        });
+    }
+
+    Some(info)
+}
+
+/// Split deb822 stanzas into lowercase field names and values, ` .` lines become blank
+fn parse_stanzas(text: &str) -> Vec<HashMap<String, String>> {
+    let mut stanzas = Vec::new();
+    let mut current: HashMap<String, String> = HashMap::new();
+    let mut last_field: Option<String> = None;
+
+    for line in text.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                stanzas.push(std::mem::take(&mut current));
+            }
+            last_field = None;
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some(value) = last_field.as_ref().and_then(|field| current.get_mut(field)) {
+                let line = line.trim();
+                value.push('\n');
+                value.push_str(if line == "." { "" } else { line });
+            }
+            continue;
+        }
+        if let Some((field, value)) = line.split_once(':') {
+            let field = field.trim().to_lowercase();
+            current.insert(field.clone(), value.trim().to_string());
+            last_field = Some(field);
+        }
+    }
+    if !current.is_empty() {
+        stanzas.push(current);
+    }
+
+    stanzas
+}
+
+/// Convert a DEP-5 licence expression to SPDX: `or`/`and`/`,` and `with X exception`
+pub fn to_spdx(expression: &str, unmapped: &mut BTreeSet<String>) -> String {
+    // In DEP-5 a comma binds looser than and/or
+    let groups: Vec<String> = expression
+        .split(',')
+        .map(|group| group.trim().trim_start_matches("and ").trim())
+        .filter(|group| !group.is_empty())
+        .map(|group| to_spdx_group(group, unmapped))
+        .collect();
+    if groups.len() == 1 {
+        return groups.into_iter().next().unwrap_or_default();
+    }
+    groups
+        .iter()
+        .map(|group| if group.contains(' ') { format!("({})", group) } else { group.clone() })
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+fn to_spdx_group(group: &str, unmapped: &mut BTreeSet<String>) -> String {
+    let words: Vec<&str> = group.split_whitespace().collect();
+    let mut parts = Vec::new();
+    let mut index = 0;
+
+    while index < words.len() {
+        let word = words[index];
+        match word.to_lowercase().as_str() {
+            "or" => parts.push("OR".to_string()),
+            "and" => parts.push("AND".to_string()),
+            _ => {
+                // `NAME with EXCEPTION exception`
+                let exception = (words.get(index + 1).is_some_and(|w| w.eq_ignore_ascii_case("with")))
+                    .then(|| words.get(index + 2))
+                    .flatten();
+                let mut id = license_id(word, unmapped);
+                if let Some(exception) = exception {
+                    let known = SPDX_EXCEPTIONS.iter().find(|(name, _)| name.eq_ignore_ascii_case(exception));
+                    id = match known {
+                        Some((_, spdx)) => format!("{} WITH {}", id, spdx),
+                        None => {
+                            let name = format!("{} with {} exception", word, exception);
+                            unmapped.insert(name.clone());
+                            license_ref(&name)
+                        }
+                    };
+                    index += 2;
+                    if words.get(index + 1).is_some_and(|w| w.eq_ignore_ascii_case("exception")) {
+                        index += 1;
+                    }
+                }
+                parts.push(id);
+            }
+        }
+        index += 1;
+    }
+
+    parts.join(" ")
+}
+
+/// Whether a declared licence is an SPDX expression of identifiers this module knows
+pub fn is_spdx_expression(expression: &str) -> bool {
+    let spaced = expression.replace(['(', ')'], " ");
+    let mut expect_id = true;
+    let mut after_with = false;
+    for token in spaced.split_whitespace() {
+        if expect_id {
+            let known = if after_with {
+                SPDX_EXCEPTIONS.iter().any(|(_, spdx)| *spdx == token)
+            } else {
+                SPDX_NAMES.iter().any(|(_, spdx)| *spdx == token)
+            };
+            if !known && !token.starts_with("LicenseRef-") {
+                return false;
+            }
+            expect_id = false;
+        } else {
+            match token {
+                "AND" | "OR" => after_with = false,
+                "WITH" => after_with = true,
+                _ => return false,
+            }
+            expect_id = true;
+        }
+    }
+    !expect_id
+}
+
+fn license_id(name: &str, unmapped: &mut BTreeSet<String>) -> String {
+    let known = SPDX_NAMES
+        .iter()
+        .find(|(short, spdx)| short.eq_ignore_ascii_case(name) || spdx.eq_ignore_ascii_case(name));
+    match known {
+        Some((_, spdx)) => spdx.to_string(),
+        None => {
+            unmapped.insert(name.to_string());
+            license_ref(name)
+        }
+    }
+}
+
+fn license_ref(name: &str) -> String {
+    let mut id = String::new();
+    for c in name.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '.' { c } else { '-' };
+        if c != '-' || !id.ends_with('-') {
+            id.push(c);
+        }
+    }
+    format!("LicenseRef-{}", id.trim_matches('-'))
+}
+
+/// DEP-5 glob: `*` matches anything including `/`, `?` one character, `\` escapes
+fn glob_regex(pattern: &str) -> Option<Regex> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.trim_start_matches("./").chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '\\' => regex.push_str(&regex::escape(&chars.next()?.to_string())),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).ok()
+}
+
+/// Match installed paths against the Files globs
+///
+/// Globs name paths in the source tree, which rarely equal installed paths.
+/// Only the full installed path and known source locations are tried, a
+/// suffix like `usr/lib/x` must not pick up an upstream `lib/*` stanza. Files
+/// nothing specific matches are left to the catch-all `*` stanza.
+fn map_files(info: &mut CopyrightInfo, files: &[FileInfo], package: &str) {
+    let globs: Vec<Vec<Regex>> = info
+        .stanzas
+        .iter()
+        .map(|stanza| stanza.patterns.iter().filter_map(|p| glob_regex(p)).collect())
+        .collect();
+    let doc_dir = format!("usr/share/doc/{}/", package);
+
+    for file in files.iter().filter(|f| f.file_type != FileType::Directory) {
+        let mut candidates = vec![file.path.as_str()];
+        candidates.extend(source_path(&file.path, &doc_dir));
+        // The last matching stanza wins
+        let stanza = globs
+            .iter()
+            .rposition(|regexes| regexes.iter().any(|r| candidates.iter().any(|c| r.is_match(c))));
+        match stanza {
+            Some(index) => {
+                info.stanzas[index].matched += 1;
+                info.file_licenses.insert(file.path.clone(), info.stanzas[index].spdx.clone());
+            }
+            None => info.uncovered.push(file.path.clone()),
+        }
+    }
+    info.uncovered.sort();
+}
+
+/// Source path of an installed file where the packaging tools fix it:
+/// dh_installdocs copies top-level documents into /usr/share/doc/<package>
+fn source_path<'a>(path: &'a str, doc_dir: &str) -> Option<&'a str> {
+    let name = path.strip_prefix(doc_dir)?;
+    // changelog.gz, copyright and NEWS.Debian come from debian/, not upstream
+    if name.contains('/') || name == "copyright" || name.contains(".Debian") {
+        return None;
+    }
+    Some(name.strip_suffix(".gz").unwrap_or(name))
+}
+
+/// Every licence needs its text in the file unless it is in /usr/share/common-licenses
+fn check_license_texts(info: &mut CopyrightInfo, stanzas: &[HashMap<String, String>]) {
+    let has_text = |name: &str| {
+        stanzas.iter().any(|stanza| {
+            stanza.get("license").is_some_and(|license| {
+                let (short, body) = license.split_once('\n').unwrap_or((license, ""));
+                short.trim() == name && !body.trim().is_empty()
+            })
+        })
+    };
+    let mut names = BTreeSet::new();
+    for stanza in &info.stanzas {
+        let mut words = stanza.license.split([',', ' ']).filter(|word| !word.is_empty());
+        while let Some(word) = words.next() {
+            match word.to_lowercase().as_str() {
+                "or" | "and" | "exception" => {}
+                // The exception name is part of the licence text
+                "with" => {
+                    words.next();
+                }
+                _ => {
+                    names.insert(word);
+                }
+            }
+        }
+    }
+
+    for name in names {
+        let base = name.trim_end_matches('+');
+        if !COMMON_LICENSES.contains(&base) && !has_text(name) {
+            info.issues.push(Issue {
+                severity: IssueSeverity::Warning,
+                message: if base.starts_with("BSD") {
+                    format!("Licence \"{}\" names the copyright holder, its full text must be included", name)
+                } else {
+                    format!("Licence \"{}\" has no text and is not in /usr/share/common-licenses", name)
+                },
+            });
+        }
+    }
+}
+
+/// Best-effort licence detection for free-form copyright files
+fn detect_licenses(text: &str) -> Vec<String> {
+    const MARKERS: &[(&str, &str)] = &[
+        ("Permission is hereby granted, free of charge", "MIT"),
+        ("Apache License, Version 2.0", "Apache-2.0"),
+        ("/usr/share/common-licenses/Apache-2.0", "Apache-2.0"),
+        ("Mozilla Public License, v. 2.0", "MPL-2.0"),
+        ("Mozilla Public License Version 2.0", "MPL-2.0"),
+        ("/usr/share/common-licenses/MPL-2.0", "MPL-2.0"),
+        ("/usr/share/common-licenses/Artistic", "Artistic-1.0"),
+        ("/usr/share/common-licenses/CC0-1.0", "CC0-1.0"),
+        ("with or without fee is hereby granted", "ISC"),
+        ("This is free and unencumbered software released into the public domain", "Unlicense"),
+        ("This software is provided 'as-is', without any express or implied", "Zlib"),
+    ];
+    static GPL_REGEX: OnceLock<Regex> = OnceLock::new();
+    static COMMON_GPL_REGEX: OnceLock<Regex> = OnceLock::new();
+    let gpl_regex = GPL_REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?is)GNU (Lesser |Library |Affero )?General Public\s+License.{0,200}?version (\d(?:\.\d)?)(.{0,120}?any later version)?",
+        )
+        .unwrap()
+    });
+    let common_gpl_regex = COMMON_GPL_REGEX
+        .get_or_init(|| Regex::new(r"/usr/share/common-licenses/((?:L|A)?GPL)-(\d(?:\.\d)?)").unwrap());
+    let mut detected = BTreeSet::new();
+
+    for (marker, spdx) in MARKERS {
+        if text.contains(marker) {
+            detected.insert(spdx.to_string());
+        }
+    }
+    if text.contains("Redistribution and use in source and binary forms") {
+        let bsd = if text.contains("advertising materials") {
+            "BSD-4-Clause"
+        } else if text.contains("Neither the name") || text.contains("may not be used to endorse") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        };
+        detected.insert(bsd.to_string());
+    }
+    for captures in gpl_regex.captures_iter(text) {
+        let family = match captures.get(1).map(|m| m.as_str().trim()) {
+            Some("Affero") => "AGPL",
+            Some(_) => "LGPL",
+            None => "GPL",
+        };
+        let suffix = if captures.get(3).is_some() { "or-later" } else { "only" };
+        detected.insert(format!("{}-{}-{}", family, spdx_version(&captures[2]), suffix));
+    }
+    // A bare pointer to common-licenses counts when the prose gave nothing
+    if !detected.iter().any(|id| id.contains("GPL")) {
+        for captures in common_gpl_regex.captures_iter(text) {
+            detected.insert(format!("{}-{}-only", &captures[1], spdx_version(&captures[2])));
+        }
+    }
+
+    detected.into_iter().collect()
+}
+
+fn spdx_version(version: &str) -> String {
+    if version.contains('.') {
+        version.to_string()
+    } else {
+        format!("{}.0", version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spdx(expression: &str) -> (String, Vec<String>) {
+        let mut unmapped = BTreeSet::new();
+        let id = to_spdx(expression, &mut unmapped);
+        (id, unmapped.into_iter().collect())
+    }
+
+    #[test]
+    fn converts_with_exception() {
+        assert_eq!(spdx("GPL-3+ with GCC exception").0, "GPL-3.0-or-later WITH GCC-exception-3.1");
+        assert_eq!(
+            spdx("GPL-2+ with Font exception or MIT").0,
+            "GPL-2.0-or-later WITH Font-exception-2.0 OR MIT"
+        );
+        assert_eq!(
+            spdx("GPL-2+ with OpenSSL exception"),
+            (
+                "LicenseRef-GPL-2-with-OpenSSL-exception".to_string(),
+                vec!["GPL-2+ with OpenSSL exception".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn comma_binds_looser_than_and_or() {
+        assert_eq!(
+            spdx("GPL-2+ or Artistic, and BSD-3-clause").0,
+            "(GPL-2.0-or-later OR Artistic-1.0) AND BSD-3-Clause"
+        );
+        assert_eq!(spdx("Expat, Zlib").0, "MIT AND Zlib");
+        assert_eq!(spdx("LGPL-2.1+").0, "LGPL-2.1-or-later");
+    }
+
+    #[test]
+    fn globs_match_dep5_wildcards() {
+        let star = glob_regex("src/*.c").unwrap();
+        assert!(star.is_match("src/a/b.c"));
+        assert!(!star.is_match("lib/b.c"));
+
+        let question = glob_regex("./doc/file?.txt").unwrap();
+        assert!(question.is_match("doc/file1.txt"));
+        assert!(!question.is_match("doc/file10.txt"));
+
+        let escaped = glob_regex(r"a\*b").unwrap();
+        assert!(escaped.is_match("a*b"));
+        assert!(!escaped.is_match("axb"));
+    }
+
+    #[test]
+    fn parses_stanzas_with_continuations() {
+        let stanzas = parse_stanzas(
+            "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n\
+             # a comment\n\
+             \n\
+             Files: *\n\
+             Copyright: 2020 Jane Doe\n\
+             \x20          2021 John Roe\n\
+             License: MIT\n\
+             \x20Permission is hereby granted.\n\
+             \x20.\n\
+             \x20THE SOFTWARE IS PROVIDED \"AS IS\".\n",
+        );
+        assert_eq!(stanzas.len(), 2);
+        assert!(stanzas[0]["format"].ends_with("copyright-format/1.0/"));
+        assert_eq!(stanzas[1]["files"], "*");
+        assert_eq!(stanzas[1]["copyright"], "2020 Jane Doe\n2021 John Roe");
+        assert_eq!(
+            stanzas[1]["license"],
+            "MIT\nPermission is hereby granted.\n\nTHE SOFTWARE IS PROVIDED \"AS IS\"."
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use crate::extractors::archive::is_ar_archive;
 use crate::utils::shell::exec_command;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::fs::{self, File};
+use std::io::Read;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -55,6 +57,36 @@ pub struct FileInfo {
     pub file_type: FileType,
     pub is_elf: bool,
     pub is_desktop: bool,
+    pub is_archive: bool,
+    /// Shannon entropy of the contents in bits per byte
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<f64>,
+    /// Hex SHA-1 of the contents, required for SPDX files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+    /// Hex SHA-256 of the contents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yara_matches: Option<Vec<YaraMatch>>,
+}
+
+/// A YARA rule that matched a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YaraMatch {
+    pub path: String,
+    pub rule: String,
+    pub tags: Vec<String>,
+    pub strings: Vec<YaraStringMatch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YaraStringMatch {
+    pub offset: u64,
+    pub identifier: String,
+    pub data: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -65,6 +97,7 @@ pub enum FileType {
     Symlink,
     Elf,
     Desktop,
+    Archive,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +115,8 @@ pub struct Scripts {
     pub config: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub templates: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triggers: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,6 +161,39 @@ impl DebExtractor {
         Ok(())
     }
 
+    /// Extract only the data files of a package (e.g. a -dbgsym .ddeb) to `dest`
+    pub fn extract_data(deb_path: &str, dest: &Path) -> Result<(), String> {
+        fs::create_dir_all(dest)
+            .map_err(|e| format!("Failed to create extract directory: {}", e))?;
+
+        let dest_str = dest.to_str().ok_or("Invalid extract path")?;
+        exec_command("dpkg", &["-x", deb_path, dest_str], 60000)
+            .map_err(|e| format!("Failed to extract package data: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Look for the matching `<package>-dbgsym` package next to `deb_path`
+    pub fn find_debug_package(deb_path: &Path, metadata: &DebianPackageMetadata) -> Option<PathBuf> {
+        let dir = deb_path.parent()?;
+        // Epochs are not part of .deb file names
+        let version = metadata
+            .version
+            .split_once(':')
+            .map(|(_, v)| v)
+            .unwrap_or(&metadata.version);
+
+        ["ddeb", "deb"]
+            .iter()
+            .map(|ext| {
+                dir.join(format!(
+                    "{}-dbgsym_{}_{}.{}",
+                    metadata.package, version, metadata.architecture, ext
+                ))
+            })
+            .find(|candidate| candidate.is_file())
+    }
+
     /// Parse control file to get metadata
     pub fn parse_metadata(control_path: &Path) -> Result<DebianPackageMetadata, String> {
         let control_file = control_path.join("control");
@@ -237,19 +305,24 @@ impl DebExtractor {
             let metadata = entry.metadata()
                 .map_err(|e| format!("Failed to get metadata: {}", e))?;
 
-            let (ftype, is_elf, is_desktop) = if file_type.is_dir() {
-                (FileType::Directory, false, false)
+            let (ftype, is_elf, is_desktop, is_archive) = if file_type.is_dir() {
+                (FileType::Directory, false, false, false)
             } else if file_type.is_file() {
                 let path_str = relative_path.to_string_lossy().to_string();
 
-                // Fast check: only check ELF for files in common binary locations
-                // This avoids opening every file just to check its magic number
-                let is_elf = Self::is_likely_elf_file(&path_str);
+                // Static libraries are ar archives of ELF objects, confirm by magic
+                let is_archive = path_str.ends_with(".a") && is_ar_archive(path);
+
+                // Fast check: only check ELF for files in common binary locations,
+                // then confirm by magic so scripts in bin directories are not ELF
+                let is_elf = !is_archive && Self::is_likely_elf_file(&path_str) && has_elf_magic(path);
 
                 // Check if .desktop file
                 let is_desktop = path_str.ends_with(".desktop");
 
-                let ftype = if is_elf {
+                let ftype = if is_archive {
+                    FileType::Archive
+                } else if is_elf {
                     FileType::Elf
                 } else if is_desktop {
                     FileType::Desktop
@@ -257,9 +330,9 @@ impl DebExtractor {
                     FileType::File
                 };
 
-                (ftype, is_elf, is_desktop)
+                (ftype, is_elf, is_desktop, is_archive)
             } else {
-                (FileType::File, false, false)
+                (FileType::File, false, false, false)
             };
 
             let mode = format!("{:04o}", metadata.permissions().mode() & 0o7777);
@@ -278,6 +351,11 @@ impl DebExtractor {
                 file_type: ftype,
                 is_elf,
                 is_desktop,
+                is_archive,
+                entropy: None,
+                sha1: None,
+                sha256: None,
+                yara_matches: None,
             });
         }
 
@@ -302,9 +380,11 @@ impl DebExtractor {
                 // Additional check: should not have a known extension
                 // that indicates it's not an ELF file
                 let lower = path.to_lowercase();
-                if lower.ends_with(".so") || lower.ends_with(".a") ||
-                   lower.ends_with(".la") || lower.ends_with(".o") {
-                    return true;  // Shared/static libraries are also ELF
+                if lower.ends_with(".so") || lower.ends_with(".la") || lower.ends_with(".o") {
+                    return true;  // Shared libraries and objects are also ELF
+                }
+                if lower.ends_with(".a") {
+                    return false;  // Static libraries are ar archives
                 }
                 if lower.ends_with(".png") || lower.ends_with(".jpg") ||
                    lower.ends_with(".jpeg") || lower.ends_with(".gif") ||
@@ -346,7 +426,7 @@ impl DebExtractor {
             extract_path.join("../control")
         };
 
-        let script_names = ["preinst", "postinst", "prerm", "postrm", "config", "templates"];
+        let script_names = ["preinst", "postinst", "prerm", "postrm", "config", "templates", "triggers"];
         let mut scripts = Scripts {
             preinst: None,
             postinst: None,
@@ -354,6 +434,7 @@ impl DebExtractor {
             postrm: None,
             config: None,
             templates: None,
+            triggers: None,
         };
 
         for script_name in script_names {
@@ -366,6 +447,7 @@ impl DebExtractor {
                     "postrm" => scripts.postrm = Some(content),
                     "config" => scripts.config = Some(content),
                     "templates" => scripts.templates = Some(content),
+                    "triggers" => scripts.triggers = Some(content),
                     _ => {}
                 }
             }
@@ -393,3 +475,25 @@ impl DebExtractor {
         })
     }
 }
+
+fn has_elf_magic(file_path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    File::open(file_path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .map(|_| &magic == b"\x7fELF")
+        .unwrap_or(false)
+}
+
+/// Whether a path is a regular file, without following symlinks
+///
+/// `list_files` reports FIFOs and device nodes as plain files, and opening
+/// a FIFO blocks forever, so check this before reading file contents.
+pub fn is_regular_file(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_file())
+}
+
+/// Upstream part of a Debian version: no epoch, no Debian revision
+pub fn upstream_version(version: &str) -> &str {
+    let version = version.split_once(':').map_or(version, |(_, rest)| rest);
+    version.rsplit_once('-').map_or(version, |(upstream, _)| upstream)
+}
@@ -0,0 +1,813 @@
+use crate::utils::issue::IssueSeverity;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+const MAIN_GROUP: &str = "Desktop Entry";
+const ACTION_GROUP_PREFIX: &str = "Desktop Action ";
+
+/// Registered main categories, at least one should be present
+const MAIN_CATEGORIES: &[&str] = &[
+    "AudioVideo", "Audio", "Video", "Development", "Education", "Game", "Graphics", "Network",
+    "Office", "Science", "Settings", "System", "Utility",
+];
+
+/// Registered additional categories
+const ADDITIONAL_CATEGORIES: &[&str] = &[
+    "Building", "Debugger", "IDE", "GUIDesigner", "Profiling", "RevisionControl", "Translation",
+    "Calendar", "ContactManagement", "Database", "Dictionary", "Chart", "Email", "Finance",
+    "FlowChart", "PDA", "ProjectManagement", "Presentation", "Spreadsheet", "WordProcessor",
+    "2DGraphics", "VectorGraphics", "RasterGraphics", "3DGraphics", "Scanning", "OCR",
+    "Photography", "Publishing", "Viewer", "TextTools", "DesktopSettings", "HardwareSettings",
+    "Printing", "PackageManager", "Dialup", "InstantMessaging", "Chat", "IRCClient", "Feed",
+    "FileTransfer", "HamRadio", "News", "P2P", "RemoteAccess", "Telephony", "TelephonyTools",
+    "VideoConference", "WebBrowser", "WebDevelopment", "Midi", "Mixer", "Sequencer", "Tuner", "TV",
+    "AudioVideoEditing", "Player", "Recorder", "DiscBurning", "ActionGame", "AdventureGame",
+    "ArcadeGame", "BoardGame", "BlocksGame", "CardGame", "KidsGame", "LogicGame", "RolePlaying",
+    "Shooter", "Simulation", "SportsGame", "StrategyGame", "Art", "Construction", "Music",
+    "Languages", "ArtificialIntelligence", "Astronomy", "Biology", "Chemistry", "ComputerScience",
+    "DataVisualization", "Economy", "Electricity", "Geography", "Geology", "Geoscience", "History",
+    "Humanities", "ImageProcessing", "Literature", "Maps", "Math", "NumericalAnalysis",
+    "MedicalSoftware", "Physics", "Robotics", "Spirituality", "Sports", "ParallelComputing",
+    "Amusement", "Archiving", "Compression", "Electronics", "Emulator", "Engineering",
+    "FileTools", "FileManager", "TerminalEmulator", "Filesystem", "Monitor", "Security",
+    "Accessibility", "Calculator", "Clock", "TextEditor", "Documentation", "Adult", "Core", "KDE",
+    "GNOME", "XFCE", "DDE", "GTK", "Qt", "Motif", "Java", "ConsoleOnly",
+];
+
+/// Reserved categories, only valid together with OnlyShowIn
+const RESERVED_CATEGORIES: &[&str] = &["Screensaver", "TrayIcon", "Applet", "Shell"];
+
+/// Registered desktop environments for OnlyShowIn/NotShowIn
+const ENVIRONMENTS: &[&str] = &[
+    "GNOME", "GNOME-Classic", "GNOME-Flashback", "KDE", "LXDE", "LXQt", "MATE", "Razor", "ROX",
+    "TDE", "Unity", "XFCE", "EDE", "Cinnamon", "Pantheon", "Budgie", "Enlightenment", "DDE",
+    "Endless", "Old",
+];
+
+/// Keys from older spec versions
+const DEPRECATED_KEYS: &[&str] = &[
+    "Encoding", "MiniIcon", "TerminalOptions", "Protocols", "Extensions", "BinaryPattern",
+    "MapNotify", "SwallowTitle", "SwallowExec", "SortOrder", "FilePattern",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    String,
+    LocaleString,
+    Boolean,
+    Strings,
+    LocaleStrings,
+}
+
+/// Standard keys of the `[Desktop Entry]` group
+const ENTRY_KEYS: &[(&str, ValueKind)] = &[
+    ("Type", ValueKind::String),
+    ("Version", ValueKind::String),
+    ("Name", ValueKind::LocaleString),
+    ("GenericName", ValueKind::LocaleString),
+    ("NoDisplay", ValueKind::Boolean),
+    ("Comment", ValueKind::LocaleString),
+    ("Icon", ValueKind::LocaleString),
+    ("Hidden", ValueKind::Boolean),
+    ("OnlyShowIn", ValueKind::Strings),
+    ("NotShowIn", ValueKind::Strings),
+    ("DBusActivatable", ValueKind::Boolean),
+    ("TryExec", ValueKind::String),
+    ("Exec", ValueKind::String),
+    ("Path", ValueKind::String),
+    ("Terminal", ValueKind::Boolean),
+    ("Actions", ValueKind::Strings),
+    ("MimeType", ValueKind::Strings),
+    ("Categories", ValueKind::Strings),
+    ("Implements", ValueKind::Strings),
+    ("Keywords", ValueKind::LocaleStrings),
+    ("StartupNotify", ValueKind::Boolean),
+    ("StartupWMClass", ValueKind::String),
+    ("URL", ValueKind::String),
+    ("PrefersNonDefaultGPU", ValueKind::Boolean),
+    ("SingleMainWindow", ValueKind::Boolean),
+];
+
+/// Keys allowed in `[Desktop Action <id>]` groups
+const ACTION_KEYS: &[(&str, ValueKind)] = &[
+    ("Name", ValueKind::LocaleString),
+    ("Icon", ValueKind::LocaleString),
+    ("Exec", ValueKind::String),
+];
+
+/// A parsed `.desktop` file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopEntry {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub entry_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<LocalizedString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generic_name: Option<LocalizedString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<LocalizedString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<LocalizedString>,
+    pub no_display: bool,
+    pub hidden: bool,
+    pub terminal: bool,
+    pub dbus_activatable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub startup_notify: Option<bool>,
+    pub prefers_non_default_gpu: bool,
+    pub single_main_window: bool,
+    pub only_show_in: Vec<String>,
+    pub not_show_in: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub try_exec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec: Option<String>,
+    /// Working directory for the program
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(rename = "startupWMClass", skip_serializing_if = "Option::is_none")]
+    pub startup_wm_class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub actions: Vec<DesktopAction>,
+    pub mime_types: Vec<String>,
+    pub categories: Vec<String>,
+    pub implements: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<LocalizedList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_resolution: Option<IconResolution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec_resolution: Option<ExecResolution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub try_exec_resolution: Option<ExecResolution>,
+    /// Every group in file order, including vendor extensions
+    pub groups: Vec<DesktopGroup>,
+    pub issues: Vec<DesktopIssue>,
+}
+
+/// Where the Icon key resolves to in the package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IconResolution {
+    pub name: String,
+    /// Best match, relative to the package root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Every matching file in lookup order
+    pub candidates: Vec<String>,
+    /// `data:` URL of the best match for preview
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_url: Option<String>,
+}
+
+/// Where an Exec or TryExec program resolves to in the package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecResolution {
+    pub program: String,
+    /// Where the program was found in the package
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub found: bool,
+    /// Essential package or dependency that ships the program when the package does not
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provided_by: Option<String>,
+}
+
+/// A locale string value with its `Key[locale]` variants
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedString {
+    pub value: String,
+    pub localized: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedList {
+    pub value: Vec<String>,
+    pub localized: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopAction {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<LocalizedString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<LocalizedString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopGroup {
+    pub name: String,
+    pub line: usize,
+    pub entries: Vec<DesktopKeyValue>,
+}
+
+/// A raw `Key[locale]=value` line, value still escaped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopKeyValue {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    pub value: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopIssue {
+    pub severity: IssueSeverity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl DesktopGroup {
+    fn get(&self, key: &str) -> Option<&DesktopKeyValue> {
+        self.entries.iter().find(|e| e.key == key && e.locale.is_none())
+    }
+
+    fn string(&self, key: &str) -> Option<String> {
+        self.get(key).map(|e| unescape(&e.value))
+    }
+
+    fn boolean(&self, key: &str) -> Option<bool> {
+        self.get(key).map(|e| matches!(e.value.as_str(), "true" | "1"))
+    }
+
+    fn list(&self, key: &str) -> Vec<String> {
+        self.get(key).map(|e| split_list(&e.value)).unwrap_or_default()
+    }
+
+    fn localized(&self, key: &str) -> Option<LocalizedString> {
+        let value = self.string(key)?;
+        let localized = self
+            .entries
+            .iter()
+            .filter(|e| e.key == key)
+            .filter_map(|e| Some((e.locale.clone()?, unescape(&e.value))))
+            .collect();
+        Some(LocalizedString { value, localized })
+    }
+
+    fn localized_list(&self, key: &str) -> Option<LocalizedList> {
+        let value = split_list(&self.get(key)?.value);
+        let localized = self
+            .entries
+            .iter()
+            .filter(|e| e.key == key)
+            .filter_map(|e| Some((e.locale.clone()?, split_list(&e.value))))
+            .collect();
+        Some(LocalizedList { value, localized })
+    }
+}
+
+/// Parse and validate a desktop entry file
+pub fn analyze_desktop(file_path: &str) -> Result<DesktopEntry, String> {
+    let bytes = fs::read(file_path).map_err(|e| format!("Failed to read desktop file: {}", e))?;
+    Ok(parse_desktop_entry(&bytes))
+}
+
+pub fn parse_desktop_entry(bytes: &[u8]) -> DesktopEntry {
+    let mut issues = Vec::new();
+    let content = match std::str::from_utf8(bytes) {
+        Ok(content) => content.to_string(),
+        Err(_) => {
+            issue(&mut issues, IssueSeverity::Error, None, "File is not valid UTF-8".to_string());
+            String::from_utf8_lossy(bytes).to_string()
+        }
+    };
+
+    let groups = parse_groups(&content, &mut issues);
+    let mut entry = DesktopEntry::default();
+
+    match groups.first() {
+        Some(first) if first.name == MAIN_GROUP => {}
+        Some(first) => issue(
+            &mut issues,
+            IssueSeverity::Error,
+            Some(first.line),
+            format!("First group must be \"{}\", found \"{}\"", MAIN_GROUP, first.name),
+        ),
+        None => issue(&mut issues, IssueSeverity::Error, None, format!("Missing \"{}\" group", MAIN_GROUP)),
+    }
+
+    if let Some(main) = groups.iter().find(|g| g.name == MAIN_GROUP) {
+        fill_entry(&mut entry, main, &groups);
+        validate_group(main, ENTRY_KEYS, true, &mut issues);
+        validate_entry(&entry, main, &groups, &mut issues);
+    }
+
+    for group in groups.iter().filter(|g| g.name.starts_with(ACTION_GROUP_PREFIX)) {
+        validate_group(group, ACTION_KEYS, false, &mut issues);
+    }
+
+    entry.groups = groups;
+    entry.issues = issues;
+    entry
+}
+
+fn issue(issues: &mut Vec<DesktopIssue>, severity: IssueSeverity, line: Option<usize>, message: String) {
+    issues.push(DesktopIssue { severity, line, message });
+}
+
+fn parse_groups(content: &str, issues: &mut Vec<DesktopIssue>) -> Vec<DesktopGroup> {
+    let mut groups: Vec<DesktopGroup> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (index, raw) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw.trim_end_matches('\r');
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|l| l.trim_end().strip_suffix(']')) {
+            if name.contains(['[', ']']) || name.chars().any(|c| c.is_control()) {
+                issue(issues, IssueSeverity::Error, Some(line_no), format!("Invalid group name \"{}\"", name));
+            }
+            if !seen.insert(name.to_string()) {
+                issue(issues, IssueSeverity::Error, Some(line_no), format!("Duplicate group \"{}\"", name));
+            }
+            groups.push(DesktopGroup {
+                name: name.to_string(),
+                line: line_no,
+                entries: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some((key_part, value)) = trimmed.split_once('=') else {
+            issue(
+                issues,
+                IssueSeverity::Error,
+                Some(line_no),
+                "Line is not a group header, comment or key-value pair".to_string(),
+            );
+            continue;
+        };
+        let Some(group) = groups.last_mut() else {
+            issue(issues, IssueSeverity::Error, Some(line_no), "Key-value pair before the first group".to_string());
+            continue;
+        };
+
+        let key_part = key_part.trim_end();
+        let (key, locale) = match key_part.split_once('[') {
+            Some((key, rest)) => (key, rest.strip_suffix(']').map(|l| l.to_string())),
+            None => (key_part, None),
+        };
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            issue(issues, IssueSeverity::Error, Some(line_no), format!("Invalid key name \"{}\"", key_part));
+            continue;
+        }
+        if key_part.contains('[') && locale.is_none() {
+            issue(issues, IssueSeverity::Error, Some(line_no), format!("Malformed locale in key \"{}\"", key_part));
+            continue;
+        }
+        if group.entries.iter().any(|e| e.key == key && e.locale == locale) {
+            issue(
+                issues,
+                IssueSeverity::Error,
+                Some(line_no),
+                format!("Duplicate key \"{}\" in group \"{}\"", key_part, group.name),
+            );
+        }
+
+        group.entries.push(DesktopKeyValue {
+            key: key.to_string(),
+            locale,
+            value: value.trim_start().to_string(),
+            line: line_no,
+        });
+    }
+
+    groups
+}
+
+fn fill_entry(entry: &mut DesktopEntry, main: &DesktopGroup, groups: &[DesktopGroup]) {
+    entry.entry_type = main.string("Type");
+    entry.version = main.string("Version");
+    entry.name = main.localized("Name");
+    entry.generic_name = main.localized("GenericName");
+    entry.comment = main.localized("Comment");
+    entry.icon = main.localized("Icon");
+    entry.no_display = main.boolean("NoDisplay").unwrap_or(false);
+    entry.hidden = main.boolean("Hidden").unwrap_or(false);
+    entry.terminal = main.boolean("Terminal").unwrap_or(false);
+    entry.dbus_activatable = main.boolean("DBusActivatable").unwrap_or(false);
+    entry.startup_notify = main.boolean("StartupNotify");
+    entry.prefers_non_default_gpu = main.boolean("PrefersNonDefaultGPU").unwrap_or(false);
+    entry.single_main_window = main.boolean("SingleMainWindow").unwrap_or(false);
+    entry.only_show_in = main.list("OnlyShowIn");
+    entry.not_show_in = main.list("NotShowIn");
+    entry.try_exec = main.string("TryExec");
+    entry.exec = main.string("Exec");
+    entry.path = main.string("Path");
+    entry.startup_wm_class = main.string("StartupWMClass");
+    entry.url = main.string("URL");
+    entry.mime_types = main.list("MimeType");
+    entry.categories = main.list("Categories");
+    entry.implements = main.list("Implements");
+    entry.keywords = main.localized_list("Keywords");
+    entry.actions = main
+        .list("Actions")
+        .into_iter()
+        .map(|id| {
+            let group_name = format!("{}{}", ACTION_GROUP_PREFIX, id);
+            let group = groups.iter().find(|g| g.name == group_name);
+            DesktopAction {
+                name: group.and_then(|g| g.localized("Name")),
+                icon: group.and_then(|g| g.localized("Icon")),
+                exec: group.and_then(|g| g.string("Exec")),
+                id,
+            }
+        })
+        .collect();
+}
+
+/// Per-key checks: unknown, deprecated and misused locale keys, boolean syntax, list terminators
+fn validate_group(group: &DesktopGroup, spec: &[(&str, ValueKind)], is_main: bool, issues: &mut Vec<DesktopIssue>) {
+    for kv in &group.entries {
+        let line = Some(kv.line);
+        if kv.key.starts_with("X-") {
+            continue;
+        }
+        if is_main && DEPRECATED_KEYS.contains(&kv.key.as_str()) {
+            issue(issues, IssueSeverity::Warning, line, format!("Key \"{}\" is deprecated", kv.key));
+            continue;
+        }
+        let Some(&(_, kind)) = spec.iter().find(|(name, _)| *name == kv.key) else {
+            issue(
+                issues,
+                IssueSeverity::Error,
+                line,
+                format!(
+                    "Unknown key \"{}\" in group \"{}\", extensions must start with \"X-\"",
+                    kv.key, group.name
+                ),
+            );
+            continue;
+        };
+
+        if kv.locale.is_some() && !matches!(kind, ValueKind::LocaleString | ValueKind::LocaleStrings) {
+            issue(issues, IssueSeverity::Error, line, format!("Key \"{}\" cannot be localized", kv.key));
+        }
+        match kind {
+            ValueKind::Boolean => match kv.value.as_str() {
+                "true" | "false" => {}
+                "0" | "1" => issue(
+                    issues,
+                    IssueSeverity::Warning,
+                    line,
+                    format!("Boolean key \"{}\" uses deprecated value \"{}\"", kv.key, kv.value),
+                ),
+                _ => issue(
+                    issues,
+                    IssueSeverity::Error,
+                    line,
+                    format!("Boolean key \"{}\" has invalid value \"{}\"", kv.key, kv.value),
+                ),
+            },
+            ValueKind::Strings | ValueKind::LocaleStrings => {
+                if !kv.value.is_empty() && !kv.value.ends_with(';') {
+                    issue(
+                        issues,
+                        IssueSeverity::Warning,
+                        line,
+                        format!("List key \"{}\" does not end with a semicolon", kv.key),
+                    );
+                }
+            }
+            ValueKind::String | ValueKind::LocaleString => {}
+        }
+    }
+
+    if !is_main && group.get("Name").is_none() {
+        issue(
+            issues,
+            IssueSeverity::Error,
+            Some(group.line),
+            format!("Group \"{}\" is missing required key \"Name\"", group.name),
+        );
+    }
+    if !is_main {
+        if let Some(exec) = group.get("Exec") {
+            validate_exec(&unescape(&exec.value), exec.line, issues);
+        }
+    }
+}
+
+/// Whole-entry checks: required keys, Categories, environments, actions and Exec
+fn validate_entry(entry: &DesktopEntry, main: &DesktopGroup, groups: &[DesktopGroup], issues: &mut Vec<DesktopIssue>) {
+    let line_of = |key: &str| main.get(key).map(|kv| kv.line);
+
+    match entry.entry_type.as_deref() {
+        None => issue(issues, IssueSeverity::Error, Some(main.line), "Missing required key \"Type\"".to_string()),
+        Some("Application") => {
+            if entry.exec.is_none() && !entry.dbus_activatable {
+                issue(
+                    issues,
+                    IssueSeverity::Error,
+                    Some(main.line),
+                    "Application entry is missing \"Exec\" and is not DBusActivatable".to_string(),
+                );
+            }
+        }
+        Some("Link") => {
+            if entry.url.is_none() {
+                issue(issues, IssueSeverity::Error, Some(main.line), "Link entry is missing \"URL\"".to_string());
+            }
+        }
+        Some("Directory") => {}
+        Some("MimeType") | Some("Service") | Some("ServiceType") | Some("FSDevice") => issue(
+            issues,
+            IssueSeverity::Warning,
+            line_of("Type"),
+            format!("Type \"{}\" is deprecated", entry.entry_type.as_deref().unwrap_or_default()),
+        ),
+        Some(other) => issue(issues, IssueSeverity::Error, line_of("Type"), format!("Unknown Type \"{}\"", other)),
+    }
+    if entry.name.is_none() {
+        issue(issues, IssueSeverity::Error, Some(main.line), "Missing required key \"Name\"".to_string());
+    }
+    if let Some(version) = &entry.version {
+        if !matches!(version.as_str(), "1.0" | "1.1" | "1.2" | "1.3" | "1.4" | "1.5") {
+            issue(issues, IssueSeverity::Warning, line_of("Version"), format!("Unknown spec version \"{}\"", version));
+        }
+    }
+
+    if let Some(exec) = &entry.exec {
+        validate_exec(exec, line_of("Exec").unwrap_or(main.line), issues);
+    }
+
+    if entry.entry_type.as_deref() == Some("Application") && !entry.categories.is_empty() {
+        for category in &entry.categories {
+            if category.starts_with("X-") {
+                continue;
+            }
+            if RESERVED_CATEGORIES.contains(&category.as_str()) {
+                if entry.only_show_in.is_empty() {
+                    issue(
+                        issues,
+                        IssueSeverity::Error,
+                        line_of("Categories"),
+                        format!("Reserved category \"{}\" requires OnlyShowIn", category),
+                    );
+                }
+            } else if !MAIN_CATEGORIES.contains(&category.as_str())
+                && !ADDITIONAL_CATEGORIES.contains(&category.as_str())
+            {
+                issue(
+                    issues,
+                    IssueSeverity::Error,
+                    line_of("Categories"),
+                    format!("Unregistered category \"{}\"", category),
+                );
+            }
+        }
+        if !entry.categories.iter().any(|c| MAIN_CATEGORIES.contains(&c.as_str())) {
+            issue(
+                issues,
+                IssueSeverity::Hint,
+                line_of("Categories"),
+                "Categories contain no main category".to_string(),
+            );
+        }
+    }
+
+    if !entry.only_show_in.is_empty() && !entry.not_show_in.is_empty() {
+        issue(
+            issues,
+            IssueSeverity::Error,
+            line_of("NotShowIn"),
+            "Both OnlyShowIn and NotShowIn are set".to_string(),
+        );
+    }
+    for env in entry.only_show_in.iter().chain(&entry.not_show_in) {
+        if !env.starts_with("X-") && !ENVIRONMENTS.contains(&env.as_str()) {
+            issue(
+                issues,
+                IssueSeverity::Error,
+                line_of("OnlyShowIn").or(line_of("NotShowIn")),
+                format!("Unregistered desktop environment \"{}\"", env),
+            );
+        }
+    }
+
+    for action in &entry.actions {
+        let group_name = format!("{}{}", ACTION_GROUP_PREFIX, action.id);
+        if !groups.iter().any(|g| g.name == group_name) {
+            issue(
+                issues,
+                IssueSeverity::Error,
+                line_of("Actions"),
+                format!("Action \"{}\" has no [{}] group", action.id, group_name),
+            );
+        }
+    }
+    for group in groups.iter().filter(|g| g.name.starts_with(ACTION_GROUP_PREFIX)) {
+        let id = &group.name[ACTION_GROUP_PREFIX.len()..];
+        if !entry.actions.iter().any(|a| a.id == id) {
+            issue(
+                issues,
+                IssueSeverity::Warning,
+                Some(group.line),
+                format!("Group \"{}\" is not listed in Actions", group.name),
+            );
+        }
+    }
+}
+
+/// Check Exec quoting and field codes
+fn validate_exec(exec: &str, line: usize, issues: &mut Vec<DesktopIssue>) {
+    let Some(args) = split_exec(exec) else {
+        issue(issues, IssueSeverity::Error, Some(line), "Exec has an unterminated quoted argument".to_string());
+        return;
+    };
+    if args.is_empty() {
+        issue(issues, IssueSeverity::Error, Some(line), "Exec is empty".to_string());
+        return;
+    }
+
+    let mut file_codes = 0;
+    for arg in &args {
+        if arg.quoted {
+            if has_field_code(&arg.text) {
+                issue(
+                    issues,
+                    IssueSeverity::Error,
+                    Some(line),
+                    format!("Field code inside quoted argument \"{}\"", arg.text),
+                );
+            }
+            continue;
+        }
+        let mut chars = arg.text.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                continue;
+            }
+            match chars.next() {
+                Some('%') => {}
+                Some(code @ ('f' | 'u' | 'F' | 'U')) => {
+                    file_codes += 1;
+                    if code.is_ascii_uppercase() && arg.text.len() != 2 {
+                        issue(
+                            issues,
+                            IssueSeverity::Error,
+                            Some(line),
+                            format!("Field code %{} must be a standalone argument", code),
+                        );
+                    }
+                }
+                Some('i' | 'c' | 'k') => {}
+                Some(code @ ('d' | 'D' | 'n' | 'N' | 'v' | 'm')) => issue(
+                    issues,
+                    IssueSeverity::Warning,
+                    Some(line),
+                    format!("Field code %{} is deprecated", code),
+                ),
+                Some(code) => issue(
+                    issues,
+                    IssueSeverity::Error,
+                    Some(line),
+                    format!("Invalid field code %{}", code),
+                ),
+                None => issue(issues, IssueSeverity::Error, Some(line), "Exec ends with a bare %".to_string()),
+            }
+        }
+    }
+    if file_codes > 1 {
+        issue(
+            issues,
+            IssueSeverity::Error,
+            Some(line),
+            "Exec contains more than one of %f, %F, %u, %U".to_string(),
+        );
+    }
+}
+
+fn has_field_code(text: &str) -> bool {
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            match chars.next() {
+                Some('%') | None => {}
+                Some(_) => return true,
+            }
+        }
+    }
+    false
+}
+
+/// One Exec argument after quote removal
+pub struct ExecArg {
+    pub text: String,
+    pub quoted: bool,
+}
+
+/// Split an (already unescaped) Exec value into arguments, None on an open quote
+pub fn split_exec(exec: &str) -> Option<Vec<ExecArg>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut started = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+                started = true;
+            }
+            '\\' if in_quotes => current.push(chars.next()?),
+            c if c.is_whitespace() && !in_quotes => {
+                if started {
+                    args.push(ExecArg {
+                        text: std::mem::take(&mut current),
+                        quoted,
+                    });
+                    quoted = false;
+                    started = false;
+                }
+            }
+            c => {
+                current.push(c);
+                started = true;
+            }
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    if started {
+        args.push(ExecArg { text: current, quoted });
+    }
+    Some(args)
+}
+
+/// Undo the `\s \n \t \r \\` escapes of string values
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Split a `;`-separated list, honouring `\;`
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(';') => current.push(';'),
+                Some(other) => {
+                    current.push('\\');
+                    current.push(other);
+                }
+                None => current.push('\\'),
+            },
+            ';' => items.push(unescape(&std::mem::take(&mut current))),
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        items.push(unescape(&current));
+    }
+    items.retain(|item| !item.is_empty());
+    items
+}
@@ -0,0 +1,233 @@
+use crate::extractors::elf::is_build_tree_path;
+use gimli::{AttributeValue, DwLang, Dwarf, DwarfSections, EndianSlice, RunTimeEndian, Unit};
+use object::read::{ReadCache, ReadRef};
+use object::{Object, ObjectSection};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::fs::File;
+
+type Reader<'a> = EndianSlice<'a, RunTimeEndian>;
+
+/// Cap on compile units returned per binary
+const MAX_COMPILE_UNITS: usize = 500;
+/// Cap on line table source files returned per binary
+const MAX_SOURCE_FILES: usize = 2000;
+/// Debug sections larger than this (after decompression) are skipped
+const MAX_SECTION_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Summary of the DWARF compile units in an unstripped binary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DwarfSummary {
+    pub compile_unit_count: usize,
+    pub compile_units: Vec<CompileUnit>,
+    /// Distinct producers, split into compiler and recorded switches
+    pub producers: Vec<DwarfProducer>,
+    pub languages: Vec<String>,
+    /// Distinct source files from the `.debug_line` file tables, headers included
+    pub source_file_count: usize,
+    pub source_files: Vec<String>,
+    /// Source or compilation directories that point into a build tree
+    pub build_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompileUnit {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comp_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub producer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DwarfProducer {
+    /// e.g. "GNU C17 12.2.0"
+    pub compiler: String,
+    /// Switches present when built with -grecord-gcc-switches
+    pub flags: Vec<String>,
+    pub units: usize,
+}
+
+/// Summarize the compile units of a binary that still carries `.debug_info`
+pub fn summarize_dwarf(file_path: &str) -> Option<DwarfSummary> {
+    // Only the .debug_* sections gimli asks for are read from disk
+    let cache = ReadCache::new(File::open(file_path).ok()?);
+    let object = object::File::parse(&cache).ok()?;
+    let endian = if object.is_little_endian() {
+        RunTimeEndian::Little
+    } else {
+        RunTimeEndian::Big
+    };
+    let sections = DwarfSections::load(|id| -> Result<Cow<[u8]>, gimli::Error> {
+        Ok(load_section(&object, id.name()))
+    })
+    .ok()?;
+    let dwarf = sections.borrow(|section| EndianSlice::new(section, endian));
+
+    let mut units: Vec<CompileUnit> = Vec::new();
+    let mut source_files = BTreeSet::new();
+    let mut headers = dwarf.units();
+    while let Ok(Some(header)) = headers.next() {
+        let Ok(unit) = dwarf.unit(header) else {
+            continue;
+        };
+        if let Some(compile_unit) = compile_unit(&dwarf, &unit) {
+            units.push(compile_unit);
+        }
+        line_table_files(&dwarf, &unit, &mut source_files);
+    }
+
+    if units.is_empty() {
+        return None;
+    }
+
+    let mut producers: Vec<DwarfProducer> = Vec::new();
+    let mut languages = BTreeSet::new();
+    let mut build_paths = BTreeSet::new();
+    for unit in &units {
+        if let Some(producer) = &unit.producer {
+            let (compiler, flags) = split_producer(producer);
+            match producers.iter_mut().find(|p| p.compiler == compiler && p.flags == flags) {
+                Some(existing) => existing.units += 1,
+                None => producers.push(DwarfProducer { compiler, flags, units: 1 }),
+            }
+        }
+        if let Some(language) = &unit.language {
+            languages.insert(language.clone());
+        }
+        if let Some(comp_dir) = &unit.comp_dir {
+            if is_build_tree_path(comp_dir) {
+                build_paths.insert(comp_dir.clone());
+            }
+        }
+        if unit.name.starts_with('/') && is_build_tree_path(&unit.name) {
+            build_paths.insert(unit.name.clone());
+        }
+    }
+
+    // Files under an already reported comp_dir add nothing new
+    let covered: Vec<String> = build_paths.iter().map(|path| format!("{}/", path.trim_end_matches('/'))).collect();
+    for source_file in &source_files {
+        if is_build_tree_path(source_file) && !covered.iter().any(|dir| source_file.starts_with(dir.as_str())) {
+            build_paths.insert(source_file.clone());
+        }
+    }
+
+    let compile_unit_count = units.len();
+    units.truncate(MAX_COMPILE_UNITS);
+
+    Some(DwarfSummary {
+        compile_unit_count,
+        compile_units: units,
+        producers,
+        languages: languages.into_iter().collect(),
+        source_file_count: source_files.len(),
+        source_files: source_files.into_iter().take(MAX_SOURCE_FILES).collect(),
+        build_paths: build_paths.into_iter().collect(),
+    })
+}
+
+/// Section contents, inflated if compressed; missing or oversized sections load as empty
+fn load_section<'data, R: ReadRef<'data>>(object: &object::File<'data, R>, name: &str) -> Cow<'data, [u8]> {
+    object
+        .section_by_name(name)
+        .filter(|section| {
+            section
+                .compressed_file_range()
+                .is_ok_and(|range| range.uncompressed_size <= MAX_SECTION_SIZE)
+        })
+        .and_then(|section| section.uncompressed_data().ok())
+        .unwrap_or(Cow::Borrowed(&[]))
+}
+
+/// Name, directory, producer and language from the unit's root DIE
+fn compile_unit(dwarf: &Dwarf<Reader>, unit: &Unit<Reader>) -> Option<CompileUnit> {
+    let mut entries = unit.entries();
+    let root = entries.next_dfs().ok()??;
+    if !matches!(
+        root.tag(),
+        gimli::DW_TAG_compile_unit | gimli::DW_TAG_partial_unit | gimli::DW_TAG_skeleton_unit
+    ) {
+        return None;
+    }
+
+    let producer = root
+        .attr_value(gimli::DW_AT_producer)
+        .and_then(|value| dwarf.attr_string(unit, value).ok())
+        .map(|s| s.to_string_lossy().to_string());
+    let language = match root.attr_value(gimli::DW_AT_language) {
+        Some(AttributeValue::Language(language)) => Some(language_name(language)),
+        _ => None,
+    };
+
+    Some(CompileUnit {
+        name: unit.name.map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+        comp_dir: unit.comp_dir.map(|s| s.to_string_lossy().to_string()),
+        producer,
+        language,
+    })
+}
+
+/// Add every file named in the unit's `.debug_line` file table, joined onto
+/// its include directory and the unit's comp_dir
+fn line_table_files(dwarf: &Dwarf<Reader>, unit: &Unit<Reader>, files: &mut BTreeSet<String>) {
+    let Some(program) = &unit.line_program else {
+        return;
+    };
+    let header = program.header();
+    let comp_dir = unit.comp_dir.map(|dir| dir.to_string_lossy().to_string());
+    let string = |value| {
+        dwarf
+            .attr_string(unit, value)
+            .ok()
+            .map(|s| s.to_string_lossy().to_string())
+    };
+    for file in header.file_names() {
+        let Some(name) = string(file.path_name()) else {
+            continue;
+        };
+        let directory = file.directory(header).and_then(string);
+        let path = [comp_dir.as_deref(), directory.as_deref()]
+            .into_iter()
+            .flatten()
+            .fold(String::new(), |path, part| join_path(&path, part));
+        files.insert(join_path(&path, &name));
+    }
+}
+
+/// Join like a path, an absolute `part` replaces what came before
+fn join_path(base: &str, part: &str) -> String {
+    if base.is_empty() || part.starts_with('/') {
+        part.to_string()
+    } else {
+        format!("{}/{}", base.trim_end_matches('/'), part)
+    }
+}
+
+/// DW_LANG_C11 -> "C11", DW_LANG_C_plus_plus_14 -> "C++14"
+fn language_name(language: DwLang) -> String {
+    match language.static_string() {
+        Some(name) => name.trim_start_matches("DW_LANG_").replace("C_plus_plus", "C++"),
+        None => format!("0x{:x}", language.0),
+    }
+}
+
+/// Split "GNU C17 12.2.0 -mtune=generic -O2" into compiler and switches
+fn split_producer(producer: &str) -> (String, Vec<String>) {
+    let mut compiler = Vec::new();
+    let mut flags = Vec::new();
+    for token in producer.split_whitespace() {
+        if token.starts_with('-') || !flags.is_empty() {
+            flags.push(token.to_string());
+        } else {
+            compiler.push(token);
+        }
+    }
+    (compiler.join(" "), flags)
+}
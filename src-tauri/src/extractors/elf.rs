@@ -1,6 +1,18 @@
+use crate::extractors::dwarf::{summarize_dwarf, DwarfSummary};
+use crate::extractors::kmod::{has_module_signature, parse_modinfo, KernelModuleInfo};
+use crate::extractors::notes::{
+    parse_note_section, AbiTag, ElfNote, NoteContext, NoteSummary, PackageNote,
+};
+use crate::extractors::provenance::{
+    parse_auditable_deps, parse_go_buildinfo, GoBuildInfo, RustPackage,
+};
+use crate::utils::entropy::shannon_entropy;
 use crate::utils::shell::exec_command_with_env;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -8,6 +20,15 @@ pub struct ELFInfo {
     #[serde(rename = "type")]
     pub elf_type: String,
     pub machine: String,
+    /// ELF32 or ELF64
+    #[serde(default)]
+    pub class: String,
+    /// "little" or "big"
+    #[serde(default)]
+    pub endian: String,
+    /// Processor-specific e_flags as decoded by readelf
+    #[serde(default)]
+    pub flags: String,
     pub entry: String,
     #[serde(default)]
     pub program_headers: Vec<String>,
@@ -17,18 +38,162 @@ pub struct ELFInfo {
     pub dependencies: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub interpreter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpath: Option<Vec<RpathEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runpath: Option<Vec<RpathEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debuglink: Option<DebugLink>,
+    /// No `.symtab` section left in the binary
+    #[serde(default)]
+    pub stripped: bool,
+    /// DWARF `.debug_info` (or compressed `.zdebug_info`) is present
+    #[serde(default)]
+    pub has_debug_info: bool,
+    /// Matching `usr/lib/debug/.build-id/xx/yyyy.debug` file, if one was found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug_file: Option<String>,
+    /// Present when the object is a Linux kernel module
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel_module: Option<KernelModuleInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub go_build_info: Option<GoBuildInfo>,
+    /// Crates embedded by cargo-auditable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rust_dependencies: Option<Vec<RustPackage>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dwarf: Option<DwarfSummary>,
+    #[serde(default)]
+    pub notes: Vec<ElfNote>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abi_tag: Option<AbiTag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub go_build_id: Option<String>,
+    /// systemd `.note.package` metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_note: Option<PackageNote>,
+    #[serde(default)]
+    pub segments: Vec<ElfSegment>,
+    /// Section header count; zero means the section table was removed, None that readelf did not report it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_header_count: Option<u32>,
+    #[serde(default)]
+    pub section_entropy: Vec<SectionEntropy>,
+    /// Known packer whose signature was found, e.g. "UPX"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packer: Option<String>,
+}
+
+/// Program header entry as reported by `readelf -l -W`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElfSegment {
+    #[serde(rename = "type")]
+    pub segment_type: String,
+    pub offset: u64,
+    pub file_size: u64,
+    pub mem_size: u64,
+    /// Combination of R, W and E
+    pub flags: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionEntropy {
+    pub name: String,
+    pub size: u64,
+    pub entropy: f64,
 }
 
+/// Contents of the `.gnu_debuglink` section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugLink {
+    pub name: String,
+    pub crc: String,
+}
+
+/// Section header entry as reported by `readelf -S -W`
+#[derive(Debug, Clone)]
+struct ElfSection {
+    name: String,
+    section_type: String,
+    offset: u64,
+    size: u64,
+    align: u64,
+}
+
+/// A single component of DT_RPATH or DT_RUNPATH
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpathEntry {
+    pub path: String,
+    pub kind: RpathKind,
+    pub insecure: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RpathKind {
+    /// Relative to the binary's own directory via $ORIGIN
+    Origin,
+    /// Absolute path that exists in the package payload
+    Package,
+    /// Absolute path outside the package, e.g. a system directory
+    Absolute,
+    /// Absolute path pointing into a build tree or temp directory
+    BuildTree,
+    /// Empty component, which the loader treats as the current directory
+    Empty,
+    /// Relative path, resolved against the current directory
+    Relative,
+    /// Absolute path with a `$LIB` or `$PLATFORM` token the loader expands
+    LoaderToken,
+}
+
+/// Path prefixes that indicate a leaked build environment
+pub const BUILD_TREE_PREFIXES: &[&str] = &[
+    "/home/", "/root/", "/tmp/", "/var/tmp/", "/build/", "/builds/",
+    "/buildd/", "/workspace/", "/usr/src/",
+];
+
 /// Analyze an ELF file and return detailed information
-pub fn analyze_elf(file_path: &str) -> Result<ELFInfo, String> {
+///
+/// `package_root` is the directory the package payload was extracted to,
+/// used to resolve absolute paths against the package contents.
+pub fn analyze_elf(file_path: &str, package_root: &Path) -> Result<ELFInfo, String> {
     let mut info = ELFInfo {
         elf_type: "unknown".to_string(),
         machine: "unknown".to_string(),
+        class: "unknown".to_string(),
+        endian: "unknown".to_string(),
+        flags: String::new(),
         entry: "0x0".to_string(),
         program_headers: Vec::new(),
         section_headers: Vec::new(),
         dependencies: None,
         interpreter: None,
+        rpath: None,
+        runpath: None,
+        build_id: None,
+        debuglink: None,
+        stripped: false,
+        has_debug_info: false,
+        debug_file: None,
+        kernel_module: None,
+        go_build_info: None,
+        rust_dependencies: None,
+        dwarf: None,
+        notes: Vec::new(),
+        abi_tag: None,
+        go_build_id: None,
+        package_note: None,
+        segments: Vec::new(),
+        section_header_count: None,
+        section_entropy: Vec::new(),
+        packer: None,
     };
 
     // Use LC_ALL=C to ensure consistent English output
@@ -38,8 +203,22 @@ pub fn analyze_elf(file_path: &str) -> Result<ELFInfo, String> {
     let stdout = exec_command_with_env("readelf", &["-h", file_path], 500, &env_vars)
         .map_err(|e| format!("Failed to read ELF header: {}", e))?;
 
+    let mut little_endian = true;
     for line in stdout.lines() {
         let line = line.trim();
+        if let Some(value) = line.strip_prefix("Class:") {
+            info.class = value.trim().to_string();
+        }
+        if line.starts_with("Data:") {
+            little_endian = !line.contains("big endian");
+            info.endian = if little_endian { "little" } else { "big" }.to_string();
+        }
+        if let Some(value) = line.strip_prefix("Flags:") {
+            info.flags = value.trim().to_string();
+        }
+        if let Some(value) = line.strip_prefix("Number of section headers:") {
+            info.section_header_count = parse_section_header_count(value);
+        }
         if line.contains("Type:") {
             if let Some(value) = line.split(':').nth(1) {
                 info.elf_type = value.trim().to_string();
@@ -106,6 +285,73 @@ pub fn analyze_elf(file_path: &str) -> Result<ELFInfo, String> {
         }
     }
 
+    info.segments = read_segments(file_path, &env_vars);
+    info.packer = detect_packer_signature(file_path);
+
+    // Stripped state and debug link, from the wide section table
+    let sections = read_section_table(file_path, &env_vars);
+    info.section_entropy = sections
+        .iter()
+        .filter(|s| s.section_type != "NOBITS")
+        .filter_map(|section| {
+            let data = read_section_data(file_path, section)?;
+            Some(SectionEntropy {
+                name: section.name.clone(),
+                size: section.size,
+                entropy: shannon_entropy(&data),
+            })
+        })
+        .collect();
+    info.stripped = !sections.iter().any(|s| s.name == ".symtab");
+    info.has_debug_info = sections
+        .iter()
+        .any(|s| s.name == ".debug_info" || s.name == ".zdebug_info");
+    if let Some(section) = sections.iter().find(|s| s.name == ".gnu_debuglink") {
+        info.debuglink = read_section_data(file_path, section)
+            .and_then(|data| parse_debuglink(&data, little_endian));
+    }
+
+    if info.has_debug_info {
+        info.dwarf = summarize_dwarf(file_path);
+    }
+
+    // Kernel modules carry their metadata in .modinfo
+    if let Some(section) = sections.iter().find(|s| s.name == ".modinfo") {
+        if let Some(data) = read_section_data(file_path, section) {
+            let mut module = parse_modinfo(&data);
+            module.signed = has_module_signature(file_path);
+            info.kernel_module = Some(module);
+        }
+    }
+
+    // Embedded dependency lists from the Go and Rust toolchains
+    if let Some(section) = sections.iter().find(|s| s.name == ".go.buildinfo") {
+        info.go_build_info = read_section_data(file_path, section)
+            .and_then(|data| parse_go_buildinfo(&data));
+    }
+    if let Some(section) = sections.iter().find(|s| s.name == ".dep-v0") {
+        info.rust_dependencies = read_section_data(file_path, section)
+            .and_then(|data| parse_auditable_deps(&data));
+    }
+
+    // Decode all note sections (build ID, ABI tag, properties, package notes)
+    let mut note_summary = NoteSummary::default();
+    for section in sections.iter().filter(|s| s.section_type == "NOTE") {
+        if let Some(data) = read_section_data(file_path, section) {
+            let ctx = NoteContext {
+                little_endian,
+                align: section.align as usize,
+                machine: &info.machine,
+            };
+            parse_note_section(&section.name, &data, ctx, &mut note_summary);
+        }
+    }
+    info.notes = note_summary.notes;
+    info.build_id = note_summary.build_id;
+    info.abi_tag = note_summary.abi_tag;
+    info.go_build_id = note_summary.go_build_id;
+    info.package_note = note_summary.package_note;
+
     // Get dynamic dependencies
     if let Ok(dyn_output) = exec_command_with_env("readelf", &["-d", file_path], 500, &env_vars) {
         let mut dependencies = Vec::new();
@@ -118,6 +364,12 @@ pub fn analyze_elf(file_path: &str) -> Result<ELFInfo, String> {
                         dependencies.push(lib_name);
                     }
                 }
+            } else if line.contains("(RPATH)") {
+                info.rpath = extract_bracketed(line)
+                    .map(|value| classify_search_path(value, package_root));
+            } else if line.contains("(RUNPATH)") {
+                info.runpath = extract_bracketed(line)
+                    .map(|value| classify_search_path(value, package_root));
             }
         }
         if !dependencies.is_empty() {
@@ -128,37 +380,270 @@ pub fn analyze_elf(file_path: &str) -> Result<ELFInfo, String> {
     Ok(info)
 }
 
-/// Analyze a .desktop file
-pub fn analyze_desktop(file_path: &str) -> Result<HashMap<String, String>, String> {
-    use std::fs;
+/// Parse the section table from `readelf -S -W`
+fn read_section_table(file_path: &str, env_vars: &[(&str, &str)]) -> Vec<ElfSection> {
+    let mut sections = Vec::new();
+    let output = match exec_command_with_env("readelf", &["-S", "-W", file_path], 500, env_vars) {
+        Ok(output) => output,
+        Err(_) => return sections,
+    };
 
-    let content = fs::read_to_string(file_path)
-        .map_err(|e| format!("Failed to read desktop file: {}", e))?;
+    for line in output.lines() {
+        let line = line.trim();
+        if !line.starts_with('[') || line.starts_with("[Nr]") {
+            continue;
+        }
+        let rest = match line.find(']') {
+            Some(pos) => &line[pos + 1..],
+            None => continue,
+        };
+        // Name Type Address Off Size ES [Flg] Lk Inf Al
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        if fields.len() < 9 {
+            continue;
+        }
+        let offset = u64::from_str_radix(fields[3], 16);
+        let size = u64::from_str_radix(fields[4], 16);
+        if let (Ok(offset), Ok(size)) = (offset, size) {
+            sections.push(ElfSection {
+                name: fields[0].to_string(),
+                section_type: fields[1].to_string(),
+                offset,
+                size,
+                align: fields[fields.len() - 1].parse().unwrap_or(4),
+            });
+        }
+    }
 
-    let mut result = HashMap::new();
-    let mut in_desktop_entry = false;
+    sections
+}
 
-    for line in content.lines() {
-        let trimmed = line.trim();
+/// Parse the program headers from `readelf -l -W`
+fn read_segments(file_path: &str, env_vars: &[(&str, &str)]) -> Vec<ElfSegment> {
+    let mut segments = Vec::new();
+    let output = match exec_command_with_env("readelf", &["-l", "-W", file_path], 500, env_vars) {
+        Ok(output) => output,
+        Err(_) => return segments,
+    };
 
-        if trimmed == "[Desktop Entry]" {
-            in_desktop_entry = true;
+    for line in output.lines() {
+        // Type Offset VirtAddr PhysAddr FileSiz MemSiz Flg Align, where Flg
+        // may itself contain spaces ("R E")
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 8 || !fields[1].starts_with("0x") {
             continue;
         }
+        let parse_hex = |value: &str| u64::from_str_radix(value.trim_start_matches("0x"), 16);
+        if let (Ok(offset), Ok(file_size), Ok(mem_size)) =
+            (parse_hex(fields[1]), parse_hex(fields[4]), parse_hex(fields[5]))
+        {
+            segments.push(ElfSegment {
+                segment_type: fields[0].to_string(),
+                offset,
+                file_size,
+                mem_size,
+                flags: fields[6..fields.len() - 1].concat(),
+            });
+        }
+    }
+
+    segments
+}
+
+/// Look for packer signatures near the start of the file
+fn detect_packer_signature(file_path: &str) -> Option<String> {
+    let mut head = vec![0u8; 4096];
+    let read = File::open(file_path).and_then(|mut f| f.read(&mut head)).ok()?;
+    head.truncate(read);
+
+    // UPX stores its "UPX!" header right after the ELF program headers
+    if head.windows(4).any(|w| w == b"UPX!") {
+        return Some("UPX".to_string());
+    }
+    None
+}
+
+/// Read the raw bytes of a section straight from the file
+fn read_section_data(file_path: &str, section: &ElfSection) -> Option<Vec<u8>> {
+    // Sections this large are never small metadata we care about
+    const MAX_SECTION_SIZE: u64 = 16 * 1024 * 1024;
+    if section.size == 0 || section.size > MAX_SECTION_SIZE {
+        return None;
+    }
+
+    let mut file = File::open(file_path).ok()?;
+    file.seek(SeekFrom::Start(section.offset)).ok()?;
+    let mut data = vec![0u8; section.size as usize];
+    file.read_exact(&mut data).ok()?;
+    Some(data)
+}
+
+/// Decode `.gnu_debuglink`: a NUL-terminated file name padded to 4 bytes, then a CRC32
+fn parse_debuglink(data: &[u8], little_endian: bool) -> Option<DebugLink> {
+    let name_end = data.iter().position(|&b| b == 0)?;
+    let name = String::from_utf8_lossy(&data[..name_end]).to_string();
+    let crc_start = (name_end + 1).next_multiple_of(4);
+    let crc_bytes: [u8; 4] = data.get(crc_start..crc_start + 4)?.try_into().ok()?;
+    let crc = if little_endian {
+        u32::from_le_bytes(crc_bytes)
+    } else {
+        u32::from_be_bytes(crc_bytes)
+    };
 
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
-            // Different section, stop parsing
-            break;
+    Some(DebugLink {
+        name,
+        crc: format!("{:08x}", crc),
+    })
+}
+
+/// Map build IDs to the `usr/lib/debug/.build-id/xx/yyyy.debug` files under `root`
+pub fn collect_build_id_files(root: &Path) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+    let build_id_dir = root.join("usr/lib/debug/.build-id");
+
+    for entry in walkdir::WalkDir::new(&build_id_dir)
+        .min_depth(2)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let file_name = entry.file_name().to_string_lossy();
+        let stem = match file_name.strip_suffix(".debug") {
+            Some(stem) => stem,
+            None => continue,
+        };
+        let prefix = entry
+            .path()
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if let Ok(relative) = entry.path().strip_prefix(root) {
+            result.insert(
+                format!("{}{}", prefix, stem),
+                relative.to_string_lossy().to_string(),
+            );
         }
+    }
+
+    result
+}
 
-        if in_desktop_entry && trimmed.contains('=') {
-            if let Some(pos) = trimmed.find('=') {
-                let key = trimmed[..pos].trim().to_string();
-                let value = trimmed[pos + 1..].trim().to_string();
-                result.insert(key, value);
+/// Whether an absolute path points into a build tree or temp directory
+pub fn is_build_tree_path(path: &str) -> bool {
+    let with_slash = format!("{}/", path.trim_end_matches('/'));
+    BUILD_TREE_PREFIXES.iter().any(|prefix| with_slash.starts_with(prefix))
+}
+
+/// Section header count as readelf prints it; a count too large for e_shnum
+/// is shown in parentheses after the 0 stored there, e.g. `0 (65280)`
+fn parse_section_header_count(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let count = match value.split_once('(') {
+        Some((_, extended)) => extended.trim_end_matches(')'),
+        None => value,
+    };
+    count.trim().parse().ok()
+}
+
+/// Return the text between the first '[' and the following ']'
+fn extract_bracketed(line: &str) -> Option<&str> {
+    let start = line.find('[')? + 1;
+    let end = line[start..].find(']')?;
+    Some(&line[start..start + end])
+}
+
+/// Split a colon-separated RPATH/RUNPATH value and classify each component
+fn classify_search_path(value: &str, package_root: &Path) -> Vec<RpathEntry> {
+    value
+        .split(':')
+        .map(|component| {
+            let kind = classify_rpath_component(component, package_root);
+            RpathEntry {
+                path: component.to_string(),
+                kind,
+                insecure: matches!(
+                    kind,
+                    RpathKind::BuildTree | RpathKind::Empty | RpathKind::Relative
+                ),
             }
-        }
+        })
+        .collect()
+}
+
+/// Rest of the component after a leading `$NAME` or `${NAME}` dynamic string token
+fn strip_loader_token<'a>(component: &'a str, name: &str) -> Option<&'a str> {
+    let rest = component.strip_prefix('$')?;
+    let rest = match rest.strip_prefix('{') {
+        Some(braced) => braced.strip_prefix(name)?.strip_prefix('}')?,
+        None => rest.strip_prefix(name)?,
+    };
+    // $ORIGINAL is not $ORIGIN, the token must end the component or a directory
+    (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+}
+
+fn has_loader_token(component: &str) -> bool {
+    component
+        .match_indices('$')
+        .any(|(index, _)| ["LIB", "PLATFORM"].iter().any(|name| strip_loader_token(&component[index..], name).is_some()))
+}
+
+fn classify_rpath_component(component: &str, package_root: &Path) -> RpathKind {
+    if component.is_empty() {
+        return RpathKind::Empty;
+    }
+    if strip_loader_token(component, "ORIGIN").is_some() {
+        return RpathKind::Origin;
+    }
+    if !component.starts_with('/') {
+        return RpathKind::Relative;
     }
 
-    Ok(result)
+    if is_build_tree_path(component) {
+        return RpathKind::BuildTree;
+    }
+    // The expansion depends on the host, so it cannot be looked up in the package
+    if has_loader_token(component) {
+        return RpathKind::LoaderToken;
+    }
+
+    if is_package_directory(component, package_root) {
+        RpathKind::Package
+    } else {
+        RpathKind::Absolute
+    }
+}
+
+/// Whether an absolute directory exists in the package, `..` and symlinks
+/// that lead out of the extraction directory do not count
+fn is_package_directory(component: &str, package_root: &Path) -> bool {
+    let Ok(root) = package_root.canonicalize() else {
+        return false;
+    };
+    package_root
+        .join(component.trim_start_matches('/'))
+        .canonicalize()
+        .is_ok_and(|path| path.starts_with(&root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_plain_section_header_count() {
+        assert_eq!(parse_section_header_count("          29"), Some(29));
+        assert_eq!(parse_section_header_count(" 0"), Some(0));
+    }
+
+    #[test]
+    fn reads_extended_section_header_count() {
+        assert_eq!(parse_section_header_count("          0 (65280)"), Some(65280));
+    }
+
+    #[test]
+    fn unparsable_section_header_count_is_unknown() {
+        assert_eq!(parse_section_header_count(""), None);
+        assert_eq!(parse_section_header_count("unknown"), None);
+    }
 }
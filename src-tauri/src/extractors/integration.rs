@@ -0,0 +1,749 @@
+use crate::utils::xml::parse_xml;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::OnceLock;
+
+/// How a package hooks into the system, one entry per configuration file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum IntegrationEntry {
+    DbusService(DbusService),
+    DbusPolicy(DbusPolicy),
+    Polkit(PolkitPolicy),
+    Udev(UdevRules),
+    Cron(CronTable),
+    Sudoers(Sudoers),
+    AppArmor(AppArmorProfiles),
+    PamConfig(PamConfig),
+    PamModule(PamModule),
+    Modprobe(ModprobeConfig),
+    Sysctl(SysctlConfig),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbusService {
+    /// `system` or `session`
+    pub bus: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub systemd_service: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbusPolicy {
+    pub bus: String,
+    /// Bus names the policy allows someone to own
+    pub owns: Vec<String>,
+    pub rules: Vec<DbusPolicyRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbusPolicyRule {
+    /// `context="default"`, `user="root"`, `group="netdev"`, `at_console="true"`
+    pub applies_to: String,
+    pub allow: bool,
+    pub attributes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolkitPolicy {
+    pub actions: Vec<PolkitAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolkitAction {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_any: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_inactive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_active: Option<String>,
+    /// `org.freedesktop.policykit.exec.path` annotation for pkexec actions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UdevRules {
+    pub rules: Vec<UdevRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UdevRule {
+    pub line: usize,
+    /// `==` and `!=` keys, i.e. which devices the rule applies to
+    pub matches: Vec<UdevKey>,
+    pub assignments: Vec<UdevKey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UdevKey {
+    pub key: String,
+    pub operator: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CronTable {
+    pub environment: Vec<String>,
+    pub jobs: Vec<CronJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CronJob {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub schedule: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sudoers {
+    pub defaults: Vec<String>,
+    pub aliases: Vec<SudoersAlias>,
+    pub rules: Vec<SudoersRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SudoersAlias {
+    /// User_Alias, Runas_Alias, Host_Alias or Cmnd_Alias
+    pub kind: String,
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SudoersRule {
+    pub line: usize,
+    pub users: Vec<String>,
+    pub hosts: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_as: Option<String>,
+    /// NOPASSWD, SETENV, ...
+    pub tags: Vec<String>,
+    pub commands: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppArmorProfiles {
+    pub includes: Vec<String>,
+    pub profiles: Vec<AppArmorProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppArmorProfile {
+    pub line: usize,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<String>,
+    pub flags: Vec<String>,
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PamConfig {
+    pub rules: Vec<PamRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PamRule {
+    pub line: usize,
+    /// auth, account, password, session or `@include`
+    pub module_type: String,
+    pub control: String,
+    pub module: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PamModule {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModprobeConfig {
+    pub directives: Vec<ModprobeDirective>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModprobeDirective {
+    pub line: usize,
+    /// alias, options, install, remove, blacklist or softdep
+    pub command: String,
+    pub module: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub args: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SysctlConfig {
+    pub settings: Vec<SysctlSetting>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SysctlSetting {
+    pub line: usize,
+    pub key: String,
+    pub value: String,
+    /// `-` prefix, errors setting the key are ignored
+    pub ignore_errors: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IntegrationKind {
+    DbusService,
+    DbusPolicy,
+    Polkit,
+    Udev,
+    Cron,
+    CronScript(&'static str),
+    Sudoers,
+    AppArmor,
+    PamConfig,
+    PamModule,
+    Modprobe,
+    Sysctl,
+}
+
+fn classify(path: &str) -> Option<IntegrationKind> {
+    let (dir, name) = path.rsplit_once('/')?;
+    if name.is_empty() || name.starts_with('.') {
+        return None;
+    }
+    let kind = match dir {
+        "usr/share/dbus-1/services" | "usr/share/dbus-1/system-services" if name.ends_with(".service") => {
+            IntegrationKind::DbusService
+        }
+        "usr/share/dbus-1/system.d" | "usr/share/dbus-1/session.d" | "etc/dbus-1/system.d" | "etc/dbus-1/session.d"
+            if name.ends_with(".conf") =>
+        {
+            IntegrationKind::DbusPolicy
+        }
+        "usr/share/polkit-1/actions" if name.ends_with(".policy") => IntegrationKind::Polkit,
+        "lib/udev/rules.d" | "usr/lib/udev/rules.d" | "etc/udev/rules.d" if name.ends_with(".rules") => {
+            IntegrationKind::Udev
+        }
+        "etc/cron.d" => IntegrationKind::Cron,
+        "etc/cron.hourly" => IntegrationKind::CronScript("@hourly"),
+        "etc/cron.daily" => IntegrationKind::CronScript("@daily"),
+        "etc/cron.weekly" => IntegrationKind::CronScript("@weekly"),
+        "etc/cron.monthly" => IntegrationKind::CronScript("@monthly"),
+        "etc/sudoers.d" => IntegrationKind::Sudoers,
+        "etc/apparmor.d" => IntegrationKind::AppArmor,
+        "etc/pam.d" => IntegrationKind::PamConfig,
+        "etc/modprobe.d" | "lib/modprobe.d" | "usr/lib/modprobe.d" if name.ends_with(".conf") => {
+            IntegrationKind::Modprobe
+        }
+        "etc/sysctl.d" | "lib/sysctl.d" | "usr/lib/sysctl.d" if name.ends_with(".conf") => IntegrationKind::Sysctl,
+        _ if dir.ends_with("/security")
+            && (dir.starts_with("lib/") || dir.starts_with("usr/lib/"))
+            && name.starts_with("pam_")
+            && name.ends_with(".so") =>
+        {
+            IntegrationKind::PamModule
+        }
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// Whether the path is a system integration file this module understands
+pub fn is_integration_file(path: &str) -> bool {
+    classify(path).is_some()
+}
+
+/// Parse a system integration file, `path` is relative to the package root
+pub fn analyze_integration(file_path: &str, path: &str) -> Result<IntegrationEntry, String> {
+    let kind = classify(path).ok_or("Not a system integration file")?;
+    let name = path.rsplit('/').next().unwrap_or_default();
+
+    match kind {
+        IntegrationKind::PamModule => {
+            return Ok(IntegrationEntry::PamModule(PamModule {
+                name: name.trim_end_matches(".so").to_string(),
+            }))
+        }
+        IntegrationKind::CronScript(schedule) => {
+            return Ok(IntegrationEntry::Cron(CronTable {
+                environment: Vec::new(),
+                jobs: vec![CronJob {
+                    line: None,
+                    schedule: schedule.to_string(),
+                    user: Some("root".to_string()),
+                    command: format!("/{}", path),
+                }],
+            }))
+        }
+        _ => {}
+    }
+
+    let bytes = fs::read(file_path).map_err(|e| format!("Failed to read integration file: {}", e))?;
+    let content = String::from_utf8_lossy(&bytes);
+    // Only the directory names the bus, file names such as org.freedesktop.systemd1.service do not
+    let bus = match path.rsplit('/').nth(1) {
+        Some("system-services" | "system.d") => "system",
+        _ => "session",
+    }
+    .to_string();
+
+    let entry = match kind {
+        IntegrationKind::DbusService => IntegrationEntry::DbusService(parse_dbus_service(&content, bus)),
+        IntegrationKind::DbusPolicy => IntegrationEntry::DbusPolicy(parse_dbus_policy(&content, bus)?),
+        IntegrationKind::Polkit => IntegrationEntry::Polkit(parse_polkit(&content)?),
+        IntegrationKind::Udev => IntegrationEntry::Udev(parse_udev(&content)),
+        IntegrationKind::Cron => IntegrationEntry::Cron(parse_cron(&content)),
+        IntegrationKind::Sudoers => IntegrationEntry::Sudoers(parse_sudoers(&content)),
+        IntegrationKind::AppArmor => IntegrationEntry::AppArmor(parse_apparmor(&content)),
+        IntegrationKind::PamConfig => IntegrationEntry::PamConfig(parse_pam(&content)),
+        IntegrationKind::Modprobe => IntegrationEntry::Modprobe(parse_modprobe(&content)),
+        IntegrationKind::Sysctl => IntegrationEntry::Sysctl(parse_sysctl(&content)),
+        IntegrationKind::PamModule | IntegrationKind::CronScript(_) => unreachable!(),
+    };
+    Ok(entry)
+}
+
+/// Non-comment lines with their 1-based line numbers, joining `\` continuations
+fn logical_lines(content: &str, comments: &[char]) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if pending.is_none() && (line.is_empty() || line.starts_with(comments)) {
+            continue;
+        }
+        let (start, mut text) = pending.take().unwrap_or((index + 1, String::new()));
+        match line.strip_suffix('\\') {
+            Some(continued) => {
+                text.push_str(continued.trim_end());
+                text.push(' ');
+                pending = Some((start, text));
+            }
+            None => {
+                text.push_str(line);
+                lines.push((start, text));
+            }
+        }
+    }
+    if let Some(last) = pending {
+        lines.push(last);
+    }
+    lines
+}
+
+fn parse_dbus_service(content: &str, bus: String) -> DbusService {
+    let mut values = BTreeMap::new();
+    for (_, line) in logical_lines(content, &['#']) {
+        if let Some((key, value)) = line.split_once('=') {
+            values.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    DbusService {
+        bus,
+        name: values.remove("Name"),
+        exec: values.remove("Exec"),
+        user: values.remove("User"),
+        systemd_service: values.remove("SystemdService"),
+    }
+}
+
+fn parse_dbus_policy(content: &str, bus: String) -> Result<DbusPolicy, String> {
+    let root = parse_xml(content)?;
+    let mut owns = Vec::new();
+    let mut rules = Vec::new();
+
+    for policy in root.children_named("policy") {
+        let applies_to = policy
+            .attributes
+            .first()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .unwrap_or_default();
+        for rule in policy.children.iter().filter(|c| c.name == "allow" || c.name == "deny") {
+            let allow = rule.name == "allow";
+            if allow {
+                owns.extend(
+                    ["own", "own_prefix"]
+                        .iter()
+                        .filter_map(|attr| rule.attr(attr))
+                        .map(|name| name.to_string()),
+                );
+            }
+            rules.push(DbusPolicyRule {
+                applies_to: applies_to.clone(),
+                allow,
+                attributes: rule.attributes.iter().cloned().collect(),
+            });
+        }
+    }
+    owns.sort();
+    owns.dedup();
+
+    Ok(DbusPolicy { bus, owns, rules })
+}
+
+fn parse_polkit(content: &str) -> Result<PolkitPolicy, String> {
+    let root = parse_xml(content)?;
+    let actions = root
+        .children_named("action")
+        .map(|action| {
+            let defaults = action.child("defaults");
+            let default = |name: &str| defaults.and_then(|d| d.child_text(name));
+            PolkitAction {
+                id: action.attr("id").unwrap_or_default().to_string(),
+                description: action.child_text("description"),
+                allow_any: default("allow_any"),
+                allow_inactive: default("allow_inactive"),
+                allow_active: default("allow_active"),
+                exec_path: action
+                    .children_named("annotate")
+                    .find(|a| a.attr("key") == Some("org.freedesktop.policykit.exec.path"))
+                    .map(|a| a.full_text()),
+            }
+        })
+        .collect();
+    Ok(PolkitPolicy { actions })
+}
+
+fn parse_udev(content: &str) -> UdevRules {
+    let rules = logical_lines(content, &['#'])
+        .into_iter()
+        .map(|(line, text)| {
+            let (matches, assignments) = parse_udev_keys(&text)
+                .into_iter()
+                .partition(|k| k.operator == "==" || k.operator == "!=");
+            UdevRule {
+                line,
+                matches,
+                assignments,
+            }
+        })
+        .collect();
+    UdevRules { rules }
+}
+
+/// `KEY=="value", ATTR{name}!="value", RUN+="..."`
+fn parse_udev_keys(text: &str) -> Vec<UdevKey> {
+    let mut keys = Vec::new();
+    let mut rest = text;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        let Some(op_start) = rest.find(['=', '!', '+', '-', ':']) else {
+            break;
+        };
+        let key = rest[..op_start].trim().to_string();
+        let after_key = &rest[op_start..];
+        let operator = ["==", "!=", "+=", "-=", ":=", "="]
+            .iter()
+            .find(|op| after_key.starts_with(*op))
+            .copied()
+            .unwrap_or("=");
+        let Some(value_part) = after_key[operator.len()..].trim_start().strip_prefix('"') else {
+            break;
+        };
+        let mut end = None;
+        let mut escaped = false;
+        for (i, c) in value_part.char_indices() {
+            match c {
+                '\\' if !escaped => escaped = true,
+                '"' if !escaped => {
+                    end = Some(i);
+                    break;
+                }
+                _ => escaped = false,
+            }
+        }
+        let Some(end) = end else {
+            break;
+        };
+        keys.push(UdevKey {
+            key,
+            operator: operator.to_string(),
+            value: value_part[..end].to_string(),
+        });
+        rest = &value_part[end + 1..];
+    }
+
+    keys
+}
+
+/// Split off the first `count` whitespace-separated fields, returning the remainder
+fn split_fields(line: &str, count: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut rest = line.trim_start();
+    for _ in 0..count {
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some((fields, rest))
+}
+
+fn parse_cron(content: &str) -> CronTable {
+    let mut environment = Vec::new();
+    let mut jobs = Vec::new();
+
+    for (line, text) in content.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let first = text.split_whitespace().next().unwrap_or_default();
+        if !text.starts_with('@') && first.contains('=') && !first.starts_with(|c: char| c.is_ascii_digit() || c == '*') {
+            environment.push(text.to_string());
+            continue;
+        }
+        // Schedule fields, then the user column of system crontabs
+        let schedule_fields = if text.starts_with('@') { 1 } else { 5 };
+        if let Some((fields, command)) = split_fields(text, schedule_fields + 1) {
+            jobs.push(CronJob {
+                line: Some(line),
+                schedule: fields[..schedule_fields].join(" "),
+                user: Some(fields[schedule_fields].to_string()),
+                command: command.to_string(),
+            });
+        }
+    }
+
+    CronTable { environment, jobs }
+}
+
+fn parse_sudoers(content: &str) -> Sudoers {
+    let mut defaults = Vec::new();
+    let mut aliases = Vec::new();
+    let mut rules = Vec::new();
+
+    for (line, text) in logical_lines(content, &[]) {
+        // `#` starts a comment unless it is a `#include` or a `#uid` user
+        if text.starts_with('#') && !text.starts_with("#include") && !text[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+        if text.starts_with("Defaults") {
+            defaults.push(text);
+            continue;
+        }
+        if text.starts_with("#include") || text.starts_with("@include") {
+            continue;
+        }
+        let first = text.split_whitespace().next().unwrap_or_default();
+        if matches!(first, "User_Alias" | "Runas_Alias" | "Host_Alias" | "Cmnd_Alias" | "Cmd_Alias") {
+            let definition = text[first.len()..].trim();
+            if let Some((name, members)) = definition.split_once('=') {
+                aliases.push(SudoersAlias {
+                    kind: first.to_string(),
+                    name: name.trim().to_string(),
+                    members: split_list(members),
+                });
+            }
+            continue;
+        }
+        if let Some(rule) = parse_sudoers_rule(line, &text) {
+            rules.push(rule);
+        }
+    }
+
+    Sudoers { defaults, aliases, rules }
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+/// `users hosts = (runas) TAG: commands`
+fn parse_sudoers_rule(line: usize, text: &str) -> Option<SudoersRule> {
+    let (who, what) = text.split_once('=')?;
+    let (users, hosts) = who.trim().rsplit_once(char::is_whitespace)?;
+    let mut what = what.trim();
+
+    let mut run_as = None;
+    if let Some(inner) = what.strip_prefix('(') {
+        let end = inner.find(')')?;
+        run_as = Some(inner[..end].trim().to_string());
+        what = inner[end + 1..].trim_start();
+    }
+
+    let mut tags = Vec::new();
+    while let Some((tag, rest)) = what.split_once(':') {
+        if tag.is_empty() || !tag.chars().all(|c| c.is_ascii_uppercase() || c == '_') {
+            break;
+        }
+        tags.push(tag.to_string());
+        what = rest.trim_start();
+    }
+
+    Some(SudoersRule {
+        line,
+        users: split_list(users),
+        hosts: hosts.trim().to_string(),
+        run_as,
+        tags,
+        commands: split_list(what),
+    })
+}
+
+fn apparmor_profile_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^(?:profile\s+(\S+)(?:\s+(/\S+|@\{\w+\}\S*))?|(/\S+|@\{\w+\}\S*))\s*(?:flags\s*=\s*\(([^)]*)\)\s*)?\{")
+            .unwrap()
+    })
+}
+
+fn parse_apparmor(content: &str) -> AppArmorProfiles {
+    let mut includes = Vec::new();
+    let mut profiles: Vec<AppArmorProfile> = Vec::new();
+    let mut depth = 0usize;
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if let Some(include) = line.strip_prefix("#include").or_else(|| line.strip_prefix("include")) {
+            if depth == 0 {
+                includes.push(include.trim().trim_matches(['<', '>', '"']).to_string());
+            }
+            continue;
+        }
+        let line = line.split_once('#').map(|(code, _)| code).unwrap_or(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if depth == 0 {
+            if let Some(caps) = apparmor_profile_regex().captures(line) {
+                let name = caps.get(1).or_else(|| caps.get(3)).map(|m| m.as_str().to_string()).unwrap_or_default();
+                profiles.push(AppArmorProfile {
+                    line: index + 1,
+                    name,
+                    attachment: caps.get(2).map(|m| m.as_str().to_string()),
+                    flags: caps.get(4).map(|m| split_list(m.as_str())).unwrap_or_default(),
+                    capabilities: Vec::new(),
+                });
+            }
+        } else if let Some(capabilities) = line.strip_prefix("capability") {
+            if let Some(profile) = profiles.last_mut() {
+                let capabilities = capabilities.trim_end_matches(',').split_whitespace();
+                profile.capabilities.extend(capabilities.map(|c| c.to_string()));
+            }
+        }
+
+        depth += line.matches('{').count();
+        depth = depth.saturating_sub(line.matches('}').count());
+    }
+
+    AppArmorProfiles { includes, profiles }
+}
+
+fn parse_pam(content: &str) -> PamConfig {
+    let mut rules = Vec::new();
+
+    for (line, text) in logical_lines(content, &['#']) {
+        if let Some(include) = text.strip_prefix("@include") {
+            rules.push(PamRule {
+                line,
+                module_type: "@include".to_string(),
+                control: String::new(),
+                module: include.trim().to_string(),
+                args: Vec::new(),
+            });
+            continue;
+        }
+        let Some((fields, rest)) = split_fields(&text, 1) else {
+            continue;
+        };
+        // Control is a keyword or a bracketed `[value=action ...]` list
+        let (control, rest) = if let Some(inner) = rest.strip_prefix('[') {
+            let Some(end) = inner.find(']') else {
+                continue;
+            };
+            (format!("[{}]", &inner[..end]), inner[end + 1..].trim_start())
+        } else {
+            match rest.split_once(char::is_whitespace) {
+                Some((control, rest)) => (control.to_string(), rest.trim_start()),
+                None => continue,
+            }
+        };
+        let mut words = rest.split_whitespace();
+        let Some(module) = words.next() else {
+            continue;
+        };
+        rules.push(PamRule {
+            line,
+            module_type: fields[0].to_string(),
+            control,
+            module: module.to_string(),
+            args: words.map(|w| w.to_string()).collect(),
+        });
+    }
+
+    PamConfig { rules }
+}
+
+fn parse_modprobe(content: &str) -> ModprobeConfig {
+    let directives = logical_lines(content, &['#'])
+        .into_iter()
+        .filter_map(|(line, text)| {
+            let mut parts = text.splitn(3, char::is_whitespace);
+            let command = parts.next()?.to_string();
+            let module = parts.next()?.to_string();
+            let args = parts.next().unwrap_or_default().trim().to_string();
+            Some(ModprobeDirective {
+                line,
+                command,
+                module,
+                args,
+            })
+        })
+        .collect();
+    ModprobeConfig { directives }
+}
+
+fn parse_sysctl(content: &str) -> SysctlConfig {
+    let settings = logical_lines(content, &['#', ';'])
+        .into_iter()
+        .filter_map(|(line, text)| {
+            let (key, value) = text.split_once('=')?;
+            let key = key.trim();
+            let ignore_errors = key.starts_with('-');
+            Some(SysctlSetting {
+                line,
+                key: key.trim_start_matches('-').to_string(),
+                value: value.trim().to_string(),
+                ignore_errors,
+            })
+        })
+        .collect();
+    SysctlConfig { settings }
+}
+
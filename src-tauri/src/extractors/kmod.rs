@@ -0,0 +1,154 @@
+use flate2::read::GzDecoder;
+use lzma_rust2::XzReader;
+use ruzstd::decoding::StreamingDecoder;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Marker the kernel's sign-file appends after a module signature
+const MODULE_SIG_MAGIC: &[u8] = b"~Module signature appended~\n";
+/// Compressed modules that inflate past this are not analyzed
+const MAX_MODULE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Metadata from a kernel module's `.modinfo` section
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KernelModuleInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub srcversion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vermagic: Option<String>,
+    /// Kernel release the module was built for, the first word of vermagic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel_version: Option<String>,
+    #[serde(default)]
+    pub depends: Vec<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub parameters: Vec<ModuleParameter>,
+    pub signed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleParameter {
+    pub name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub param_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Decode the NUL-separated `key=value` strings of `.modinfo`
+pub fn parse_modinfo(data: &[u8]) -> KernelModuleInfo {
+    let mut info = KernelModuleInfo::default();
+
+    for entry in data.split(|&b| b == 0) {
+        let entry = String::from_utf8_lossy(entry);
+        let (key, value) = match entry.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim().to_string()),
+            None => continue,
+        };
+
+        match key {
+            "name" => info.name = Some(value),
+            "license" => info.license = Some(value),
+            "author" => info.authors.push(value),
+            "description" => info.description = Some(value),
+            "version" => info.version = Some(value),
+            "srcversion" => info.srcversion = Some(value),
+            "vermagic" => {
+                info.kernel_version = value.split_whitespace().next().map(|v| v.to_string());
+                info.vermagic = Some(value);
+            }
+            "depends" => {
+                info.depends = value
+                    .split(',')
+                    .map(|d| d.trim().to_string())
+                    .filter(|d| !d.is_empty())
+                    .collect();
+            }
+            "alias" => info.aliases.push(value),
+            // parm=name:description and parmtype=name:type
+            "parm" | "parmtype" => {
+                let (name, detail) = match value.split_once(':') {
+                    Some((name, detail)) => (name.to_string(), detail.to_string()),
+                    None => (value, String::new()),
+                };
+                let index = match info.parameters.iter().position(|p| p.name == name) {
+                    Some(index) => index,
+                    None => {
+                        info.parameters.push(ModuleParameter {
+                            name,
+                            param_type: None,
+                            description: None,
+                        });
+                        info.parameters.len() - 1
+                    }
+                };
+                if key == "parm" {
+                    info.parameters[index].description = Some(detail);
+                } else {
+                    info.parameters[index].param_type = Some(detail);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    info
+}
+
+/// Check whether a module signature is appended to the file
+pub fn has_module_signature(file_path: &str) -> bool {
+    let mut file = match File::open(file_path) {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+    let mut tail = vec![0u8; MODULE_SIG_MAGIC.len()];
+    file.seek(SeekFrom::End(-(MODULE_SIG_MAGIC.len() as i64)))
+        .and_then(|_| file.read_exact(&mut tail))
+        .map(|_| tail == MODULE_SIG_MAGIC)
+        .unwrap_or(false)
+}
+
+/// Whether a path is a kernel module compressed by `make modules_install`
+pub fn is_compressed_module(path: &str) -> bool {
+    path.ends_with(".ko.gz") || path.ends_with(".ko.xz") || path.ends_with(".ko.zst")
+}
+
+/// Inflate a `.ko.gz`, `.ko.xz` or `.ko.zst` module to `dest` so it can be handed to readelf
+pub fn decompress_module(file_path: &Path, dest: &Path) -> Result<(), String> {
+    let file = File::open(file_path).map_err(|e| format!("Failed to open module: {}", e))?;
+    let reader = BufReader::new(file);
+    let decoder: Box<dyn Read> = match file_path.extension().and_then(|e| e.to_str()) {
+        Some("gz") => Box::new(GzDecoder::new(reader)),
+        Some("xz") => Box::new(XzReader::new(reader, true)),
+        Some("zst") => Box::new(
+            StreamingDecoder::new(reader).map_err(|e| format!("Invalid zstd module: {}", e))?,
+        ),
+        _ => return Err("Not a compressed kernel module".to_string()),
+    };
+
+    let mut output = File::create(dest).map_err(|e| format!("Failed to create module file: {}", e))?;
+    // Read one byte past the limit to tell a module that is exactly the limit from a bigger one
+    let written = io::copy(&mut decoder.take(MAX_MODULE_SIZE + 1), &mut output)
+        .map_err(|e| format!("Failed to decompress module: {}", e))?;
+    if written > MAX_MODULE_SIZE {
+        let _ = fs::remove_file(dest);
+        return Err(format!("Module decompresses to more than {} bytes", MAX_MODULE_SIZE));
+    }
+    Ok(())
+}
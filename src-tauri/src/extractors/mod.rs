@@ -1,2 +1,13 @@
+pub mod appstream;
+pub mod archive;
+pub mod changelog;
+pub mod copyright;
 pub mod deb;
+pub mod desktop;
+pub mod dwarf;
 pub mod elf;
+pub mod integration;
+pub mod kmod;
+pub mod notes;
+pub mod provenance;
+pub mod systemd;
@@ -0,0 +1,358 @@
+use serde::{Deserialize, Serialize};
+
+const NT_GNU_ABI_TAG: u32 = 1;
+const NT_GNU_HWCAP: u32 = 2;
+const NT_GNU_BUILD_ID: u32 = 3;
+const NT_GNU_GOLD_VERSION: u32 = 4;
+const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+const NT_GO_BUILD_ID: u32 = 4;
+const NT_FDO_PACKAGING_METADATA: u32 = 0xcafe_1a7e;
+
+const GNU_PROPERTY_STACK_SIZE: u32 = 1;
+const GNU_PROPERTY_NO_COPY_ON_PROTECTED: u32 = 2;
+const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc000_0000;
+const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc000_0002;
+const GNU_PROPERTY_X86_ISA_1_NEEDED: u32 = 0xc000_8002;
+
+/// A single decoded ELF note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElfNote {
+    pub section: String,
+    pub owner: String,
+    #[serde(rename = "type")]
+    pub note_type: u32,
+    pub type_name: String,
+    /// Human-readable rendering of the descriptor
+    pub description: String,
+}
+
+/// Contents of `NT_GNU_ABI_TAG`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiTag {
+    pub os: String,
+    /// Minimum kernel version the binary requires
+    pub kernel_version: String,
+}
+
+/// systemd package-notes metadata from `.note.package`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageNote {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub package_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distro: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug_info_url: Option<String>,
+}
+
+/// Everything decoded from the note sections of one binary
+#[derive(Debug, Clone, Default)]
+pub struct NoteSummary {
+    pub notes: Vec<ElfNote>,
+    pub build_id: Option<String>,
+    pub abi_tag: Option<AbiTag>,
+    pub go_build_id: Option<String>,
+    pub package_note: Option<PackageNote>,
+}
+
+/// Byte order and word size needed to decode note descriptors
+#[derive(Debug, Clone, Copy)]
+pub struct NoteContext<'a> {
+    pub little_endian: bool,
+    /// Note alignment, 8 for ELF64 property notes and 4 otherwise
+    pub align: usize,
+    pub machine: &'a str,
+}
+
+impl NoteContext<'_> {
+    fn read_u32(&self, data: &[u8], pos: usize) -> Option<u32> {
+        let bytes: [u8; 4] = data.get(pos..pos + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_u64(&self, data: &[u8], pos: usize) -> Option<u64> {
+        let bytes: [u8; 8] = data.get(pos..pos + 8)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        })
+    }
+}
+
+/// Decode the notes in one note section and fold them into `summary`
+pub fn parse_note_section(section: &str, data: &[u8], ctx: NoteContext, summary: &mut NoteSummary) {
+    let align = if ctx.align == 8 { 8 } else { 4 };
+    let mut pos = 0;
+
+    while pos + 12 <= data.len() {
+        let (namesz, descsz, note_type) = match (
+            ctx.read_u32(data, pos),
+            ctx.read_u32(data, pos + 4),
+            ctx.read_u32(data, pos + 8),
+        ) {
+            (Some(n), Some(d), Some(t)) => (n as usize, d as usize, t),
+            _ => break,
+        };
+        let name_start = pos + 12;
+        let desc_start = name_start + namesz.next_multiple_of(4);
+        let desc_end = desc_start + descsz;
+        let (name, desc) = match (data.get(name_start..name_start + namesz), data.get(desc_start..desc_end)) {
+            (Some(name), Some(desc)) => (name, desc),
+            _ => break,
+        };
+        let owner = String::from_utf8_lossy(name)
+            .trim_end_matches('\0')
+            .to_string();
+
+        let (type_name, description) = decode_note(&owner, note_type, desc, ctx, summary);
+        summary.notes.push(ElfNote {
+            section: section.to_string(),
+            owner,
+            note_type,
+            type_name,
+            description,
+        });
+
+        pos = desc_end.next_multiple_of(align);
+    }
+}
+
+fn decode_note(
+    owner: &str,
+    note_type: u32,
+    desc: &[u8],
+    ctx: NoteContext,
+    summary: &mut NoteSummary,
+) -> (String, String) {
+    match (owner, note_type) {
+        ("GNU", NT_GNU_ABI_TAG) => {
+            let words: Vec<u32> = (0..4).filter_map(|i| ctx.read_u32(desc, i * 4)).collect();
+            if words.len() < 4 {
+                return ("NT_GNU_ABI_TAG".to_string(), hex_string(desc));
+            }
+            let os = match words[0] {
+                0 => "Linux",
+                1 => "Hurd",
+                2 => "Solaris",
+                3 => "FreeBSD",
+                _ => "Unknown",
+            };
+            let tag = AbiTag {
+                os: os.to_string(),
+                kernel_version: format!("{}.{}.{}", words[1], words[2], words[3]),
+            };
+            let description = format!("OS: {}, ABI: {}", tag.os, tag.kernel_version);
+            summary.abi_tag = Some(tag);
+            ("NT_GNU_ABI_TAG".to_string(), description)
+        }
+        ("GNU", NT_GNU_HWCAP) => ("NT_GNU_HWCAP".to_string(), hex_string(desc)),
+        ("GNU", NT_GNU_BUILD_ID) => {
+            let build_id = hex_string(desc);
+            summary.build_id = Some(build_id.clone());
+            ("NT_GNU_BUILD_ID".to_string(), build_id)
+        }
+        ("GNU", NT_GNU_GOLD_VERSION) => ("NT_GNU_GOLD_VERSION".to_string(), c_string(desc)),
+        ("GNU", NT_GNU_PROPERTY_TYPE_0) => (
+            "NT_GNU_PROPERTY_TYPE_0".to_string(),
+            decode_gnu_properties(desc, ctx).join("; "),
+        ),
+        ("Go", NT_GO_BUILD_ID) => {
+            let build_id = c_string(desc);
+            summary.go_build_id = Some(build_id.clone());
+            ("NT_GO_BUILD_ID".to_string(), build_id)
+        }
+        ("FDO", NT_FDO_PACKAGING_METADATA) => {
+            let json = c_string(desc);
+            summary.package_note = serde_json::from_str(&json).ok();
+            ("NT_FDO_PACKAGING_METADATA".to_string(), json)
+        }
+        _ => (format!("0x{:x}", note_type), hex_string(desc)),
+    }
+}
+
+/// Decode the property array of `NT_GNU_PROPERTY_TYPE_0`
+fn decode_gnu_properties(desc: &[u8], ctx: NoteContext) -> Vec<String> {
+    let align = if ctx.align == 8 { 8 } else { 4 };
+    let mut properties = Vec::new();
+    let mut pos = 0;
+
+    while pos + 8 <= desc.len() {
+        let (pr_type, datasz) = match (ctx.read_u32(desc, pos), ctx.read_u32(desc, pos + 4)) {
+            (Some(t), Some(s)) => (t, s as usize),
+            _ => break,
+        };
+        let data_start = pos + 8;
+        let value = ctx.read_u32(desc, data_start).unwrap_or(0);
+        let is_aarch64 = ctx.machine == "AArch64";
+
+        let property = match pr_type {
+            GNU_PROPERTY_STACK_SIZE => {
+                let size = if datasz == 8 {
+                    ctx.read_u64(desc, data_start).unwrap_or(0)
+                } else {
+                    value as u64
+                };
+                format!("stack size: 0x{:x}", size)
+            }
+            GNU_PROPERTY_NO_COPY_ON_PROTECTED => "no copy on protected".to_string(),
+            GNU_PROPERTY_AARCH64_FEATURE_1_AND if is_aarch64 => {
+                format!("AArch64 feature: {}", flag_names(value, &[(1, "BTI"), (2, "PAC"), (4, "GCS")]))
+            }
+            GNU_PROPERTY_X86_FEATURE_1_AND if !is_aarch64 => {
+                format!("x86 feature: {}", flag_names(value, &[(1, "IBT"), (2, "SHSTK")]))
+            }
+            GNU_PROPERTY_X86_ISA_1_NEEDED if !is_aarch64 => format!(
+                "x86 ISA needed: {}",
+                flag_names(
+                    value,
+                    &[(1, "x86-64-baseline"), (2, "x86-64-v2"), (4, "x86-64-v3"), (8, "x86-64-v4")]
+                )
+            ),
+            _ => format!("type 0x{:x}: {}", pr_type, hex_string(desc.get(data_start..data_start + datasz).unwrap_or(&[]))),
+        };
+        properties.push(property);
+
+        pos = (data_start + datasz).next_multiple_of(align);
+    }
+
+    properties
+}
+
+fn flag_names(value: u32, names: &[(u32, &str)]) -> String {
+    let set: Vec<&str> = names
+        .iter()
+        .filter(|(bit, _)| value & bit != 0)
+        .map(|(_, name)| *name)
+        .collect();
+    if set.is_empty() {
+        format!("0x{:x}", value)
+    } else {
+        set.join(", ")
+    }
+}
+
+fn c_string(data: &[u8]) -> String {
+    let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).to_string()
+}
+
+fn hex_string(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X86_64: NoteContext = NoteContext {
+        little_endian: true,
+        align: 8,
+        machine: "Advanced Micro Devices X86-64",
+    };
+
+    fn note(owner: &str, note_type: u32, desc: &[u8], little_endian: bool, align: usize) -> Vec<u8> {
+        let word = |v: u32| if little_endian { v.to_le_bytes() } else { v.to_be_bytes() };
+        let mut name = owner.as_bytes().to_vec();
+        name.push(0);
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&word(name.len() as u32));
+        bytes.extend_from_slice(&word(desc.len() as u32));
+        bytes.extend_from_slice(&word(note_type));
+        bytes.extend_from_slice(&name);
+        bytes.resize(bytes.len().next_multiple_of(4), 0);
+        bytes.extend_from_slice(desc);
+        bytes.resize(bytes.len().next_multiple_of(align), 0);
+        bytes
+    }
+
+    fn words(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn decodes_build_id_and_abi_tag() {
+        let mut data = note("GNU", NT_GNU_ABI_TAG, &words(&[0, 3, 2, 0]), true, 4);
+        data.extend(note("GNU", NT_GNU_BUILD_ID, &[0xde, 0xad, 0xbe, 0xef, 0x01], true, 4));
+        let ctx = NoteContext { align: 4, ..X86_64 };
+        let mut summary = NoteSummary::default();
+        parse_note_section(".note.gnu.build-id", &data, ctx, &mut summary);
+
+        assert_eq!(summary.notes.len(), 2);
+        assert_eq!(summary.notes[0].type_name, "NT_GNU_ABI_TAG");
+        assert_eq!(summary.notes[0].description, "OS: Linux, ABI: 3.2.0");
+        let tag = summary.abi_tag.unwrap();
+        assert_eq!((tag.os.as_str(), tag.kernel_version.as_str()), ("Linux", "3.2.0"));
+        assert_eq!(summary.build_id.as_deref(), Some("deadbeef01"));
+        assert_eq!(summary.notes[1].owner, "GNU");
+    }
+
+    #[test]
+    fn decodes_gnu_properties_with_eight_byte_alignment() {
+        let mut desc = words(&[GNU_PROPERTY_X86_FEATURE_1_AND, 4, 3, 0]);
+        desc.extend(words(&[GNU_PROPERTY_X86_ISA_1_NEEDED, 4, 1, 0]));
+        let data = note("GNU", NT_GNU_PROPERTY_TYPE_0, &desc, true, 8);
+        let mut summary = NoteSummary::default();
+        parse_note_section(".note.gnu.property", &data, X86_64, &mut summary);
+
+        assert_eq!(
+            summary.notes[0].description,
+            "x86 feature: IBT, SHSTK; x86 ISA needed: x86-64-baseline"
+        );
+    }
+
+    #[test]
+    fn reads_big_endian_go_and_package_notes() {
+        let mut data = note("Go", NT_GO_BUILD_ID, b"abc/def\0", false, 4);
+        data.extend(note(
+            "FDO",
+            NT_FDO_PACKAGING_METADATA,
+            br#"{"type":"deb","name":"hello","version":"1.0-1"}"#,
+            false,
+            4,
+        ));
+        let ctx = NoteContext {
+            little_endian: false,
+            align: 4,
+            machine: "PowerPC64",
+        };
+        let mut summary = NoteSummary::default();
+        parse_note_section(".note", &data, ctx, &mut summary);
+
+        assert_eq!(summary.go_build_id.as_deref(), Some("abc/def"));
+        let package = summary.package_note.unwrap();
+        assert_eq!(package.package_type.as_deref(), Some("deb"));
+        assert_eq!(package.name.as_deref(), Some("hello"));
+        assert_eq!(package.version.as_deref(), Some("1.0-1"));
+    }
+
+    #[test]
+    fn stops_at_a_truncated_note() {
+        let mut data = note("GNU", NT_GNU_BUILD_ID, &[1, 2, 3, 4], true, 4);
+        let mut truncated = note("GNU", NT_GNU_BUILD_ID, &[5; 20], true, 4);
+        truncated.truncate(24);
+        data.extend(truncated);
+        let mut summary = NoteSummary::default();
+        parse_note_section(".note.gnu.build-id", &data, NoteContext { align: 4, ..X86_64 }, &mut summary);
+
+        assert_eq!(summary.notes.len(), 1);
+        assert_eq!(summary.build_id.as_deref(), Some("01020304"));
+    }
+}
@@ -0,0 +1,180 @@
+use flate2::read::ZlibDecoder;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+/// Magic at the start of the `.go.buildinfo` section
+const GO_BUILDINFO_MAGIC: &[u8] = b"\xff Go buildinf:";
+/// Set when version and module info are stored inline (Go 1.18+)
+const GO_FLAG_VERSION_INLINE: u8 = 0x2;
+
+/// Build information embedded by the Go toolchain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoBuildInfo {
+    pub go_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main: Option<GoModule>,
+    #[serde(default)]
+    pub deps: Vec<GoModule>,
+    #[serde(default)]
+    pub settings: Vec<GoBuildSetting>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoModule {
+    pub path: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sum: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replace: Option<Box<GoModule>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoBuildSetting {
+    pub key: String,
+    pub value: String,
+}
+
+/// A crate recorded by cargo-auditable in the `.dep-v0` section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustPackage {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub source: String,
+    /// "build" for build-time only dependencies, "runtime" otherwise
+    #[serde(default = "default_dependency_kind")]
+    pub kind: String,
+    #[serde(default)]
+    pub root: bool,
+}
+
+fn default_dependency_kind() -> String {
+    "runtime".to_string()
+}
+
+#[derive(Deserialize)]
+struct AuditableDependencies {
+    packages: Vec<RustPackage>,
+}
+
+/// Decode `.go.buildinfo`
+///
+/// Only the inline layout written since Go 1.18 is supported; older
+/// binaries store pointers into the data segment instead.
+pub fn parse_go_buildinfo(data: &[u8]) -> Option<GoBuildInfo> {
+    if !data.starts_with(GO_BUILDINFO_MAGIC) || data.len() < 32 {
+        return None;
+    }
+    if data[15] & GO_FLAG_VERSION_INLINE == 0 {
+        return None;
+    }
+
+    let mut pos = 32;
+    let go_version = String::from_utf8_lossy(read_varint_bytes(data, &mut pos)?).to_string();
+    // The sentinels are not UTF-8, so strip them before decoding
+    let modinfo = read_varint_bytes(data, &mut pos).map(strip_modinfo_sentinels).unwrap_or_default();
+
+    let mut info = GoBuildInfo {
+        go_version,
+        ..Default::default()
+    };
+    parse_go_modinfo(&String::from_utf8_lossy(modinfo), &mut info);
+    Some(info)
+}
+
+/// Read a uvarint length followed by that many bytes
+fn read_varint_bytes<'a>(data: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+    let mut len: usize = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        len |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        if shift > 63 {
+            return None;
+        }
+    }
+    // The length is untrusted and may overflow
+    let end = pos.checked_add(len)?;
+    let bytes = data.get(*pos..end)?;
+    *pos = end;
+    Some(bytes)
+}
+
+/// The module info is wrapped in 16-byte sentinels on both sides
+fn strip_modinfo_sentinels(modinfo: &[u8]) -> &[u8] {
+    if modinfo.len() >= 33 && modinfo[modinfo.len() - 17] == b'\n' {
+        &modinfo[16..modinfo.len() - 16]
+    } else {
+        modinfo
+    }
+}
+
+fn parse_go_modinfo(modinfo: &str, info: &mut GoBuildInfo) {
+    for line in modinfo.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["path", path, ..] => info.path = Some(path.to_string()),
+            ["mod", rest @ ..] => info.main = parse_go_module(rest),
+            ["dep", rest @ ..] => {
+                if let Some(module) = parse_go_module(rest) {
+                    info.deps.push(module);
+                }
+            }
+            // A replacement applies to the module on the previous line
+            ["=>", rest @ ..] => {
+                let replacement = parse_go_module(rest).map(Box::new);
+                if let Some(last) = info.deps.last_mut() {
+                    last.replace = replacement;
+                } else if let Some(main) = info.main.as_mut() {
+                    main.replace = replacement;
+                }
+            }
+            ["build", setting, ..] => {
+                if let Some((key, value)) = setting.split_once('=') {
+                    info.settings.push(GoBuildSetting {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn parse_go_module(fields: &[&str]) -> Option<GoModule> {
+    let path = fields.first()?;
+    Some(GoModule {
+        path: path.to_string(),
+        version: fields.get(1).unwrap_or(&"").to_string(),
+        sum: fields
+            .get(2)
+            .filter(|sum| !sum.is_empty())
+            .map(|sum| sum.to_string()),
+        replace: None,
+    })
+}
+
+/// Decode the zlib-compressed JSON that cargo-auditable stores in `.dep-v0`
+pub fn parse_auditable_deps(data: &[u8]) -> Option<Vec<RustPackage>> {
+    let mut json = String::new();
+    ZlibDecoder::new(data)
+        // The format caps the decompressed size at 8 MiB
+        .take(8 * 1024 * 1024)
+        .read_to_string(&mut json)
+        .ok()?;
+    let deps: AuditableDependencies = serde_json::from_str(&json).ok()?;
+    Some(deps.packages)
+}
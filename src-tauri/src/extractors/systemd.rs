@@ -0,0 +1,365 @@
+use crate::utils::issue::Issue;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Unit directories, relative to the package root, with their scope
+const UNIT_DIRS: &[(&str, UnitScope)] = &[
+    ("lib/systemd/system/", UnitScope::System),
+    ("usr/lib/systemd/system/", UnitScope::System),
+    ("etc/systemd/system/", UnitScope::System),
+    ("lib/systemd/user/", UnitScope::User),
+    ("usr/lib/systemd/user/", UnitScope::User),
+    ("etc/systemd/user/", UnitScope::User),
+];
+
+const UNIT_TYPES: &[&str] = &["service", "socket", "timer", "path", "mount"];
+
+/// Keys that start a process, in the order systemd runs them
+pub const EXEC_KEYS: &[&str] = &[
+    "ExecCondition", "ExecStartPre", "ExecStart", "ExecStartPost", "ExecReload", "ExecStop", "ExecStopPost",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum UnitScope {
+    System,
+    User,
+}
+
+/// A parsed systemd unit file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemdUnit {
+    pub name: String,
+    pub unit_type: String,
+    pub scope: UnitScope,
+    pub sections: Vec<UnitSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub documentation: Vec<String>,
+    pub exec: Vec<ExecCommand>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    pub dynamic_user: bool,
+    /// Sandboxing and hardening directives that are set
+    pub sandboxing: Vec<UnitSetting>,
+    pub wanted_by: Vec<String>,
+    pub required_by: Vec<String>,
+    pub also: Vec<String>,
+    pub alias: Vec<String>,
+    /// Socket Listen*, timer On*, path Path* and mount What/Where settings
+    pub triggers: Vec<UnitSetting>,
+    /// `<unit>.d/*.conf` drop-ins merged into the settings, in the order systemd applies them
+    pub drop_ins: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exposure: Option<ExposureScore>,
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitSection {
+    pub name: String,
+    pub entries: Vec<UnitSetting>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitSetting {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecCommand {
+    /// ExecStart, ExecStartPre, ...
+    pub kind: String,
+    pub command: String,
+    /// Special executable prefixes such as `-`, `+` or `!`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub prefixes: String,
+    pub program: String,
+    /// Where the program was found in the package
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub found: bool,
+    /// Essential package or dependency that ships the program when the package does not
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provided_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExposureScore {
+    /// 0.0 (fully locked down) to 10.0 (fully exposed)
+    pub score: f64,
+    pub level: String,
+    pub checks: Vec<ExposureCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExposureCheck {
+    pub setting: String,
+    pub description: String,
+    pub weight: u32,
+    /// 0 (good) to 10 (bad)
+    pub badness: u32,
+}
+
+impl SystemdUnit {
+    /// All values of a key in a section, honouring empty assignments that reset lists
+    pub fn values(&self, section: &str, key: &str) -> Vec<String> {
+        let mut values = Vec::new();
+        for s in self.sections.iter().filter(|s| s.name == section) {
+            for entry in s.entries.iter().filter(|e| e.key == key) {
+                if entry.value.is_empty() {
+                    values.clear();
+                } else {
+                    values.push(entry.value.clone());
+                }
+            }
+        }
+        values
+    }
+
+    /// Last assignment of a key in a section
+    pub fn value(&self, section: &str, key: &str) -> Option<String> {
+        self.values(section, key).pop()
+    }
+
+    fn words(&self, section: &str, key: &str) -> Vec<String> {
+        self.values(section, key)
+            .iter()
+            .flat_map(|v| v.split_whitespace().map(|w| w.to_string()).collect::<Vec<_>>())
+            .collect()
+    }
+}
+
+/// Unit name and scope if the path is a unit file in a systemd unit directory
+pub fn unit_location(path: &str) -> Option<(&str, UnitScope)> {
+    let (name, scope) = UNIT_DIRS
+        .iter()
+        .find_map(|(dir, scope)| path.strip_prefix(dir).map(|name| (name, *scope)))?;
+    if name.contains('/') {
+        return None;
+    }
+    let extension = name.rsplit_once('.')?.1;
+    UNIT_TYPES.contains(&extension).then_some((name, scope))
+}
+
+/// Unit name and scope if the path is a `<unit>.d/*.conf` drop-in in a systemd unit directory
+pub fn drop_in_location(path: &str) -> Option<(&str, UnitScope)> {
+    let (rest, scope) = UNIT_DIRS
+        .iter()
+        .find_map(|(dir, scope)| path.strip_prefix(dir).map(|rest| (rest, *scope)))?;
+    let (dir, file) = rest.split_once('/')?;
+    if file.contains('/') || !file.ends_with(".conf") {
+        return None;
+    }
+    let name = dir.strip_suffix(".d")?;
+    let extension = name.rsplit_once('.')?.1;
+    UNIT_TYPES.contains(&extension).then_some((name, scope))
+}
+
+pub fn parse_boolean(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "yes" | "true" | "on" => Some(true),
+        "0" | "no" | "false" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse a unit file together with its drop-ins, which must already be in file name order
+pub fn analyze_unit(file_path: &str, drop_ins: &[PathBuf], name: &str, scope: UnitScope) -> Result<SystemdUnit, String> {
+    let content = fs::read_to_string(file_path).map_err(|e| format!("Failed to read unit file: {}", e))?;
+    let drop_ins: Vec<String> = drop_ins
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect();
+    Ok(parse_unit(&content, &drop_ins, name, scope))
+}
+
+pub fn parse_unit(content: &str, drop_ins: &[String], name: &str, scope: UnitScope) -> SystemdUnit {
+    // Drop-in sections follow the unit's own, so their assignments win
+    let mut sections = parse_sections(content);
+    for drop_in in drop_ins {
+        sections.extend(parse_sections(drop_in));
+    }
+    let unit_type = name.rsplit_once('.').map(|(_, t)| t).unwrap_or_default().to_string();
+
+    let mut unit = SystemdUnit {
+        name: name.to_string(),
+        unit_type: unit_type.clone(),
+        scope,
+        sections,
+        description: None,
+        documentation: Vec::new(),
+        exec: Vec::new(),
+        user: None,
+        group: None,
+        dynamic_user: false,
+        sandboxing: Vec::new(),
+        wanted_by: Vec::new(),
+        required_by: Vec::new(),
+        also: Vec::new(),
+        alias: Vec::new(),
+        triggers: Vec::new(),
+        drop_ins: Vec::new(),
+        exposure: None,
+        issues: Vec::new(),
+    };
+
+    unit.description = unit.value("Unit", "Description");
+    unit.documentation = unit.words("Unit", "Documentation");
+    unit.wanted_by = unit.words("Install", "WantedBy");
+    unit.required_by = unit.words("Install", "RequiredBy");
+    unit.also = unit.words("Install", "Also");
+    unit.alias = unit.words("Install", "Alias");
+
+    // Exec and sandboxing settings live in the type-specific section
+    let section = match unit_type.as_str() {
+        "service" => "Service",
+        "socket" => "Socket",
+        "mount" => "Mount",
+        _ => "",
+    };
+    if !section.is_empty() {
+        for kind in EXEC_KEYS {
+            for command in unit.values(section, kind) {
+                let (prefixes, program) = split_exec_command(&command);
+                unit.exec.push(ExecCommand {
+                    kind: kind.to_string(),
+                    command: command.clone(),
+                    prefixes,
+                    program,
+                    path: None,
+                    found: false,
+                    provided_by: None,
+                });
+            }
+        }
+        unit.user = unit.value(section, "User");
+        unit.group = unit.value(section, "Group");
+        unit.dynamic_user = unit
+            .value(section, "DynamicUser")
+            .and_then(|v| parse_boolean(&v))
+            .unwrap_or(false);
+        unit.sandboxing = unit
+            .sections
+            .iter()
+            .filter(|s| s.name == section)
+            .flat_map(|s| s.entries.iter())
+            .filter(|e| is_sandboxing_key(&e.key))
+            .cloned()
+            .collect();
+    }
+
+    let (trigger_section, trigger_prefixes): (&str, &[&str]) = match unit_type.as_str() {
+        "socket" => ("Socket", &["Listen"]),
+        "timer" => ("Timer", &["On", "Unit"]),
+        "path" => ("Path", &["Path", "DirectoryNotEmpty", "Unit"]),
+        "mount" => ("Mount", &["What", "Where", "Type"]),
+        _ => ("", &[]),
+    };
+    unit.triggers = unit
+        .sections
+        .iter()
+        .filter(|s| s.name == trigger_section)
+        .flat_map(|s| s.entries.iter())
+        .filter(|e| trigger_prefixes.iter().any(|p| e.key.starts_with(p)))
+        .cloned()
+        .collect();
+
+    unit
+}
+
+fn is_sandboxing_key(key: &str) -> bool {
+    key.starts_with("Protect")
+        || key.starts_with("Private")
+        || key.starts_with("Restrict")
+        || matches!(
+            key,
+            "NoNewPrivileges"
+                | "DynamicUser"
+                | "CapabilityBoundingSet"
+                | "AmbientCapabilities"
+                | "SystemCallFilter"
+                | "SystemCallArchitectures"
+                | "LockPersonality"
+                | "MemoryDenyWriteExecute"
+                | "DevicePolicy"
+                | "DeviceAllow"
+                | "IPAddressDeny"
+                | "IPAddressAllow"
+                | "UMask"
+                | "ProcSubset"
+                | "ReadOnlyPaths"
+                | "ReadWritePaths"
+                | "InaccessiblePaths"
+                | "RemoveIPC"
+                | "KeyringMode"
+                | "SupplementaryGroups"
+        )
+}
+
+/// Split `-+!/usr/bin/foo args` into its prefixes and program
+fn split_exec_command(command: &str) -> (String, String) {
+    let command = command.trim();
+    let program_start = command
+        .find(|c: char| !matches!(c, '@' | '-' | ':' | '+' | '!' | '|'))
+        .unwrap_or(command.len());
+    let prefixes = command[..program_start].to_string();
+    let program = command[program_start..]
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_matches(['"', '\''])
+        .to_string();
+    (prefixes, program)
+}
+
+/// INI-style sections with `\` line continuation and `#`/`;` comments
+fn parse_sections(content: &str) -> Vec<UnitSection> {
+    let mut sections: Vec<UnitSection> = Vec::new();
+    let mut pending = String::new();
+
+    for raw in content.lines() {
+        let line = raw.trim();
+        if pending.is_empty() && (line.is_empty() || line.starts_with('#') || line.starts_with(';')) {
+            continue;
+        }
+        if let Some(continued) = line.strip_suffix('\\') {
+            pending.push_str(continued);
+            pending.push(' ');
+            continue;
+        }
+        let line = if pending.is_empty() {
+            line.to_string()
+        } else {
+            let joined = format!("{}{}", pending, line);
+            pending.clear();
+            joined
+        };
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push(UnitSection {
+                name: name.to_string(),
+                entries: Vec::new(),
+            });
+        } else if let Some((key, value)) = line.split_once('=') {
+            if let Some(section) = sections.last_mut() {
+                section.entries.push(UnitSetting {
+                    key: key.trim().to_string(),
+                    value: value.trim().to_string(),
+                });
+            }
+        }
+    }
+
+    sections
+}
@@ -7,11 +7,13 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             commands::parse::parse_deb_package,
+            commands::sbom::export_sbom,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+mod analyzers;
 mod commands;
 mod extractors;
 mod utils;
@@ -0,0 +1,37 @@
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Hex content hashes of a file
+pub struct FileHashes {
+    pub sha1: String,
+    pub sha256: String,
+}
+
+/// SHA-1 and SHA-256 of a whole file, computed in a single streamed read
+pub fn file_hashes(path: &Path) -> Option<FileHashes> {
+    let mut file = File::open(path).ok()?;
+    let mut sha1 = Sha1::new();
+    let mut sha256 = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        sha1.update(&buffer[..read]);
+        sha256.update(&buffer[..read]);
+    }
+
+    Some(FileHashes {
+        sha1: hex(&sha1.finalize()),
+        sha256: hex(&sha256.finalize()),
+    })
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,50 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Shannon entropy in bits per byte (0.0 - 8.0)
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    let mut counts = [0u64; 256];
+    for &byte in data {
+        counts[byte as usize] += 1;
+    }
+    entropy_from_counts(&counts, data.len() as u64)
+}
+
+/// Entropy of a whole file, streamed in chunks
+pub fn file_entropy(path: &Path) -> Option<f64> {
+    let mut file = File::open(path).ok()?;
+    let mut counts = [0u64; 256];
+    let mut total = 0u64;
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        for &byte in &buffer[..read] {
+            counts[byte as usize] += 1;
+        }
+        total += read as u64;
+    }
+
+    if total == 0 {
+        return None;
+    }
+    Some(entropy_from_counts(&counts, total))
+}
+
+fn entropy_from_counts(counts: &[u64; 256], total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
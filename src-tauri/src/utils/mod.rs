@@ -1 +1,6 @@
+pub mod checksum;
+pub mod entropy;
+pub mod issue;
 pub mod shell;
+pub mod shell_words;
+pub mod xml;
@@ -0,0 +1,110 @@
+/// Words that may precede a command within one shell command list
+const SHELL_KEYWORDS: &[&str] = &["if", "then", "else", "elif", "do", "!", "command", "exec", "{", "("];
+
+/// Split a line into words with shell quoting, keeping `;`, `&&`, `||`, `|` and `&` as words
+pub fn shell_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    current.push(c);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"' | '\\' | '$' | '`')) => {
+                            current.extend(chars.next());
+                        }
+                        _ => current.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                current.extend(chars.next());
+            }
+            '#' if !in_word => break,
+            // `2>&1` and `>&2` are redirections, not background operators
+            '&' if current.ends_with(['>', '<']) => current.push(c),
+            ';' | '&' | '|' => {
+                finish(&mut current, &mut in_word, &mut words);
+                let mut operator = c.to_string();
+                if (c == '&' || c == '|') && chars.peek() == Some(&c) {
+                    operator.extend(chars.next());
+                }
+                words.push(operator);
+            }
+            c if c.is_whitespace() => finish(&mut current, &mut in_word, &mut words),
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    finish(&mut current, &mut in_word, &mut words);
+
+    words
+}
+
+fn finish(current: &mut String, in_word: &mut bool, words: &mut Vec<String>) {
+    if *in_word {
+        words.push(std::mem::take(current));
+        *in_word = false;
+    }
+}
+
+pub fn is_operator(word: &str) -> bool {
+    matches!(word, ";" | "&&" | "||" | "|" | "&")
+}
+
+/// Index of one of `commands` in a simple command, after keywords and assignments
+pub fn command_start(segment: &[String], commands: &[&str]) -> Option<usize> {
+    for (index, word) in segment.iter().enumerate() {
+        let name = word.rsplit('/').next().unwrap_or_default();
+        if commands.contains(&name) {
+            return Some(index);
+        }
+        let is_assignment = word.split_once('=').is_some_and(|(k, _)| {
+            !k.is_empty() && k.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        if !SHELL_KEYWORDS.contains(&word.as_str()) && !is_assignment {
+            return None;
+        }
+    }
+    None
+}
+
+/// Non-comment script lines with their 1-based line numbers, joining `\` continuations
+pub fn script_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut iter = content.lines().enumerate();
+
+    while let Some((index, raw)) = iter.next() {
+        let mut line = raw.trim().to_string();
+        while line.ends_with('\\') {
+            line.pop();
+            let Some((_, next)) = iter.next() else {
+                break;
+            };
+            line.push(' ');
+            line.push_str(next.trim());
+        }
+        if !line.is_empty() && !line.starts_with('#') {
+            lines.push((index + 1, line));
+        }
+    }
+
+    lines
+}
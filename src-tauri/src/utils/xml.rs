@@ -0,0 +1,122 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// Minimal element tree for the small XML documents packages ship
+pub struct XmlElement {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlElement>,
+    /// Direct text content
+    pub text: String,
+    /// Text including inline children, in document order
+    inline_text: String,
+}
+
+fn append_text(target: &mut String, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if !target.is_empty() {
+        target.push(' ');
+    }
+    target.push_str(text);
+}
+
+impl XmlElement {
+    fn from_start(start: &BytesStart) -> XmlElement {
+        let attributes = start
+            .attributes()
+            .flatten()
+            .map(|a| {
+                let key = String::from_utf8_lossy(a.key.as_ref()).to_string();
+                let value = a.unescape_value().map(|v| v.to_string()).unwrap_or_default();
+                (key, value)
+            })
+            .collect();
+        XmlElement {
+            name: String::from_utf8_lossy(start.name().as_ref()).to_string(),
+            attributes,
+            children: Vec::new(),
+            text: String::new(),
+            inline_text: String::new(),
+        }
+    }
+
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+
+    pub fn lang(&self) -> Option<&str> {
+        self.attr("xml:lang")
+    }
+
+    /// First untranslated child with this name
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|c| c.name == name && c.lang().is_none())
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// Text of this element and its inline children
+    pub fn full_text(&self) -> String {
+        self.inline_text.trim().to_string()
+    }
+
+    pub fn child_text(&self, name: &str) -> Option<String> {
+        self.child(name).map(|c| c.full_text()).filter(|t| !t.is_empty())
+    }
+}
+
+/// Parse a whole document into its root element
+pub fn parse_xml(content: &str) -> Result<XmlElement, String> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+    let mut stack: Vec<XmlElement> = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(start)) => stack.push(XmlElement::from_start(&start)),
+            Ok(Event::Empty(start)) => {
+                let element = XmlElement::from_start(&start);
+                match stack.last_mut() {
+                    Some(parent) => {
+                        append_text(&mut parent.inline_text, &element.inline_text);
+                        parent.children.push(element);
+                    }
+                    None => return Ok(element),
+                }
+            }
+            Ok(Event::End(_)) => {
+                let element = stack.pop().ok_or("Unbalanced closing tag")?;
+                match stack.last_mut() {
+                    Some(parent) => {
+                        append_text(&mut parent.inline_text, &element.inline_text);
+                        parent.children.push(element);
+                    }
+                    None => return Ok(element),
+                }
+            }
+            Ok(Event::Text(text)) => {
+                if let Some(current) = stack.last_mut() {
+                    let text = text.unescape().map_err(|e| format!("Invalid text: {}", e))?;
+                    append_text(&mut current.text, &text);
+                    append_text(&mut current.inline_text, &text);
+                }
+            }
+            Ok(Event::CData(data)) => {
+                if let Some(current) = stack.last_mut() {
+                    let data = String::from_utf8_lossy(&data);
+                    append_text(&mut current.text, &data);
+                    append_text(&mut current.inline_text, &data);
+                }
+            }
+            Ok(Event::Eof) => return Err("Unexpected end of document".to_string()),
+            Ok(_) => {}
+            Err(e) => {
+                return Err(format!("XML error at position {}: {}", reader.error_position(), e));
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 import { useState, useCallback, useEffect } from 'react';
-import { Upload, FileText, X, Loader2 } from 'lucide-react';
+import { Upload, FileText, X, Loader2, FolderOpen } from 'lucide-react';
 import { useLocale, useI18n } from '@/lib/i18n';
 import { native } from '@/lib/platform';
 import type { ParseResult } from '@/types';
@@ -19,6 +19,8 @@ export default function FileUpload({ onComplete, onError, isLoading, onLoadingCh
   const [selectedFileSize, setSelectedFileSize] = useState<number>(0);
   const [dialogOpen, setDialogOpen] = useState<any>(null);
   const [isDragging, setIsDragging] = useState(false);
+  const [yaraRulesDir, setYaraRulesDir] = useState<string | null>(null);
+  const [debugPackagePath, setDebugPackagePath] = useState<string | null>(null);
 
   useEffect(() => {
     import('@tauri-apps/plugin-dialog').then(module => {
@@ -38,7 +40,7 @@ export default function FileUpload({ onComplete, onError, isLoading, onLoadingCh
         multiple: false,
         filters: [{
           name: 'Debian Packages',
-          extensions: ['deb', 'udeb']
+          extensions: ['deb', 'udeb', 'ddeb']
         }]
       });
 
@@ -54,12 +56,50 @@ export default function FileUpload({ onComplete, onError, isLoading, onLoadingCh
     }
   }, [dialogOpen, onError, t]);
 
+  const handleDebugPackageSelect = useCallback(async () => {
+    if (!dialogOpen) {
+      onError('Dialog plugin not available');
+      return;
+    }
+    try {
+      const selected = await dialogOpen({
+        multiple: false,
+        filters: [{
+          name: 'Debug Packages',
+          extensions: ['deb', 'ddeb']
+        }]
+      });
+      if (selected && typeof selected === 'string') {
+        setDebugPackagePath(selected);
+      }
+    } catch (err) {
+      console.error('File selection error:', err);
+      onError(t('errors.uploadFailed'));
+    }
+  }, [dialogOpen, onError, t]);
+
+  const handleYaraRulesSelect = useCallback(async () => {
+    if (!dialogOpen) {
+      onError('Dialog plugin not available');
+      return;
+    }
+    try {
+      const selected = await dialogOpen({ directory: true, multiple: false });
+      if (selected && typeof selected === 'string') {
+        setYaraRulesDir(selected);
+      }
+    } catch (err) {
+      console.error('Folder selection error:', err);
+      onError(t('errors.uploadFailed'));
+    }
+  }, [dialogOpen, onError, t]);
+
   const handleUpload = async () => {
     if (!selectedFilePath) return;
     onLoadingChange(true);
 
     try {
-      const result = await native.parseDebPackage(selectedFilePath);
+      const result = await native.parseDebPackage(selectedFilePath, debugPackagePath ?? undefined, yaraRulesDir ?? undefined);
       onComplete(result);
     } catch (error: any) {
       console.error('解析失败:', error);
@@ -154,6 +194,32 @@ export default function FileUpload({ onComplete, onError, isLoading, onLoadingCh
               </button>
             </div>
 
+            {/* Scan Options */}
+            <div className="mb-6 space-y-2">
+              <OptionRow
+                label={t('upload.debugPackage')}
+                value={debugPackagePath?.split('/').pop()}
+                title={debugPackagePath ?? undefined}
+                placeholder={t('upload.debugPackageAuto')}
+                buttonLabel={t('upload.chooseFile')}
+                icon={<FileText className="w-4 h-4" />}
+                disabled={isLoading}
+                onSelect={handleDebugPackageSelect}
+                onClear={() => setDebugPackagePath(null)}
+              />
+              <OptionRow
+                label={t('upload.yaraRules')}
+                value={yaraRulesDir ?? undefined}
+                title={yaraRulesDir ?? undefined}
+                placeholder={t('upload.yaraRulesNone')}
+                buttonLabel={t('upload.chooseFolder')}
+                icon={<FolderOpen className="w-4 h-4" />}
+                disabled={isLoading}
+                onSelect={handleYaraRulesSelect}
+                onClear={() => setYaraRulesDir(null)}
+              />
+            </div>
+
             {/* Loading State */}
             {isLoading ? (
               <div className="space-y-6">
@@ -185,3 +251,42 @@ export default function FileUpload({ onComplete, onError, isLoading, onLoadingCh
     </div>
   );
 }
+
+// Optional input shown below the selected package
+function OptionRow({ label, value, title, placeholder, buttonLabel, icon, disabled, onSelect, onClear }: {
+  label: string;
+  value?: string;
+  title?: string;
+  placeholder: string;
+  buttonLabel: string;
+  icon: React.ReactNode;
+  disabled: boolean;
+  onSelect: () => void;
+  onClear: () => void;
+}) {
+  return (
+    <div className="flex items-center gap-3 rounded-xl bg-gray-50 dark:bg-slate-900/50 px-4 py-3">
+      <span className="text-sm text-gray-500 dark:text-slate-400 whitespace-nowrap">{label}</span>
+      <span className="flex-1 min-w-0 truncate text-sm font-mono text-gray-700 dark:text-slate-300" title={title}>
+        {value ?? placeholder}
+      </span>
+      {value && (
+        <button
+          onClick={onClear}
+          disabled={disabled}
+          className="p-1.5 hover:bg-gray-200 dark:hover:bg-slate-700 rounded-lg transition-colors disabled:opacity-50"
+        >
+          <X className="w-4 h-4 text-gray-400 dark:text-slate-400" />
+        </button>
+      )}
+      <button
+        onClick={onSelect}
+        disabled={disabled}
+        className="flex items-center gap-2 px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-slate-600 text-gray-700 dark:text-slate-300 hover:bg-gray-100 dark:hover:bg-slate-700 transition-colors disabled:opacity-50"
+      >
+        {icon}
+        {buttonLabel}
+      </button>
+    </div>
+  );
+}
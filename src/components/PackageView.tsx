@@ -1,7 +1,8 @@
 import { useState, useMemo } from 'react';
 import { useLocale, useI18n } from '@/lib/i18n';
-import { ArrowLeft, Package, Clock, HardDrive, File, Cpu, Monitor, Search, Filter, ChevronDown, ChevronRight, FileText, Code2, Terminal, X } from 'lucide-react';
-import type { ParseResult, FileTypeFilter, ELFInfo, DesktopInfo } from '@/types';
+import { ArrowLeft, Package, Clock, HardDrive, File, Cpu, Monitor, Search, Filter, ChevronDown, ChevronRight, FileText, Code2, Terminal, X, AlertTriangle, Users, Download } from 'lucide-react';
+import { native } from '@/lib/platform';
+import type { ParseResult, SbomFormat, BundledComponent, AccountInventory, AlternativesInventory, ChangelogInfo, CopyrightInfo, ScriptCommandReport, FileTypeFilter, ELFInfo, KernelModuleInfo, ArchiveInfo, YaraMatch, YaraScan, DesktopInfo, LocalizedString, AppStreamComponent, SystemdUnit, IntegrationEntry, ShebangInfo } from '@/types';
 
 interface PackageViewProps {
   result: ParseResult;
@@ -27,6 +28,7 @@ export default function PackageView({ result, onNewUpload }: PackageViewProps) {
   const [fileSearch, setFileSearch] = useState('');
   const [selectedFile, setSelectedFile] = useState<FileNode | null>(null);
   const [expandedPaths, setExpandedPaths] = useState<Set<string>>(new Set());
+  const [sbomStatus, setSbomStatus] = useState<{ ok: boolean; message: string } | null>(null);
 
   // Build file tree
   const fileTree = useMemo(() => {
@@ -92,7 +94,7 @@ export default function PackageView({ result, onNewUpload }: PackageViewProps) {
   function backendTypeToFileType(backendType: string, isElf?: boolean, isDesktop?: boolean) {
     if (isElf) return 'elf';
     if (isDesktop) return 'desktop';
-    if (backendType === 'file') return 'file';
+    if (backendType === 'file' || backendType === 'archive') return 'file';
     if (backendType === 'directory') return 'directory';
     return null;
   }
@@ -142,6 +144,68 @@ export default function PackageView({ result, onNewUpload }: PackageViewProps) {
     });
   }, [visibleFiles, fileFilter, fileSearch]);
 
+  const flaggedFiles = useMemo(() => {
+    const flagged = new Map<string, string[]>();
+    (result.packerFindings ?? []).forEach(finding => {
+      flagged.set(finding.path, [...(flagged.get(finding.path) ?? []), finding.message]);
+    });
+    (result.secretFindings ?? []).forEach(finding => {
+      const location = finding.line !== undefined ? `:${finding.line}` : finding.offset !== undefined ? `@0x${finding.offset.toString(16)}` : '';
+      const message = `${finding.rule}${location}${finding.snippet ? ` ${finding.snippet}` : ''}`;
+      flagged.set(finding.path, [...(flagged.get(finding.path) ?? []), message]);
+    });
+    const inventory = result.certificateInventory;
+    [...(inventory?.certificates ?? []), ...(inventory?.keys ?? [])].forEach(entry => {
+      entry.issues.forEach(issue => {
+        flagged.set(entry.path, [...(flagged.get(entry.path) ?? []), issue.message]);
+      });
+    });
+    Object.entries(result.scriptInfo ?? {}).forEach(([path, info]) => {
+      info.issues.forEach(issue => {
+        flagged.set(path, [...(flagged.get(path) ?? []), issue.message]);
+      });
+    });
+    result.files.forEach(file => {
+      (file.yaraMatches ?? []).forEach(yaraMatch => {
+        flagged.set(file.path, [...(flagged.get(file.path) ?? []), `YARA: ${yaraMatch.rule}`]);
+      });
+    });
+    Object.entries(result.elfInfo ?? {}).forEach(([path, elf]) => {
+      if (elf.stripped && !elf.hasDebugInfo && !elf.debugFile) {
+        flagged.set(path, [...(flagged.get(path) ?? []), t('elfInfo.noDebugSymbols')]);
+      }
+      const searchPaths = [
+        ...(elf.rpath ?? []).map(entry => ({ tag: 'RPATH', entry })),
+        ...(elf.runpath ?? []).map(entry => ({ tag: 'RUNPATH', entry })),
+      ];
+      searchPaths.filter(({ entry }) => entry.insecure).forEach(({ tag, entry }) => {
+        const message = `${tag}: ${entry.path || '""'} (${t(`elfInfo.rpathKind.${entry.kind}`)})`;
+        flagged.set(path, [...(flagged.get(path) ?? []), message]);
+      });
+    });
+    return flagged;
+  }, [result.packerFindings, result.secretFindings, result.certificateInventory, result.scriptInfo, result.files, result.elfInfo, t]);
+
+  const selectedYaraMatches = selectedFile
+    ? result.files.find(file => file.path === selectedFile.path)?.yaraMatches
+    : undefined;
+
+  const handleExportSbom = async (format: SbomFormat) => {
+    try {
+      const { save } = await import('@tauri-apps/plugin-dialog');
+      const extension = format === 'spdx' ? 'spdx.json' : 'cdx.json';
+      const outputPath = await save({
+        defaultPath: `${result.metadata.Package}_${result.metadata.Version.replace(/^\d+:/, '')}_${result.metadata.Architecture}.${extension}`,
+        filters: [{ name: format === 'spdx' ? 'SPDX' : 'CycloneDX', extensions: ['json'] }],
+      });
+      if (!outputPath) return;
+      await native.exportSbom(result, format, outputPath);
+      setSbomStatus({ ok: true, message: `${t('sbom.saved')}: ${outputPath}` });
+    } catch (err) {
+      setSbomStatus({ ok: false, message: `${t('sbom.failed')}: ${err}` });
+    }
+  };
+
   const toggleFolder = (path: string) => {
     setExpandedPaths(prev => {
       const newSet = new Set(prev);
@@ -229,8 +293,26 @@ export default function PackageView({ result, onNewUpload }: PackageViewProps) {
                   ? ((1 - result.stats.originalSize / result.stats.extractedSize) * 100).toFixed(1)
                   : 0}%
               </span>
+              <span className="flex items-center gap-1">
+                <Download className="w-3.5 h-3.5 text-gray-500 dark:text-slate-400" />
+                <span className="text-gray-500 dark:text-slate-400">{t('sbom.export')}:</span>
+                {(['spdx', 'cyclonedx'] as SbomFormat[]).map(format => (
+                  <button
+                    key={format}
+                    onClick={() => handleExportSbom(format)}
+                    className="px-2 py-1 font-medium text-gray-700 dark:text-slate-300 hover:bg-gray-100 dark:hover:bg-slate-700 rounded-lg transition-colors"
+                  >
+                    {format === 'spdx' ? 'SPDX 2.3' : 'CycloneDX 1.5'}
+                  </button>
+                ))}
+              </span>
             </div>
           </div>
+          {sbomStatus && (
+            <div className={`mb-4 text-xs font-mono break-all ${sbomStatus.ok ? 'text-green-600 dark:text-green-400' : 'text-red-600 dark:text-red-400'}`}>
+              {sbomStatus.message}
+            </div>
+          )}
           <div className="flex items-center gap-5">
             <div className="w-16 h-16 bg-gradient-to-br from-blue-50 to-blue-100 dark:from-blue-900/30 dark:to-blue-800/30 rounded-2xl flex items-center justify-center shadow-lg shadow-blue-500/10">
               <Package className="w-8 h-8 text-blue-600 dark:text-blue-400" />
@@ -282,6 +364,7 @@ export default function PackageView({ result, onNewUpload }: PackageViewProps) {
             desktopCount={result.stats.desktopCount}
             toggleExpandAll={toggleExpandCollapseAll}
             expandButtonLabel={expandButtonLabel}
+            flaggedFiles={flaggedFiles}
           />
         )}
         {activeTab === 'scripts' && <ScriptsTab result={result} />}
@@ -297,19 +380,40 @@ export default function PackageView({ result, onNewUpload }: PackageViewProps) {
               <div className="flex items-center gap-3 min-w-0">
                 {getFileIcon(selectedFile, false)}
                 <span className="font-mono text-sm truncate text-gray-900 dark:text-white">{selectedFile.path}</span>
+                {result.copyright?.fileLicenses[selectedFile.path] && (
+                  <span className="px-2 py-0.5 rounded-lg text-xs font-mono bg-blue-500/10 text-blue-600 dark:text-blue-400 whitespace-nowrap" title={t('copyright.license')}>
+                    {result.copyright.fileLicenses[selectedFile.path]}
+                  </span>
+                )}
               </div>
               <button onClick={() => setSelectedFile(null)} className="p-2 hover:bg-gray-100 dark:hover:bg-slate-700 rounded-xl transition-colors">
                 <X className="w-5 h-5" />
               </button>
             </div>
             <div className="p-6 overflow-y-auto max-h-[calc(90vh-80px)]">
-              {selectedFile.isElf && result.elfInfo?.[selectedFile.path] && (
+              {result.elfInfo?.[selectedFile.path] && (
                 <ELFInfoPanel elfInfo={result.elfInfo[selectedFile.path] as ELFInfo} />
               )}
+              {result.archiveInfo?.[selectedFile.path] && (
+                <ArchivePanel archive={result.archiveInfo[selectedFile.path]} formatFileSize={formatFileSize} />
+              )}
               {selectedFile.isDesktop && result.desktopInfo?.[selectedFile.path] && (
                 <DesktopInfoPanel desktopInfo={result.desktopInfo[selectedFile.path] as DesktopInfo} />
               )}
-              {!selectedFile.isElf && !selectedFile.isDesktop && selectedFile.size < 1024 * 1024 && (
+              {result.appstreamInfo?.[selectedFile.path] && (
+                <AppStreamPanel component={result.appstreamInfo[selectedFile.path]} />
+              )}
+              {result.systemdUnits?.[selectedFile.path] && (
+                <SystemdUnitPanel unit={result.systemdUnits[selectedFile.path]} />
+              )}
+              {result.integrations?.[selectedFile.path] && (
+                <IntegrationPanel entry={result.integrations[selectedFile.path]} />
+              )}
+              {result.scriptInfo?.[selectedFile.path] && (
+                <ShebangPanel info={result.scriptInfo[selectedFile.path]} />
+              )}
+              {selectedYaraMatches && <YaraMatchesPanel matches={selectedYaraMatches} />}
+              {!selectedFile.isElf && !selectedFile.isDesktop && !result.archiveInfo?.[selectedFile.path] && !result.appstreamInfo?.[selectedFile.path] && !result.systemdUnits?.[selectedFile.path] && !result.integrations?.[selectedFile.path] && !result.scriptInfo?.[selectedFile.path] && !selectedYaraMatches && selectedFile.size < 1024 * 1024 && (
                 <div className="text-gray-600 dark:text-slate-400">
                   {t('common.fileSize')}: {formatFileSize(selectedFile.size)}
                   <p className="mt-4 text-gray-500 dark:text-slate-500">{t('common.filePreview')}</p>
@@ -354,7 +458,23 @@ function ELFInfoPanel({ elfInfo }: { elfInfo: ELFInfo }) {
         <InfoItem label={t('elfInfo.machine')} value={elfInfo.machine} />
         <InfoItem label={t('elfInfo.entry')} value={elfInfo.entry} />
         {elfInfo.interpreter && <InfoItem label={t('elfInfo.interpreter')} value={elfInfo.interpreter} />}
+        {elfInfo.buildId && <InfoItem label={t('elfInfo.buildId')} value={elfInfo.buildId} />}
+        {elfInfo.debuglink && <InfoItem label={t('elfInfo.debuglink')} value={`${elfInfo.debuglink.name} (crc ${elfInfo.debuglink.crc})`} />}
+        {elfInfo.debugFile && <InfoItem label={t('elfInfo.debugFile')} value={elfInfo.debugFile} />}
+        {elfInfo.abiTag && <InfoItem label={t('elfInfo.minKernel')} value={`${elfInfo.abiTag.os} ${elfInfo.abiTag.kernelVersion}`} />}
+        {elfInfo.packageNote && (
+          <InfoItem
+            label={t('elfInfo.packageNote')}
+            value={[elfInfo.packageNote.type, elfInfo.packageNote.name, elfInfo.packageNote.version, elfInfo.packageNote.os ?? elfInfo.packageNote.distro].filter(Boolean).join(' ')}
+          />
+        )}
       </div>
+      {elfInfo.stripped && !elfInfo.hasDebugInfo && !elfInfo.debugFile && (
+        <div className="rounded-xl bg-yellow-500/10 border border-yellow-500/20 p-4 text-sm text-yellow-600 dark:text-yellow-400">
+          {t('elfInfo.noDebugSymbols')}
+        </div>
+      )}
+      {elfInfo.kernelModule && <KernelModuleSection kmod={elfInfo.kernelModule} />}
       {elfInfo.dependencies && elfInfo.dependencies.length > 0 && (
         <div>
           <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('elfInfo.dynamicDeps')} ({elfInfo.dependencies.length})</h4>
@@ -365,6 +485,56 @@ function ELFInfoPanel({ elfInfo }: { elfInfo: ELFInfo }) {
           </div>
         </div>
       )}
+      {[
+        { label: 'RPATH', entries: elfInfo.rpath ?? [] },
+        { label: 'RUNPATH', entries: elfInfo.runpath ?? [] },
+      ].filter(({ entries }) => entries.length > 0).map(({ label, entries }) => (
+        <div key={label}>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{label} ({entries.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 max-h-48 overflow-y-auto">
+            {entries.map((entry, i) => (
+              <div key={i} className="flex items-center gap-2 text-xs font-mono text-gray-900 dark:text-slate-300 py-1">
+                <span className="break-all">{entry.path || '""'}</span>
+                <span
+                  className={`px-2 py-0.5 rounded-lg font-sans whitespace-nowrap ${
+                    entry.insecure
+                      ? 'bg-red-500/10 text-red-600 dark:text-red-400'
+                      : 'bg-gray-200 dark:bg-slate-800 text-gray-600 dark:text-slate-400'
+                  }`}
+                >
+                  {t(`elfInfo.rpathKind.${entry.kind}`)}{entry.insecure && ` · ${t('elfInfo.insecure')}`}
+                </span>
+              </div>
+            ))}
+          </div>
+        </div>
+      ))}
+      {elfInfo.goBuildInfo && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">
+            {t('elfInfo.goModules')} · {elfInfo.goBuildInfo.goVersion} ({elfInfo.goBuildInfo.deps.length})
+          </h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 max-h-48 overflow-y-auto">
+            {elfInfo.goBuildInfo.deps.map((dep, i) => (
+              <div key={i} className="text-xs font-mono text-gray-900 dark:text-slate-300 py-1">
+                {dep.path} {dep.version}{dep.replace && ` => ${dep.replace.path} ${dep.replace.version}`}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {elfInfo.rustDependencies && elfInfo.rustDependencies.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('elfInfo.rustCrates')} ({elfInfo.rustDependencies.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 max-h-48 overflow-y-auto">
+            {elfInfo.rustDependencies.map((pkg, i) => (
+              <div key={i} className="text-xs font-mono text-gray-900 dark:text-slate-300 py-1">
+                {pkg.name} {pkg.version} <span className="text-gray-500 dark:text-slate-500">{pkg.source}{pkg.kind === 'build' && ' (build)'}</span>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
       {elfInfo.sectionHeaders && elfInfo.sectionHeaders.length > 0 && (
         <div>
           <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('elfInfo.sections')}</h4>
@@ -382,21 +552,174 @@ function ELFInfoPanel({ elfInfo }: { elfInfo: ELFInfo }) {
   );
 }
 
+// Kernel module .modinfo section
+function KernelModuleSection({ kmod }: { kmod: KernelModuleInfo }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  return (
+    <div className="space-y-4">
+      <h4 className="text-sm font-semibold text-gray-900 dark:text-white">{t('kernelModule.title')}</h4>
+      <div className="grid grid-cols-2 gap-4">
+        <InfoItem label={t('kernelModule.name')} value={kmod.name} />
+        <InfoItem label={t('kernelModule.license')} value={kmod.license} />
+        <InfoItem label={t('kernelModule.description')} value={kmod.description} />
+        <InfoItem label={t('kernelModule.version')} value={kmod.version} />
+        <InfoItem label={t('kernelModule.authors')} value={kmod.authors.join(', ')} />
+        <InfoItem label={t('kernelModule.kernelVersion')} value={kmod.kernelVersion} />
+        <InfoItem label={t('kernelModule.vermagic')} value={kmod.vermagic} />
+        <InfoItem label={t('kernelModule.srcversion')} value={kmod.srcversion} />
+        <InfoItem label={t('kernelModule.depends')} value={kmod.depends.join(', ')} />
+        <InfoItem label={t('kernelModule.signed')} value={kmod.signed ? '✓' : '✗'} />
+      </div>
+      {kmod.parameters.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('kernelModule.parameters')} ({kmod.parameters.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 max-h-48 overflow-y-auto">
+            {kmod.parameters.map(param => (
+              <div key={param.name} className="text-xs font-mono text-gray-900 dark:text-slate-300 py-1">
+                {param.name}{param.type && <span className="text-gray-500 dark:text-slate-500">: {param.type}</span>}
+                {param.description && <span className="text-gray-500 dark:text-slate-500"> — {param.description}</span>}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {kmod.aliases.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('kernelModule.aliases')} ({kmod.aliases.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 max-h-48 overflow-y-auto">
+            {kmod.aliases.map(alias => (
+              <div key={alias} className="text-xs font-mono text-gray-900 dark:text-slate-300 py-1">{alias}</div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
+// Static library (ar archive) panel
+const ARCHIVE_SYMBOL_LIMIT = 200;
+
+function ArchivePanel({ archive, formatFileSize }: { archive: ArchiveInfo; formatFileSize: (bytes: number) => string }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  return (
+    <div className="space-y-6">
+      <h3 className="text-lg font-semibold flex items-center gap-2 text-gray-900 dark:text-white">
+        <Package className="w-5 h-5 text-orange-400" />
+        {t('archive.title')}
+        {archive.thin && (
+          <span className="px-2 py-0.5 rounded-lg text-xs font-medium bg-yellow-500/10 text-yellow-600 dark:text-yellow-400">{t('archive.thin')}</span>
+        )}
+      </h3>
+      <div>
+        <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('archive.members')} ({archive.members.length})</h4>
+        <div className="overflow-x-auto max-h-64 overflow-y-auto">
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="text-left text-gray-500 dark:text-slate-400">
+                <th className="py-2 pr-4">{t('archive.name')}</th>
+                <th className="py-2 pr-4">{t('archive.size')}</th>
+                <th className="py-2 pr-4">{t('elfInfo.type')}</th>
+                <th className="py-2">{t('elfInfo.machine')}</th>
+              </tr>
+            </thead>
+            <tbody className="font-mono text-gray-700 dark:text-slate-300">
+              {archive.members.map((member, index) => (
+                <tr key={index} className="border-t border-gray-100 dark:border-slate-800">
+                  <td className="py-2 pr-4 break-all">{member.name}</td>
+                  <td className="py-2 pr-4 whitespace-nowrap">{formatFileSize(member.size)}</td>
+                  <td className="py-2 pr-4">{member.elf?.type ?? '-'}</td>
+                  <td className="py-2">{member.elf?.machine ?? '-'}</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      </div>
+      {archive.symbolIndex.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('archive.symbolIndex')} ({archive.symbolIndex.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 max-h-64 overflow-y-auto">
+            {archive.symbolIndex.slice(0, ARCHIVE_SYMBOL_LIMIT).map((entry, i) => (
+              <div key={i} className="text-xs font-mono text-gray-900 dark:text-slate-300 py-1">
+                {entry.symbol} <span className="text-gray-500 dark:text-slate-500">{entry.member}</span>
+              </div>
+            ))}
+            {archive.symbolIndex.length > ARCHIVE_SYMBOL_LIMIT && (
+              <div className="text-xs text-gray-500 dark:text-slate-500 py-1">{t('archive.moreSymbols').replace('{count}', String(archive.symbolIndex.length - ARCHIVE_SYMBOL_LIMIT))}</div>
+            )}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
+// YARA matches of a single file or script
+function YaraMatchList({ matches, showPath }: { matches: YaraMatch[]; showPath?: boolean }) {
+  return (
+    <div className="space-y-3">
+      {matches.map((yaraMatch, index) => (
+        <div key={index} className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4">
+          <div className="flex flex-wrap items-center gap-2 mb-2">
+            <span className="text-sm font-semibold font-mono text-red-600 dark:text-red-400">{yaraMatch.rule}</span>
+            {yaraMatch.tags.map(tag => (
+              <span key={tag} className="px-2 py-0.5 rounded-lg text-xs bg-red-500/10 text-red-600 dark:text-red-400">{tag}</span>
+            ))}
+            {showPath && <span className="text-xs font-mono text-gray-500 dark:text-slate-500 break-all">{yaraMatch.path}</span>}
+          </div>
+          {yaraMatch.strings.map((string, i) => (
+            <div key={i} className="text-xs font-mono text-gray-700 dark:text-slate-300 py-0.5 break-all">
+              <span className="text-gray-500 dark:text-slate-500">0x{string.offset.toString(16)}</span> {string.identifier}: {string.data}
+            </div>
+          ))}
+        </div>
+      ))}
+    </div>
+  );
+}
+
+function YaraMatchesPanel({ matches }: { matches: YaraMatch[] }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  return (
+    <div className="space-y-4">
+      <h3 className="text-lg font-semibold flex items-center gap-2 text-gray-900 dark:text-white">
+        <AlertTriangle className="w-5 h-5 text-red-400" />
+        {t('yara.title')} ({matches.length})
+      </h3>
+      <YaraMatchList matches={matches} />
+    </div>
+  );
+}
+
 // Desktop Info Panel
 function DesktopInfoPanel({ desktopInfo }: { desktopInfo: DesktopInfo }) {
   const { locale } = useLocale();
   const { t } = useI18n(locale);
+  const localize = (value?: LocalizedString) => {
+    if (!value) return undefined;
+    const match = Object.keys(value.localized).find(key => key === locale || key.startsWith(`${locale}_`));
+    return match ? value.localized[match] : value.value;
+  };
   const fields = [
-    { key: 'Name', label: t('desktopInfo.name') },
-    { key: 'GenericName', label: t('desktopInfo.genericName') },
-    { key: 'Comment', label: t('desktopInfo.comment') },
-    { key: 'Icon', label: t('desktopInfo.icon') },
-    { key: 'Exec', label: t('desktopInfo.exec') },
-    { key: 'Terminal', label: t('desktopInfo.terminal') },
-    { key: 'Type', label: t('desktopInfo.type') },
-    { key: 'Categories', label: t('desktopInfo.categories') },
-    { key: 'Keywords', label: t('desktopInfo.keywords') },
+    { key: 'name', label: t('desktopInfo.name'), value: localize(desktopInfo.name) },
+    { key: 'genericName', label: t('desktopInfo.genericName'), value: localize(desktopInfo.genericName) },
+    { key: 'comment', label: t('desktopInfo.comment'), value: localize(desktopInfo.comment) },
+    { key: 'icon', label: t('desktopInfo.icon'), value: desktopInfo.icon?.value },
+    { key: 'exec', label: t('desktopInfo.exec'), value: desktopInfo.exec },
+    { key: 'terminal', label: t('desktopInfo.terminal'), value: desktopInfo.terminal ? 'true' : undefined },
+    { key: 'type', label: t('desktopInfo.type'), value: desktopInfo.type },
+    { key: 'categories', label: t('desktopInfo.categories'), value: desktopInfo.categories.join(';') },
+    { key: 'keywords', label: t('desktopInfo.keywords'), value: desktopInfo.keywords?.value.join(';') },
   ];
+  const severityClass = {
+    error: 'text-red-600 dark:text-red-400',
+    warning: 'text-yellow-600 dark:text-yellow-400',
+    hint: 'text-gray-500 dark:text-slate-400',
+  };
 
   return (
     <div className="space-y-6">
@@ -404,28 +727,344 @@ function DesktopInfoPanel({ desktopInfo }: { desktopInfo: DesktopInfo }) {
         <Monitor className="w-5 h-5 text-green-400" />
         {t('desktopInfo.title')}
       </h3>
+      {desktopInfo.iconResolution?.dataUrl && (
+        <div className="flex items-center gap-3">
+          <img src={desktopInfo.iconResolution.dataUrl} alt={desktopInfo.iconResolution.name} className="w-12 h-12 object-contain" />
+          <span className="text-xs font-mono text-gray-500 dark:text-slate-400">{desktopInfo.iconResolution.path}</span>
+        </div>
+      )}
       <div className="space-y-3">
         {fields.map(field => {
-          const value = desktopInfo[field.key as keyof DesktopInfo];
-          if (!value) return null;
+          if (!field.value) return null;
           return (
             <div key={field.key} className="flex items-start gap-3">
               <span className="text-gray-500 text-sm min-w-[80px] dark:text-slate-400">{field.label}:</span>
-              <span className="text-sm font-mono text-gray-900 dark:text-white">{value}</span>
+              <span className="text-sm font-mono text-gray-900 dark:text-white">{field.value}</span>
             </div>
           );
         })}
       </div>
-      {desktopInfo.NoDisplay === 'true' && (
+      {desktopInfo.actions.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('desktopInfo.actions')} ({desktopInfo.actions.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1">
+            {desktopInfo.actions.map(action => (
+              <div key={action.id} className="text-sm font-mono text-gray-700 dark:text-slate-300">
+                {localize(action.name) ?? action.id}
+                {action.exec && <span className="text-gray-500 dark:text-slate-500"> — {action.exec}</span>}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {desktopInfo.noDisplay && (
         <div className="rounded-xl bg-yellow-500/10 border border-yellow-500/20 p-4 text-sm text-yellow-600 dark:text-yellow-400">
           {t('desktopInfo.noDisplay')}
         </div>
       )}
-      {desktopInfo.Hidden === 'true' && (
+      {desktopInfo.hidden && (
         <div className="rounded-xl bg-orange-500/10 border border-orange-500/20 p-4 text-sm text-orange-600 dark:text-orange-400">
           {t('desktopInfo.hidden')}
         </div>
       )}
+      {desktopInfo.issues.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('desktopInfo.issues')} ({desktopInfo.issues.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 max-h-48 overflow-y-auto">
+            {desktopInfo.issues.map((issue, index) => (
+              <div key={index} className={`text-sm font-mono ${severityClass[issue.severity]}`}>
+                {issue.line !== undefined && `${issue.line}: `}{issue.message}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
+// AppStream Panel
+function AppStreamPanel({ component }: { component: AppStreamComponent }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const fields = [
+    { key: 'id', label: t('appstream.id'), value: component.id },
+    { key: 'name', label: t('appstream.name'), value: component.name?.value },
+    { key: 'summary', label: t('appstream.summary'), value: component.summary?.value },
+    { key: 'type', label: t('appstream.type'), value: component.type },
+    { key: 'developer', label: t('appstream.developer'), value: component.developer },
+    { key: 'projectLicense', label: t('appstream.projectLicense'), value: component.projectLicense },
+    { key: 'metadataLicense', label: t('appstream.metadataLicense'), value: component.metadataLicense },
+    { key: 'binaries', label: t('appstream.binaries'), value: component.provides.binaries.join(', ') },
+    { key: 'mediatypes', label: t('appstream.mediatypes'), value: component.provides.mediatypes.join(', ') },
+  ];
+  const severityClass = {
+    error: 'text-red-600 dark:text-red-400',
+    warning: 'text-yellow-600 dark:text-yellow-400',
+    hint: 'text-gray-500 dark:text-slate-400',
+  };
+
+  return (
+    <div className="space-y-6">
+      <h3 className="text-lg font-semibold flex items-center gap-2 text-gray-900 dark:text-white">
+        <Package className="w-5 h-5 text-blue-400" />
+        {t('appstream.title')}
+      </h3>
+      <div className="space-y-3">
+        {fields.map(field => {
+          if (!field.value) return null;
+          return (
+            <div key={field.key} className="flex items-start gap-3">
+              <span className="text-gray-500 text-sm min-w-[80px] dark:text-slate-400">{field.label}:</span>
+              <span className="text-sm font-mono text-gray-900 dark:text-white">{field.value}</span>
+            </div>
+          );
+        })}
+      </div>
+      {component.description && (
+        <p className="text-sm whitespace-pre-line text-gray-700 dark:text-slate-300">{component.description}</p>
+      )}
+      {component.releases.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('appstream.releases')} ({component.releases.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 max-h-48 overflow-y-auto">
+            {component.releases.map((release, index) => (
+              <div key={index} className="text-sm font-mono text-gray-700 dark:text-slate-300">
+                {release.version ?? '?'}{release.date && <span className="text-gray-500 dark:text-slate-500"> — {release.date}</span>}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {component.screenshots.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('appstream.screenshots')} ({component.screenshots.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1">
+            {component.screenshots.flatMap(shot => shot.images).map(url => (
+              <div key={url} className="text-sm font-mono text-gray-700 dark:text-slate-300 truncate">{url}</div>
+            ))}
+          </div>
+        </div>
+      )}
+      {component.issues.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('appstream.issues')} ({component.issues.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 max-h-48 overflow-y-auto">
+            {component.issues.map((issue, index) => (
+              <div key={index} className={`text-sm font-mono ${severityClass[issue.severity]}`}>{issue.message}</div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
+// systemd Unit Panel
+function SystemdUnitPanel({ unit }: { unit: SystemdUnit }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const fields = [
+    { key: 'name', label: t('systemd.name'), value: unit.name },
+    { key: 'scope', label: t('systemd.scope'), value: unit.scope },
+    { key: 'description', label: t('systemd.description'), value: unit.description },
+    { key: 'user', label: t('systemd.user'), value: unit.dynamicUser ? 'DynamicUser' : unit.user },
+    { key: 'group', label: t('systemd.group'), value: unit.group },
+    { key: 'wantedBy', label: t('systemd.wantedBy'), value: [...unit.wantedBy, ...unit.requiredBy].join(', ') },
+    { key: 'triggers', label: t('systemd.triggers'), value: unit.triggers.map(s => `${s.key}=${s.value}`).join(', ') },
+    { key: 'dropIns', label: t('systemd.dropIns'), value: unit.dropIns.join(', ') },
+  ];
+  const severityClass = {
+    error: 'text-red-600 dark:text-red-400',
+    warning: 'text-yellow-600 dark:text-yellow-400',
+    hint: 'text-gray-500 dark:text-slate-400',
+  };
+  const exposureClass = (score: number) =>
+    score >= 7.5 ? 'text-red-600 dark:text-red-400' : score >= 5 ? 'text-yellow-600 dark:text-yellow-400' : 'text-green-600 dark:text-green-400';
+
+  return (
+    <div className="space-y-6">
+      <h3 className="text-lg font-semibold flex items-center gap-2 text-gray-900 dark:text-white">
+        <Terminal className="w-5 h-5 text-blue-400" />
+        {t('systemd.title')}
+      </h3>
+      <div className="space-y-3">
+        {fields.map(field => {
+          if (!field.value) return null;
+          return (
+            <div key={field.key} className="flex items-start gap-3">
+              <span className="text-gray-500 text-sm min-w-[80px] dark:text-slate-400">{field.label}:</span>
+              <span className="text-sm font-mono text-gray-900 dark:text-white">{field.value}</span>
+            </div>
+          );
+        })}
+      </div>
+      {unit.exec.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('systemd.exec')} ({unit.exec.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1">
+            {unit.exec.map((exec, index) => (
+              <div key={index} className="text-sm font-mono text-gray-700 dark:text-slate-300 break-all">
+                <span className="text-gray-500 dark:text-slate-500">{exec.kind}=</span>{exec.command}
+                {!exec.found && (exec.providedBy
+                  ? <span className="text-gray-500 dark:text-slate-500"> ({t('systemd.providedBy')} {exec.providedBy})</span>
+                  : <span className="text-yellow-600 dark:text-yellow-400"> ({t('systemd.notFound')})</span>)}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {unit.exposure && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">
+            {t('systemd.exposure')}: <span className={exposureClass(unit.exposure.score)}>{unit.exposure.score.toFixed(1)} {unit.exposure.level}</span>
+          </h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 max-h-48 overflow-y-auto">
+            {unit.exposure.checks.map(check => (
+              <div key={check.setting} className="text-sm font-mono text-gray-700 dark:text-slate-300">
+                <span className={check.badness === 0 ? 'text-green-600 dark:text-green-400' : 'text-red-600 dark:text-red-400'}>
+                  {check.badness === 0 ? '✓' : '✗'}
+                </span>{' '}
+                {check.setting} <span className="text-gray-500 dark:text-slate-500">— {check.description}</span>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {unit.issues.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('systemd.issues')} ({unit.issues.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 max-h-48 overflow-y-auto">
+            {unit.issues.map((issue, index) => (
+              <div key={index} className={`text-sm font-mono ${severityClass[issue.severity]}`}>{issue.message}</div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
+// Script Shebang Panel
+function ShebangPanel({ info }: { info: ShebangInfo }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const fields = [
+    { key: 'line', label: t('shebang.line'), value: `#!${info.line}` },
+    { key: 'language', label: t('shebang.language'), value: info.language },
+    { key: 'program', label: t('shebang.program'), value: info.program },
+    { key: 'arguments', label: t('shebang.arguments'), value: info.arguments.join(' ') },
+    { key: 'usesEnv', label: t('shebang.usesEnv'), value: info.usesEnv ? '✓' : '' },
+    { key: 'crlf', label: t('shebang.crlf'), value: info.crlf ? '✓' : '' },
+    { key: 'providers', label: t('shebang.providers'), value: info.providers.join(', ') },
+    { key: 'providedBy', label: t('shebang.providedBy'), value: info.providedBy },
+  ];
+  const severityClass = {
+    error: 'text-red-600 dark:text-red-400',
+    warning: 'text-yellow-600 dark:text-yellow-400',
+    hint: 'text-gray-500 dark:text-slate-400',
+  };
+
+  return (
+    <div className="space-y-6">
+      <h3 className="text-lg font-semibold flex items-center gap-2 text-gray-900 dark:text-white">
+        <Code2 className="w-5 h-5 text-blue-400" />
+        {t('shebang.title')}
+      </h3>
+      <div className="space-y-3">
+        {fields.map(field => {
+          if (!field.value) return null;
+          return (
+            <div key={field.key} className="flex items-start gap-3">
+              <span className="text-gray-500 text-sm min-w-[80px] dark:text-slate-400">{field.label}:</span>
+              <span className="text-sm font-mono text-gray-900 dark:text-white break-all">{field.value}</span>
+            </div>
+          );
+        })}
+      </div>
+      {info.issues.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('shebang.issues')} ({info.issues.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1">
+            {info.issues.map((issue, index) => (
+              <div key={index} className={`text-sm font-mono ${severityClass[issue.severity]}`}>{issue.message}</div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
+// 每条集成记录一行摘要
+function integrationLines(entry: IntegrationEntry): string[] {
+  const keys = (keys: { key: string; operator: string; value: string }[]) =>
+    keys.map(k => `${k.key}${k.operator}"${k.value}"`).join(', ');
+  switch (entry.kind) {
+    case 'dbusService':
+      return [`${entry.name ?? '?'} → ${entry.exec ?? entry.systemdService ?? '?'}${entry.user ? ` (User=${entry.user})` : ''}`];
+    case 'dbusPolicy':
+      return entry.rules.map(rule =>
+        `${rule.allow ? 'allow' : 'deny'} [${rule.appliesTo}] ${Object.entries(rule.attributes).map(([k, v]) => `${k}=${v}`).join(' ')}`);
+    case 'polkit':
+      return entry.actions.map(action =>
+        `${action.id}: ${action.allowAny ?? '-'}/${action.allowInactive ?? '-'}/${action.allowActive ?? '-'}${action.execPath ? ` → ${action.execPath}` : ''}`);
+    case 'udev':
+      return entry.rules.map(rule => `${rule.line}: ${keys(rule.matches)} ⇒ ${keys(rule.assignments)}`);
+    case 'cron':
+      return [...entry.environment, ...entry.jobs.map(job => `${job.schedule} ${job.user ?? ''} ${job.command}`)];
+    case 'sudoers':
+      return [
+        ...entry.defaults,
+        ...entry.aliases.map(alias => `${alias.kind} ${alias.name} = ${alias.members.join(', ')}`),
+        ...entry.rules.map(rule =>
+          `${rule.users.join(', ')} ${rule.hosts} = ${rule.runAs ? `(${rule.runAs}) ` : ''}${rule.tags.map(tag => `${tag}: `).join('')}${rule.commands.join(', ')}`),
+      ];
+    case 'appArmor':
+      return entry.profiles.map(profile =>
+        `${profile.name}${profile.attachment ? ` ${profile.attachment}` : ''}${profile.flags.length ? ` (${profile.flags.join(', ')})` : ''}${profile.capabilities.length ? ` capability ${profile.capabilities.join(' ')}` : ''}`);
+    case 'pamConfig':
+      return entry.rules.map(rule => `${rule.moduleType} ${rule.control} ${rule.module} ${rule.args.join(' ')}`);
+    case 'pamModule':
+      return [entry.name];
+    case 'modprobe':
+      return entry.directives.map(d => `${d.command} ${d.module} ${d.args ?? ''}`);
+    case 'sysctl':
+      return entry.settings.map(setting => `${setting.ignoreErrors ? '-' : ''}${setting.key} = ${setting.value}`);
+  }
+}
+
+// System Integration Panel
+function IntegrationPanel({ entry }: { entry: IntegrationEntry }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const lines = integrationLines(entry);
+
+  return (
+    <div className="space-y-6">
+      <h3 className="text-lg font-semibold flex items-center gap-2 text-gray-900 dark:text-white">
+        <Code2 className="w-5 h-5 text-blue-400" />
+        {t('integration.title')}: {t(`integration.${entry.kind}`)}
+      </h3>
+      {entry.kind === 'dbusPolicy' && entry.owns.length > 0 && (
+        <div className="flex items-start gap-3">
+          <span className="text-gray-500 text-sm min-w-[80px] dark:text-slate-400">{t('integration.owns')}:</span>
+          <span className="text-sm font-mono text-gray-900 dark:text-white">{entry.owns.join(', ')}</span>
+        </div>
+      )}
+      {entry.kind === 'appArmor' && entry.includes.length > 0 && (
+        <div className="flex items-start gap-3">
+          <span className="text-gray-500 text-sm min-w-[80px] dark:text-slate-400">{t('integration.includes')}:</span>
+          <span className="text-sm font-mono text-gray-900 dark:text-white">{entry.includes.join(', ')}</span>
+        </div>
+      )}
+      {lines.length > 0 && (
+        <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 max-h-96 overflow-y-auto">
+          {lines.map((line, index) => (
+            <div key={index} className="text-sm font-mono text-gray-700 dark:text-slate-300 break-all">{line}</div>
+          ))}
+        </div>
+      )}
     </div>
   );
 }
@@ -460,6 +1099,12 @@ function OverviewTab({ result }: { result: ParseResult }) {
             <InfoRow label={t('overview.priority')} value={result.metadata.Priority} />
             <InfoRow label={t('overview.section')} value={result.metadata.Section} />
             {result.metadata.Homepage && <InfoRow label={t('overview.homepage')} value={result.metadata.Homepage} />}
+            {result.debugPackage && <InfoRow label={t('overview.debugPackage')} value={result.debugPackage} />}
+            {result.debugPackageError && (
+              <div className="my-2 rounded-xl bg-yellow-500/10 border border-yellow-500/20 p-3 text-xs font-mono text-yellow-600 dark:text-yellow-400 break-all">
+                {result.debugPackageError}
+              </div>
+            )}
             {result.metadata.Description && (
               <div className="py-3">
                 <span className="text-gray-500 text-sm dark:text-slate-400">{t('overview.description')}:</span>
@@ -486,6 +1131,27 @@ function OverviewTab({ result }: { result: ParseResult }) {
           </div>
         </div>
       </div>
+      {result.architectureCheck.issues.length > 0 && (
+        <div className="mt-8 pt-8 border-t border-gray-200 dark:border-slate-700">
+          <h3 className="text-lg font-semibold flex items-center gap-2 mb-4 text-gray-900 dark:text-white">
+            <Cpu className="w-5 h-5 text-red-400" />
+            {t('overview.architectureIssues')} ({result.architectureCheck.issues.length})
+          </h3>
+          <div className="rounded-xl bg-orange-500/10 border border-orange-500/20 p-4 space-y-1 max-h-48 overflow-y-auto">
+            {result.architectureCheck.issues.map((issue, i) => (
+              <div key={i} className="text-xs font-mono text-orange-600 dark:text-orange-400">
+                {issue.path && <span className="font-semibold">{issue.path}: </span>}
+                {issue.message}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {result.yaraScan && <YaraScanSection scan={result.yaraScan} files={result.files} />}
+      {result.changelog && <ChangelogSection changelog={result.changelog} />}
+      {result.copyright && <CopyrightSection copyright={result.copyright} />}
+      {result.bundledComponents && <BundledComponentsSection ecosystems={result.bundledComponents} />}
+      {result.accountInventory && <AccountInventorySection inventory={result.accountInventory} />}
       {(result.metadata.Depends || result.metadata.Recommends || result.metadata.Suggests) && (
         <div className="mt-8 pt-8 border-t border-gray-200 dark:border-slate-700">
           <h3 className="text-lg font-semibold flex items-center gap-2 mb-4 text-gray-900 dark:text-white">
@@ -504,6 +1170,334 @@ function OverviewTab({ result }: { result: ParseResult }) {
   );
 }
 
+// YARA scan summary
+function YaraScanSection({ scan, files }: { scan: YaraScan; files: ParseResult['files'] }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const payloadMatches = files.flatMap(file => file.yaraMatches ?? []);
+  const matches = [...scan.scriptMatches, ...payloadMatches];
+
+  return (
+    <div className="mt-8 pt-8 border-t border-gray-200 dark:border-slate-700">
+      <h3 className="text-lg font-semibold flex items-center gap-2 mb-4 text-gray-900 dark:text-white">
+        <AlertTriangle className={`w-5 h-5 ${matches.length > 0 ? 'text-red-400' : 'text-green-500'}`} />
+        {t('yara.title')} ({matches.length})
+      </h3>
+      <div className="space-y-1 mb-4">
+        <InfoRow label={t('yara.rulesDir')} value={scan.rulesDir} />
+        <InfoRow label={t('yara.ruleFiles')} value={String(scan.ruleFiles.length)} />
+      </div>
+      {scan.error && (
+        <div className="rounded-xl bg-yellow-500/10 border border-yellow-500/20 p-4 mb-4 text-xs font-mono whitespace-pre-wrap text-yellow-600 dark:text-yellow-400">
+          {scan.error}
+        </div>
+      )}
+      {scan.skipped.length > 0 && (
+        <div className="rounded-xl bg-yellow-500/10 border border-yellow-500/20 p-4 mb-4">
+          <div className="text-sm text-yellow-600 dark:text-yellow-400 mb-2">
+            {t('yara.skipped').replace('{count}', String(scan.skipped.length))}
+          </div>
+          <div className="max-h-40 overflow-y-auto text-xs font-mono text-gray-600 dark:text-slate-400 space-y-0.5">
+            {scan.skipped.map(path => (
+              <div key={path} className="break-all">{path}</div>
+            ))}
+          </div>
+        </div>
+      )}
+      {matches.length > 0 ? (
+        <div className="max-h-96 overflow-y-auto">
+          <YaraMatchList matches={matches} showPath />
+        </div>
+      ) : (
+        <p className="text-sm text-gray-500 dark:text-slate-400">{t('yara.noMatches')}</p>
+      )}
+    </div>
+  );
+}
+
+// Accounts and tmpfiles.d paths created by the package
+// 概览中显示的最新 changelog 条目数
+const OVERVIEW_CHANGELOG_ENTRIES = 3;
+
+// Changelog Section
+function ChangelogSection({ changelog }: { changelog: ChangelogInfo }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const [showUpstream, setShowUpstream] = useState(false);
+  const entries = changelog.debian?.entries.slice(0, OVERVIEW_CHANGELOG_ENTRIES) ?? [];
+  const severityClass = {
+    error: 'text-red-600 dark:text-red-400',
+    warning: 'text-yellow-600 dark:text-yellow-400',
+    hint: 'text-gray-500 dark:text-slate-400',
+  };
+
+  return (
+    <div className="mt-8 pt-8 border-t border-gray-200 dark:border-slate-700">
+      <h3 className="text-lg font-semibold flex items-center gap-2 mb-4 text-gray-900 dark:text-white">
+        <Clock className="w-5 h-5 text-blue-500 dark:text-blue-400" />
+        {t('changelog.title')}
+        {changelog.debian && (
+          <span className="text-sm font-normal text-gray-500 dark:text-slate-400">
+            ({entries.length}/{changelog.debian.entryCount})
+          </span>
+        )}
+      </h3>
+      {changelog.issues.length > 0 && (
+        <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 mb-4">
+          {changelog.issues.map((issue, index) => (
+            <div key={index} className={`text-sm font-mono ${severityClass[issue.severity]}`}>{issue.message}</div>
+          ))}
+        </div>
+      )}
+      <div className="space-y-4">
+        {entries.map((entry, index) => (
+          <div key={index}>
+            <div className="text-sm font-mono text-gray-900 dark:text-white">
+              <span className="font-semibold">{entry.version}</span>{' '}
+              <span className="text-gray-500 dark:text-slate-400">
+                {entry.distributions.join(' ')}{entry.urgency && `; urgency=${entry.urgency}`}
+              </span>
+            </div>
+            <div className="text-xs text-gray-500 dark:text-slate-500 mb-1">
+              {entry.maintainer ?? '-'}{entry.date && ` · ${entry.date}`}
+            </div>
+            <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-3 space-y-1">
+              {entry.changes.map((change, i) => (
+                <div key={i} className="text-sm font-mono text-gray-700 dark:text-slate-300 break-words">{change}</div>
+              ))}
+            </div>
+          </div>
+        ))}
+      </div>
+      {changelog.upstream && (
+        <div className="mt-4">
+          <button
+            onClick={() => setShowUpstream(!showUpstream)}
+            className="flex items-center gap-1 text-sm font-semibold text-gray-900 dark:text-white"
+          >
+            {showUpstream ? <ChevronDown className="w-4 h-4" /> : <ChevronRight className="w-4 h-4" />}
+            {t('changelog.upstream')} <span className="font-mono font-normal text-gray-500 dark:text-slate-400">{changelog.upstream.path}</span>
+          </button>
+          {showUpstream && (
+            <pre className="mt-2 rounded-xl bg-gray-100 dark:bg-slate-900 p-4 text-xs font-mono text-gray-700 dark:text-slate-300 max-h-64 overflow-auto whitespace-pre-wrap">
+              {changelog.upstream.excerpt}
+            </pre>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
+// Copyright Section
+function CopyrightSection({ copyright }: { copyright: CopyrightInfo }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const [showText, setShowText] = useState(false);
+  const severityClass = {
+    error: 'text-red-600 dark:text-red-400',
+    warning: 'text-yellow-600 dark:text-yellow-400',
+    hint: 'text-gray-500 dark:text-slate-400',
+  };
+
+  return (
+    <div className="mt-8 pt-8 border-t border-gray-200 dark:border-slate-700">
+      <h3 className="text-lg font-semibold flex items-center gap-2 mb-4 text-gray-900 dark:text-white">
+        <FileText className="w-5 h-5 text-blue-500 dark:text-blue-400" />
+        {t('copyright.title')}
+        <span className="text-sm font-normal text-gray-500 dark:text-slate-400">
+          ({copyright.machineReadable ? 'DEP-5' : t('copyright.freeForm')})
+        </span>
+      </h3>
+      {copyright.issues.length > 0 && (
+        <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 mb-4">
+          {copyright.issues.map((issue, index) => (
+            <div key={index} className={`text-sm font-mono ${severityClass[issue.severity]}`}>{issue.message}</div>
+          ))}
+        </div>
+      )}
+      {(copyright.upstreamName || copyright.source) && (
+        <div className="text-sm text-gray-600 dark:text-slate-400 mb-4">
+          {copyright.upstreamName && <span className="font-mono text-gray-900 dark:text-white">{copyright.upstreamName}</span>}
+          {copyright.source && <span className="font-mono"> · {copyright.source}</span>}
+        </div>
+      )}
+      {copyright.stanzas.length > 0 && (
+        <div className="overflow-x-auto mb-4">
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="text-left text-gray-500 dark:text-slate-400">
+                <th className="py-2 pr-4">{t('copyright.files')}</th>
+                <th className="py-2 pr-4">{t('copyright.license')}</th>
+                <th className="py-2 pr-4">SPDX</th>
+                <th className="py-2">{t('copyright.matched')}</th>
+              </tr>
+            </thead>
+            <tbody className="font-mono text-gray-700 dark:text-slate-300">
+              {copyright.stanzas.map((stanza, index) => (
+                <tr key={index} className="border-t border-gray-100 dark:border-slate-800 align-top" title={stanza.copyright}>
+                  <td className="py-2 pr-4 break-all">{stanza.patterns.join(' ')}</td>
+                  <td className="py-2 pr-4">{stanza.license}</td>
+                  <td className="py-2 pr-4">{stanza.spdx}</td>
+                  <td className="py-2">{stanza.matched}</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+      {copyright.detectedLicenses.length > 0 && (
+        <div className="text-sm mb-4">
+          <span className="text-gray-500 dark:text-slate-400">{t('copyright.detected')}: </span>
+          <span className="font-mono text-gray-900 dark:text-white">{copyright.detectedLicenses.join(', ')}</span>
+        </div>
+      )}
+      {copyright.uncovered.length > 0 && (
+        <div className="mb-4">
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('copyright.uncovered')} ({copyright.uncovered.length})</h4>
+          <div className="rounded-xl bg-orange-500/10 border border-orange-500/20 p-4 space-y-1 max-h-48 overflow-y-auto">
+            {copyright.uncovered.map(path => (
+              <div key={path} className="text-xs font-mono text-orange-600 dark:text-orange-400">{path}</div>
+            ))}
+          </div>
+        </div>
+      )}
+      {copyright.text && (
+        <div>
+          <button
+            onClick={() => setShowText(!showText)}
+            className="flex items-center gap-1 text-sm font-semibold text-gray-900 dark:text-white"
+          >
+            {showText ? <ChevronDown className="w-4 h-4" /> : <ChevronRight className="w-4 h-4" />}
+            <span className="font-mono font-normal text-gray-500 dark:text-slate-400">{copyright.path}</span>
+          </button>
+          {showText && (
+            <pre className="mt-2 rounded-xl bg-gray-100 dark:bg-slate-900 p-4 text-xs font-mono text-gray-700 dark:text-slate-300 max-h-64 overflow-auto whitespace-pre-wrap">
+              {copyright.text}
+            </pre>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
+// Bundled Components Section
+function BundledComponentsSection({ ecosystems }: { ecosystems: Record<string, BundledComponent[]> }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const total = Object.values(ecosystems).reduce((sum, components) => sum + components.length, 0);
+
+  return (
+    <div className="mt-8 pt-8 border-t border-gray-200 dark:border-slate-700">
+      <h3 className="text-lg font-semibold flex items-center gap-2 mb-4 text-gray-900 dark:text-white">
+        <Package className="w-5 h-5 text-blue-500 dark:text-blue-400" />
+        {t('bundled.title')} ({total})
+      </h3>
+      <div className="space-y-6">
+        {Object.entries(ecosystems).map(([ecosystem, components]) => (
+          <div key={ecosystem}>
+            <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t(`bundled.${ecosystem}`)} ({components.length})</h4>
+            <div className="overflow-x-auto max-h-64 overflow-y-auto">
+              <table className="w-full text-sm">
+                <thead>
+                  <tr className="text-left text-gray-500 dark:text-slate-400">
+                    <th className="py-2 pr-4">{t('bundled.name')}</th>
+                    <th className="py-2 pr-4">{t('bundled.version')}</th>
+                    <th className="py-2 pr-4">{t('bundled.license')}</th>
+                    <th className="py-2">{t('bundled.path')}</th>
+                  </tr>
+                </thead>
+                <tbody className="font-mono text-gray-700 dark:text-slate-300">
+                  {components.map((component, index) => (
+                    <tr key={index} className="border-t border-gray-100 dark:border-slate-800 align-top" title={component.purl}>
+                      <td className="py-2 pr-4">{component.name}</td>
+                      <td className="py-2 pr-4">{component.version ?? '-'}</td>
+                      <td className="py-2 pr-4">{component.license ?? '-'}</td>
+                      <td className="py-2 break-all text-gray-500 dark:text-slate-500">
+                        {component.path}{component.entry && `!/${component.entry}`}
+                      </td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            </div>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
+
+function AccountInventorySection({ inventory }: { inventory: AccountInventory }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const formatSource = (source: { path: string; line: number; method: string }) => `${source.path}:${source.line} (${source.method})`;
+
+  return (
+    <div className="mt-8 pt-8 border-t border-gray-200 dark:border-slate-700">
+      <h3 className="text-lg font-semibold flex items-center gap-2 mb-4 text-gray-900 dark:text-white">
+        <Users className="w-5 h-5 text-blue-500 dark:text-blue-400" />
+        {t('accounts.title')}
+      </h3>
+      {inventory.accounts.length > 0 && (
+        <div className="overflow-x-auto mb-6">
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="text-left text-gray-500 dark:text-slate-400">
+                <th className="py-2 pr-4">{t('accounts.name')}</th>
+                <th className="py-2 pr-4">{t('accounts.kind')}</th>
+                <th className="py-2 pr-4">{t('accounts.system')}</th>
+                <th className="py-2 pr-4">{t('accounts.home')}</th>
+                <th className="py-2 pr-4">{t('accounts.shell')}</th>
+                <th className="py-2">{t('accounts.source')}</th>
+              </tr>
+            </thead>
+            <tbody className="font-mono text-gray-700 dark:text-slate-300">
+              {inventory.accounts.map((account, index) => (
+                <tr key={index} className="border-t border-gray-100 dark:border-slate-800 align-top">
+                  <td className="py-2 pr-4">
+                    {account.name}
+                    {account.id && <span className="text-gray-500 dark:text-slate-500"> ({account.id})</span>}
+                  </td>
+                  <td className="py-2 pr-4">
+                    {t(`accounts.${account.kind}`)}
+                    {account.group && <span className="text-gray-500 dark:text-slate-500"> → {account.group}</span>}
+                  </td>
+                  <td className="py-2 pr-4">{account.system ? '✓' : ''}</td>
+                  <td className="py-2 pr-4">
+                    {account.home ?? '-'}
+                    {account.createHome !== undefined && (
+                      <span className="text-gray-500 dark:text-slate-500"> ({account.createHome ? t('accounts.created') : t('accounts.notCreated')})</span>
+                    )}
+                  </td>
+                  <td className="py-2 pr-4">{account.shell ?? '-'}</td>
+                  <td className="py-2">
+                    {account.sources.map((source, i) => <div key={i}>{formatSource(source)}</div>)}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+      {inventory.tmpfiles.length > 0 && (
+        <div>
+          <h4 className="text-sm font-semibold mb-3 text-gray-900 dark:text-white">{t('accounts.tmpfiles')} ({inventory.tmpfiles.length})</h4>
+          <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 max-h-48 overflow-y-auto">
+            {inventory.tmpfiles.map((entry, index) => (
+              <div key={index} className="text-sm font-mono text-gray-700 dark:text-slate-300" title={formatSource(entry.source)}>
+                {entry.entryType} {entry.path} {entry.mode ?? '-'} {entry.user ?? '-'} {entry.group ?? '-'} {entry.age ?? '-'}
+                {entry.argument && ` ${entry.argument}`}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
 function InfoRow({ label, value }: { label: string; value?: string }) {
   if (!value) return null;
   return (
@@ -557,6 +1551,7 @@ function FilesTab({
   desktopCount,
   toggleExpandAll,
   expandButtonLabel,
+  flaggedFiles,
 }: any) {
   const { locale } = useLocale();
   const { t } = useI18n(locale);
@@ -608,9 +1603,14 @@ function FilesTab({
                 style={{ paddingLeft: `${16 + file.depth * 20}px` }}
               >
                 {getFileIcon(file, false)}
-                <span className="flex-1 text-sm font-mono truncate text-gray-700 dark:text-slate-300">
+                <span className={`flex-1 text-sm font-mono truncate ${flaggedFiles.has(file.path) ? 'text-orange-600 dark:text-orange-400' : 'text-gray-700 dark:text-slate-300'}`}>
                   {file.name}
                 </span>
+                {flaggedFiles.has(file.path) && (
+                  <span title={flaggedFiles.get(file.path).join('\n')}>
+                    <AlertTriangle className="w-4 h-4 text-orange-500" />
+                  </span>
+                )}
                 {file.size > 0 && (
                   <span className="text-xs text-gray-400 w-20 text-right dark:text-slate-500">{formatFileSize(file.size)}</span>
                 )}
@@ -646,9 +1646,14 @@ function ScriptsTab({ result }: { result: ParseResult }) {
           {result.scripts.config && (
             <ScriptSection name="config" script={result.scripts.config} description={t('scripts.config')} />
           )}
+          {result.scriptCommands && <ScriptCommandsSection report={result.scriptCommands} />}
+          {result.alternativesInventory && <AlternativesSection inventory={result.alternativesInventory} />}
           {result.scripts.templates && (
             <ScriptSection name="templates" script={result.scripts.templates} description={t('scripts.templates')} />
           )}
+          {result.scripts.triggers && (
+            <ScriptSection name="triggers" script={result.scripts.triggers} description={t('scripts.triggers')} />
+          )}
         </div>
       ) : (
         <p className="text-gray-500 text-center py-12 dark:text-slate-400">{t('scripts.noScripts')}</p>
@@ -657,6 +1662,88 @@ function ScriptsTab({ result }: { result: ParseResult }) {
   );
 }
 
+// External commands run by maintainer scripts and the dependency that guarantees them
+function ScriptCommandsSection({ report }: { report: ScriptCommandReport }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const coverageClass = {
+    package: 'text-green-600 dark:text-green-400',
+    essential: 'text-green-600 dark:text-green-400',
+    dependency: 'text-green-600 dark:text-green-400',
+    guarded: 'text-gray-500 dark:text-slate-400',
+    missing: 'text-red-600 dark:text-red-400',
+    unknown: 'text-yellow-600 dark:text-yellow-400',
+  };
+  const uncovered = report.commands.filter(c => c.coverage === 'missing' || c.coverage === 'unknown').length;
+
+  return (
+    <div>
+      <h3 className="text-base font-semibold mb-3 text-gray-900 dark:text-white">
+        {t('scriptCommands.title')} ({report.commands.length})
+        {uncovered > 0 && <span className="ml-2 text-red-600 dark:text-red-400">{t('scriptCommands.uncovered')}: {uncovered}</span>}
+      </h3>
+      {!report.databaseAvailable && (
+        <p className="text-sm mb-3 text-yellow-600 dark:text-yellow-400">{t('scriptCommands.noDatabase')}</p>
+      )}
+      <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-1 max-h-64 overflow-y-auto">
+        {report.commands.map((command, index) => (
+          <div key={index} className="text-sm font-mono text-gray-700 dark:text-slate-300">
+            <span className="text-gray-500 dark:text-slate-500">{command.script}:{command.lines.join(',')} </span>
+            {command.command}{' '}
+            <span className={coverageClass[command.coverage]}>
+              {t(`scriptCommands.${command.coverage}`)}
+              {command.coveredBy && ` (${command.coveredBy})`}
+            </span>
+            {command.coverage === 'missing' && command.providers.length > 0 && (
+              <span className="text-gray-500 dark:text-slate-500"> — {t('scriptCommands.providedBy')} {command.providers.join(', ')}</span>
+            )}
+            {command.note && <span className="text-gray-500 dark:text-slate-500"> — {command.note}</span>}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
+
+// Alternatives and diversions found in maintainer scripts
+function AlternativesSection({ inventory }: { inventory: AlternativesInventory }) {
+  const { locale } = useLocale();
+  const { t } = useI18n(locale);
+  const shippedMark = (shipped?: boolean) =>
+    shipped === false && <span className="text-orange-600 dark:text-orange-400"> ({t('alternatives.notShipped')})</span>;
+
+  return (
+    <div>
+      <h3 className="text-base font-semibold mb-3 text-gray-900 dark:text-white">{t('alternatives.title')}</h3>
+      <div className="rounded-xl bg-gray-100 dark:bg-slate-900 p-4 space-y-2 max-h-64 overflow-y-auto">
+        {inventory.alternatives.map((alternative, index) => (
+          <div key={`a${index}`} className="text-sm font-mono text-gray-700 dark:text-slate-300">
+            <span className="text-gray-500 dark:text-slate-500">{alternative.source.script}:{alternative.source.line} </span>
+            update-alternatives --{alternative.action} {alternative.link && `${alternative.link} `}{alternative.name}
+            {alternative.path && ` ${alternative.path}`}{alternative.priority && ` ${alternative.priority}`}
+            {shippedMark(alternative.shipped)}
+            {alternative.slaves.map(slave => (
+              <div key={slave.name} className="pl-6">
+                --slave {slave.link} {slave.name} {slave.path}{shippedMark(slave.shipped)}
+              </div>
+            ))}
+          </div>
+        ))}
+        {inventory.diversions.map((diversion, index) => (
+          <div key={`d${index}`} className="text-sm font-mono text-gray-700 dark:text-slate-300">
+            <span className="text-gray-500 dark:text-slate-500">{diversion.source.script}:{diversion.source.line} </span>
+            dpkg-divert --{diversion.action} {diversion.path}
+            {diversion.divertTo && ` → ${diversion.divertTo}`}
+            {diversion.package && ` (${diversion.package})`}
+            {diversion.local && ' (--local)'}
+            {diversion.rename && ' --rename'}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
+
 function ScriptSection({ script, description }: { script: string; description: string; name?: string }) {
   return (
     <div>
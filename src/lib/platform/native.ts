@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { ParseResult } from '@/types';
+import type { ParseResult, SbomFormat } from '@/types';
 
 /**
  * Tauri native API wrapper
@@ -8,10 +8,22 @@ import type { ParseResult } from '@/types';
 
 export const native = {
   /**
-   * Parse a Debian package file
+   * Parse a Debian package file, optionally pairing it with a -dbgsym package
+   * and scanning it with a directory of YARA rules
    */
-  parseDebPackage: (filePath: string): Promise<ParseResult> => {
-    return invoke('parse_deb_package', { filePath });
+  parseDebPackage: (filePath: string, debugPath?: string, yaraRulesDir?: string): Promise<ParseResult> => {
+    return invoke('parse_deb_package', {
+      filePath,
+      debugPath: debugPath ?? null,
+      yaraRulesDir: yaraRulesDir ?? null,
+    });
+  },
+
+  /**
+   * Write a parsed package as an SPDX 2.3 or CycloneDX 1.5 JSON SBOM
+   */
+  exportSbom: (result: ParseResult, format: SbomFormat, outputPath: string): Promise<void> => {
+    return invoke('export_sbom', { result, format, outputPath });
   },
 
   /**
@@ -34,40 +34,541 @@ export interface FileInfo {
   uid: number;
   gid: number;
   mtime: string;
-  type: 'file' | 'directory' | 'symlink' | 'elf' | 'desktop';
+  type: 'file' | 'directory' | 'symlink' | 'elf' | 'desktop' | 'archive';
   isElf: boolean;
   isDesktop: boolean;
+  isArchive: boolean;
+  entropy?: number;
+  sha1?: string; // 文件内容的 SHA-1（SPDX 要求）
+  sha256?: string; // 文件内容的 SHA-256
+  yaraMatches?: YaraMatch[];
+}
+
+// YARA 匹配结果
+export interface YaraMatch {
+  path: string;
+  rule: string;
+  tags: string[];
+  strings: { offset: number; identifier: string; data: string }[];
+}
+
+export interface YaraScan {
+  rulesDir: string;
+  ruleFiles: string[];
+  scriptMatches: YaraMatch[];
+  skipped: string[]; // 扫描时间用尽而未扫描的文件
+  error?: string;
 }
 
 // ELF 文件信息
 export interface ELFInfo {
   type: string;
   machine: string;
+  class: string;
+  endian: string;
+  flags: string;
   entry: string;
   programHeaders: string[];
   sectionHeaders: string[];
   dependencies?: string[];
   dynamicLibraries?: string[];
   interpreter?: string;
+  rpath?: RpathEntry[];
+  runpath?: RpathEntry[];
+  buildId?: string;
+  debuglink?: DebugLink;
+  stripped: boolean;
+  hasDebugInfo: boolean;
+  debugFile?: string;
+  kernelModule?: KernelModuleInfo;
+  goBuildInfo?: GoBuildInfo;
+  rustDependencies?: RustPackage[];
+  dwarf?: DwarfSummary;
+  notes: ElfNote[];
+  abiTag?: { os: string; kernelVersion: string };
+  goBuildId?: string;
+  packageNote?: PackageNote;
+  segments: ElfSegment[];
+  sectionHeaderCount?: number; // 缺失表示 readelf 未给出
+  sectionEntropy: { name: string; size: number; entropy: number }[];
+  packer?: string;
+}
+
+// 程序头（段）
+export interface ElfSegment {
+  type: string;
+  offset: number;
+  fileSize: number;
+  memSize: number;
+  flags: string;
+}
+
+// 加壳/高熵检测结果
+export interface PackerFinding {
+  path: string;
+  kind: 'knownPacker' | 'writableExecutableSegment' | 'noSectionTable' | 'highEntropy';
+  message: string;
+}
+
+// 密钥/凭据泄露检测结果
+export interface SecretFinding {
+  path: string;
+  kind: 'privateKey' | 'token' | 'assignment' | 'credentialFile';
+  rule: string;
+  line?: number; // 文本文件行号（从 1 开始）
+  offset?: number; // 二进制文件字节偏移
+  snippet: string; // 已脱敏片段
+}
+
+// 证书/密钥问题
+export interface CertIssue {
+  kind: 'expired' | 'expiresSoon' | 'notYetValid' | 'weakKey' | 'weakSignature';
+  message: string;
+}
+
+// X.509 证书
+export interface CertificateInfo {
+  path: string;
+  index: number; // 文件内序号
+  encoding: 'pem' | 'der' | 'openssh';
+  subject: string;
+  issuer: string;
+  serial: string;
+  notBefore: number; // Unix 时间戳（秒）
+  notAfter: number;
+  keyType: string;
+  keySize: number;
+  curve?: string;
+  signatureAlgorithm: string;
+  subjectAltNames: string[];
+  isCa: boolean;
+  selfSigned: boolean;
+  issues: CertIssue[];
+}
+
+// 公钥/私钥
+export interface KeyInfo {
+  path: string;
+  index: number;
+  encoding: 'pem' | 'der' | 'openssh';
+  private: boolean;
+  keyType: string;
+  keySize?: number;
+  curve?: string;
+  encrypted: boolean;
+  issues: CertIssue[];
+}
+
+// 证书与密钥清单
+export interface CertificateInventory {
+  certificates: CertificateInfo[];
+  keys: KeyInfo[];
+  pkcs12Bundles: string[]; // PKCS#12 包路径
+}
+
+// 账户来源（配置文件或维护脚本）
+export interface AccountSource {
+  path: string;
+  line: number;
+  method: string; // sysusers.d、adduser、useradd 等
+}
+
+// 包创建的用户、组或组成员关系
+export interface Account {
+  name: string;
+  kind: 'user' | 'group' | 'membership';
+  id?: string;
+  group?: string; // 用户主组或成员关系所属组
+  system: boolean;
+  home?: string;
+  createHome?: boolean;
+  shell?: string;
+  gecos?: string;
+  sources: AccountSource[];
+}
+
+// tmpfiles.d 条目
+export interface TmpfilesEntry {
+  entryType: string;
+  path: string;
+  mode?: string;
+  user?: string;
+  group?: string;
+  age?: string;
+  argument?: string;
+  source: AccountSource;
+}
+
+// 账户与目录清单
+export interface AccountInventory {
+  accounts: Account[];
+  tmpfiles: TmpfilesEntry[];
+}
+
+// ELF note
+export interface ElfNote {
+  section: string;
+  owner: string;
+  type: number;
+  typeName: string;
+  description: string;
+}
+
+// systemd .note.package 元数据
+export interface PackageNote {
+  type?: string;
+  name?: string;
+  version?: string;
+  architecture?: string;
+  distro?: string;
+  os?: string;
+  osVersion?: string;
+  debugInfoUrl?: string;
+}
+
+// DWARF 编译单元摘要
+export interface DwarfSummary {
+  compileUnitCount: number;
+  compileUnits: {
+    name: string;
+    compDir?: string;
+    producer?: string;
+    language?: string;
+  }[];
+  producers: {
+    compiler: string;
+    flags: string[];
+    units: number;
+  }[];
+  languages: string[];
+  sourceFileCount: number;
+  sourceFiles: string[]; // .debug_line 文件表中的源文件（含头文件）
+  buildPaths: string[];
+}
+
+// Go 构建信息
+export interface GoBuildInfo {
+  goVersion: string;
+  path?: string;
+  main?: GoModule;
+  deps: GoModule[];
+  settings: { key: string; value: string }[];
+}
+
+export interface GoModule {
+  path: string;
+  version: string;
+  sum?: string;
+  replace?: GoModule;
+}
+
+// cargo-auditable 记录的 crate
+export interface RustPackage {
+  name: string;
+  version: string;
+  source: string;
+  kind: 'runtime' | 'build';
+  root: boolean;
+}
+
+// 内核模块 .modinfo 信息
+export interface KernelModuleInfo {
+  name?: string;
+  license?: string;
+  authors: string[];
+  description?: string;
+  version?: string;
+  srcversion?: string;
+  vermagic?: string;
+  kernelVersion?: string;
+  depends: string[];
+  aliases: string[];
+  parameters: {
+    name: string;
+    type?: string;
+    description?: string;
+  }[];
+  signed: boolean;
+}
+
+// .gnu_debuglink 信息
+export interface DebugLink {
+  name: string;
+  crc: string;
+}
+
+// RPATH/RUNPATH 条目
+export interface RpathEntry {
+  path: string;
+  kind: 'origin' | 'package' | 'absolute' | 'buildTree' | 'empty' | 'relative' | 'loaderToken'; // loaderToken：含 $LIB/$PLATFORM 的绝对路径
+  insecure: boolean;
+}
+
+// 静态库（ar 归档）信息
+export interface ArchiveInfo {
+  thin: boolean;
+  members: ArchiveMember[];
+  symbolIndex: ArchiveSymbol[];
+}
+
+export interface ArchiveMember {
+  name: string;
+  size: number;
+  elf?: ELFInfo;
+}
+
+export interface ArchiveSymbol {
+  symbol: string;
+  member: string;
+}
+
+// 架构一致性检查
+export interface ArchitectureCheck {
+  declared: string;
+  expected?: {
+    machine: string;
+    class: string;
+    endian: string;
+    floatAbi?: 'hard' | 'soft';
+  };
+  issues: ArchIssue[];
+}
+
+export interface ArchIssue {
+  kind:
+    | 'archAllWithElf'
+    | 'machineMismatch'
+    | 'classMismatch'
+    | 'endianMismatch'
+    | 'floatAbiMismatch'
+    | 'mixedArchitectures'
+    | 'unknownArchitecture';
+  path?: string;
+  message: string;
+}
+
+// 本地化字符串（Key[locale]）
+export interface LocalizedString {
+  value: string;
+  localized: Record<string, string>;
+}
+
+export interface LocalizedList {
+  value: string[];
+  localized: Record<string, string[]>;
+}
+
+// Desktop Action 分组
+export interface DesktopAction {
+  id: string;
+  name?: LocalizedString;
+  icon?: LocalizedString;
+  exec?: string;
+}
+
+// Desktop 文件原始分组
+export interface DesktopGroup {
+  name: string;
+  line: number;
+  entries: { key: string; locale?: string; value: string; line: number }[];
+}
+
+// Desktop 文件校验问题
+export interface DesktopIssue {
+  severity: 'error' | 'warning' | 'hint';
+  line?: number;
+  message: string;
+}
+
+// Desktop 图标解析结果
+export interface IconResolution {
+  name: string;
+  path?: string; // 最佳匹配（相对包根目录）
+  candidates: string[];
+  dataUrl?: string; // 预览用 data URL
+}
+
+// Exec/TryExec 程序解析结果
+export interface ExecResolution {
+  program: string;
+  path?: string;
+  found: boolean;
+  providedBy?: string; // 包内没有时，保障该程序的 Essential 包或依赖
+}
+
+// 校验问题
+export interface Issue {
+  severity: 'error' | 'warning' | 'hint';
+  message: string;
+}
+
+// AppStream 版本发布
+export interface AppStreamRelease {
+  version?: string;
+  date?: string;
+  timestamp?: number;
+  type?: string;
+  description?: string;
+}
+
+// AppStream 截图
+export interface AppStreamScreenshot {
+  default: boolean;
+  caption?: string;
+  images: string[];
+  videos: string[];
+}
+
+// AppStream 元信息组件
+export interface AppStreamComponent {
+  type?: string;
+  id?: string;
+  name?: LocalizedString;
+  summary?: LocalizedString;
+  description?: string;
+  developer?: string;
+  metadataLicense?: string;
+  projectLicense?: string;
+  urls: { type: string; url: string }[];
+  launchables: { type: string; value: string }[];
+  releases: AppStreamRelease[];
+  screenshots: AppStreamScreenshot[];
+  contentRating?: { type?: string; attributes: { id: string; value: string }[] };
+  provides: {
+    binaries: string[];
+    mediatypes: string[];
+    libraries: string[];
+    ids: string[];
+    dbus: string[];
+  };
+  categories: string[];
+  keywords: string[];
+  issues: Issue[];
+}
+
+// systemd 单元键值
+export interface UnitSetting {
+  key: string;
+  value: string;
+}
+
+// systemd Exec* 命令
+export interface ExecCommand {
+  kind: string; // ExecStart、ExecStartPre 等
+  command: string;
+  prefixes?: string; // 特殊前缀，如 - + !
+  program: string;
+  path?: string; // 包内位置
+  found: boolean;
+  providedBy?: string; // 包内没有时，保障该程序的 Essential 包或依赖
+}
+
+// 暴露度评估（类似 systemd-analyze security）
+export interface ExposureScore {
+  score: number; // 0.0 完全加固 ~ 10.0 完全暴露
+  level: string;
+  checks: { setting: string; description: string; weight: number; badness: number }[];
+}
+
+// systemd 单元信息
+export interface SystemdUnit {
+  name: string;
+  unitType: string;
+  scope: 'system' | 'user';
+  sections: { name: string; entries: UnitSetting[] }[];
+  description?: string;
+  documentation: string[];
+  exec: ExecCommand[];
+  user?: string;
+  group?: string;
+  dynamicUser: boolean;
+  sandboxing: UnitSetting[];
+  wantedBy: string[];
+  requiredBy: string[];
+  also: string[];
+  alias: string[];
+  triggers: UnitSetting[];
+  dropIns: string[]; // 已合并的 <unit>.d/*.conf，按生效顺序
+  exposure?: ExposureScore;
+  issues: Issue[];
 }
 
+// 系统集成配置（D-Bus、polkit、udev、cron、sudoers、AppArmor、PAM、modprobe、sysctl）
+export interface UdevKey {
+  key: string;
+  operator: string;
+  value: string;
+}
+
+export type IntegrationEntry =
+  | { kind: 'dbusService'; bus: string; name?: string; exec?: string; user?: string; systemdService?: string }
+  | {
+      kind: 'dbusPolicy';
+      bus: string;
+      owns: string[];
+      rules: { appliesTo: string; allow: boolean; attributes: Record<string, string> }[];
+    }
+  | {
+      kind: 'polkit';
+      actions: {
+        id: string;
+        description?: string;
+        allowAny?: string;
+        allowInactive?: string;
+        allowActive?: string;
+        execPath?: string;
+      }[];
+    }
+  | { kind: 'udev'; rules: { line: number; matches: UdevKey[]; assignments: UdevKey[] }[] }
+  | { kind: 'cron'; environment: string[]; jobs: { line?: number; schedule: string; user?: string; command: string }[] }
+  | {
+      kind: 'sudoers';
+      defaults: string[];
+      aliases: { kind: string; name: string; members: string[] }[];
+      rules: { line: number; users: string[]; hosts: string; runAs?: string; tags: string[]; commands: string[] }[];
+    }
+  | {
+      kind: 'appArmor';
+      includes: string[];
+      profiles: { line: number; name: string; attachment?: string; flags: string[]; capabilities: string[] }[];
+    }
+  | { kind: 'pamConfig'; rules: { line: number; moduleType: string; control: string; module: string; args: string[] }[] }
+  | { kind: 'pamModule'; name: string }
+  | { kind: 'modprobe'; directives: { line: number; command: string; module: string; args?: string }[] }
+  | { kind: 'sysctl'; settings: { line: number; key: string; value: string; ignoreErrors: boolean }[] };
+
 // Desktop 文件信息
 export interface DesktopInfo {
-  Name?: string;
-  GenericName?: string;
-  Comment?: string;
-  Icon?: string;
-  Exec?: string;
-  Terminal?: string;
-  Type?: string;
-  Categories?: string;
-  Keywords?: string;
-  NoDisplay?: string;
-  Hidden?: string;
-  OnlyShowIn?: string;
-  NotShowIn?: string;
-  MimeType?: string;
-  [key: string]: string | undefined;
+  type?: string;
+  version?: string;
+  name?: LocalizedString;
+  genericName?: LocalizedString;
+  comment?: LocalizedString;
+  icon?: LocalizedString;
+  noDisplay: boolean;
+  hidden: boolean;
+  terminal: boolean;
+  dbusActivatable: boolean;
+  startupNotify?: boolean;
+  prefersNonDefaultGpu: boolean;
+  singleMainWindow: boolean;
+  onlyShowIn: string[];
+  notShowIn: string[];
+  tryExec?: string;
+  exec?: string;
+  path?: string; // 工作目录
+  startupWMClass?: string;
+  url?: string;
+  actions: DesktopAction[];
+  mimeTypes: string[];
+  categories: string[];
+  implements: string[];
+  keywords?: LocalizedList;
+  iconResolution?: IconResolution;
+  execResolution?: ExecResolution;
+  tryExecResolution?: ExecResolution;
+  groups: DesktopGroup[];
+  issues: DesktopIssue[];
 }
 
 // 脚本内容
@@ -78,8 +579,137 @@ export interface Scripts {
   postrm?: string;
   config?: string;
   templates?: string;
+  triggers?: string;
+}
+
+// 脚本中的位置
+export interface ScriptLocation {
+  script: string;
+  line: number;
+}
+
+// update-alternatives 调用
+export interface Alternative {
+  action: string; // install、remove、set 等
+  name: string;
+  link?: string;
+  path?: string;
+  priority?: string;
+  shipped?: boolean; // 目标是否由本包提供
+  slaves: { link: string; name: string; path: string; shipped?: boolean }[];
+  source: ScriptLocation;
+}
+
+// dpkg-divert 调用
+export interface Diversion {
+  action: 'add' | 'remove';
+  path: string;
+  divertTo?: string;
+  package?: string;
+  local: boolean;
+  rename: boolean;
+  shipped?: boolean; // 本包是否提供被转移的路径
+  source: ScriptLocation;
+}
+
+export interface AlternativesInventory {
+  alternatives: Alternative[];
+  diversions: Diversion[];
+}
+
+// 维护脚本调用的外部命令及其依赖保障情况
+export interface ScriptCommand {
+  command: string;
+  script: string;
+  lines: number[];
+  providers: string[]; // 本地 dpkg 数据库中提供该命令的包
+  coverage: 'package' | 'essential' | 'dependency' | 'guarded' | 'missing' | 'unknown';
+  coveredBy?: string;
+  note?: string;
+}
+
+export interface ScriptCommandReport {
+  databaseAvailable: boolean;
+  commands: ScriptCommand[];
 }
 
+// 随包脚本的 shebang 解释器
+export interface ShebangInfo {
+  line: string; // 去掉 #! 的首行
+  interpreter: string;
+  program: string; // 实际执行的解释器，使用 env 时为其目标
+  arguments: string[];
+  usesEnv: boolean;
+  language: string;
+  crlf: boolean;
+  providers: string[]; // 本地 dpkg 数据库中提供解释器的包
+  providedBy?: string; // 本包、Essential 包或保障解释器的依赖
+  issues: Issue[];
+}
+
+// Debian changelog 条目
+export interface ChangelogEntry {
+  package: string;
+  version: string;
+  distributions: string[];
+  urgency?: string;
+  maintainer?: string;
+  date?: string;
+  changes: string[];
+}
+
+// Debian 与上游 changelog
+export interface ChangelogInfo {
+  debian?: {
+    path: string;
+    entries: ChangelogEntry[]; // 最新在前，最多 20 条
+    entryCount: number;
+  };
+  upstream?: {
+    path: string;
+    excerpt: string; // 开头若干行
+    mentionsVersion: boolean; // 是否提到当前上游版本
+  };
+  issues: Issue[];
+}
+
+// copyright 文件中的 Files 段
+export interface FilesStanza {
+  patterns: string[];
+  copyright?: string;
+  license: string; // 原始许可证简称表达式
+  spdx: string; // 规范化后的 SPDX 表达式
+  matched: number; // 以该段为最终匹配的安装文件数
+}
+
+// copyright 文件（DEP-5 或自由格式）
+export interface CopyrightInfo {
+  path: string;
+  machineReadable: boolean;
+  upstreamName?: string;
+  source?: string;
+  stanzas: FilesStanza[];
+  licenses: { name: string; spdx: string; hasText: boolean }[]; // 独立 License 段
+  fileLicenses: Record<string, string>; // 安装路径 → SPDX 表达式
+  uncovered: string[]; // 未被任何 Files 段覆盖的文件
+  detectedLicenses: string[]; // 非 DEP-5 时的启发式识别结果
+  text?: string; // 非 DEP-5 时的全文
+  issues: Issue[];
+}
+
+// 语言生态中被打包进来的依赖组件
+export interface BundledComponent {
+  name: string;
+  version?: string;
+  license?: string;
+  path: string; // 清单文件或 jar 的安装路径
+  entry?: string; // jar 内的条目（被 shade 的组件）
+  purl?: string;
+}
+
+// SBOM 导出格式
+export type SbomFormat = 'spdx' | 'cyclonedx';
+
 // 控制文件内容
 export interface ControlFiles {
   control: string;
@@ -95,6 +725,24 @@ export interface ParseResult {
   controlFiles: ControlFiles;
   elfInfo?: Record<string, ELFInfo>;
   desktopInfo?: Record<string, DesktopInfo>;
+  appstreamInfo?: Record<string, AppStreamComponent>;
+  systemdUnits?: Record<string, SystemdUnit>;
+  integrations?: Record<string, IntegrationEntry>;
+  archiveInfo?: Record<string, ArchiveInfo>;
+  debugPackage?: string;
+  debugPackageError?: string; // 调试包无法使用的原因（不影响主包解析）
+  architectureCheck: ArchitectureCheck;
+  packerFindings?: PackerFinding[];
+  secretFindings?: SecretFinding[];
+  certificateInventory?: CertificateInventory;
+  accountInventory?: AccountInventory;
+  alternativesInventory?: AlternativesInventory;
+  scriptCommands?: ScriptCommandReport;
+  scriptInfo?: Record<string, ShebangInfo>; // 按文件路径索引
+  changelog?: ChangelogInfo;
+  copyright?: CopyrightInfo;
+  bundledComponents?: Record<string, BundledComponent[]>; // 按生态（npm、pypi、gem、maven）分组
+  yaraScan?: YaraScan;
   stats: {
     parseTime: number; // 解析耗时（毫秒）
     originalSize: number; // 原始大小（字节）
@@ -102,6 +750,7 @@ export interface ParseResult {
     fileCount: number;
     elfCount: number;
     desktopCount: number;
+    archiveCount: number;
   };
 }
 